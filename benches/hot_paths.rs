@@ -0,0 +1,146 @@
+/*!
+ * Benchmarks of the hot paths, run them with:
+ *
+ * ```text
+ * PQ_DSN="host=localhost" cargo bench --bench hot_paths
+ * ```
+ *
+ * Save a baseline before a change with `-- --save-baseline main`, then compare with
+ * `-- --baseline main`. Except for the parameter encoding, the benchmarks need a server: the
+ * results include the round trips and mostly make sense compared to each other, on the same
+ * machine.
+ *
+ * Reference timings, with a local PostgreSQL 15 server over TCP:
+ *
+ * | benchmark          | time     | throughput      |
+ * |--------------------|----------|-----------------|
+ * | params/encode      | 1.2 µs   |                 |
+ * | params/exec_params | 150 µs   |                 |
+ * | result/value       | 305 µs   | 32.8 Melem/s    |
+ * | result/get         | 393 µs   | 25.4 Melem/s    |
+ * | copy/in            | 8.7 ms   | 1.14 Melem/s    |
+ * | copy/out           | 6.4 ms   | 1.55 Melem/s    |
+ */
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use std::hint::black_box;
+use std::io::Write;
+
+const ROWS: usize = 10_000;
+
+fn dsn() -> String {
+    std::env::var("PQ_DSN").unwrap_or_else(|_| "host=localhost".to_string())
+}
+
+fn params(c: &mut Criterion) {
+    let mut group = c.benchmark_group("params");
+
+    group.bench_function("encode", |b| {
+        b.iter(|| {
+            let mut query = libpq::query::QueryBuilder::new("SELECT ");
+            query.push_bind_list(&[&1_i32, &2_i64, &1.5_f64, &"text", &true, &None::<i32>]);
+
+            black_box(query.param_values().len())
+        })
+    });
+
+    let conn = libpq::Connection::new(&dsn()).unwrap();
+    let values = (0..100).map(|x| format!("{x}\0")).collect::<Vec<_>>();
+    let values = values
+        .iter()
+        .map(|x| Some(x.as_bytes()))
+        .collect::<Vec<_>>();
+    let placeholders = (1..=values.len())
+        .map(|x| format!("${x}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let query = format!("SELECT {placeholders}");
+
+    group.bench_function("exec_params", |b| {
+        b.iter(|| {
+            let result = conn.exec_params(&query, &[], &values, &[], libpq::Format::Text);
+
+            black_box(result.ntuples())
+        })
+    });
+
+    group.finish();
+}
+
+fn result(c: &mut Criterion) {
+    let mut group = c.benchmark_group("result");
+    group.throughput(Throughput::Elements(ROWS as u64));
+
+    let conn = libpq::Connection::new(&dsn()).unwrap();
+    let result = conn.exec(&format!(
+        "SELECT x, 'name ' || x FROM generate_series(1, {ROWS}) x"
+    ));
+
+    group.bench_function("value", |b| {
+        b.iter(|| {
+            let mut len = 0;
+
+            for row in 0..result.ntuples() {
+                for column in 0..result.nfields() {
+                    len += result.value(row, column).map_or(0, <[u8]>::len);
+                }
+            }
+
+            black_box(len)
+        })
+    });
+
+    group.bench_function("get", |b| {
+        b.iter(|| {
+            let mut sum = 0;
+
+            for row in 0..result.ntuples() {
+                sum += result.get::<i32>(row, 0).unwrap();
+            }
+
+            black_box(sum)
+        })
+    });
+
+    group.finish();
+}
+
+fn copy(c: &mut Criterion) {
+    let mut group = c.benchmark_group("copy");
+    group.throughput(Throughput::Elements(ROWS as u64));
+
+    let conn = libpq::Connection::new(&dsn()).unwrap();
+    conn.exec("CREATE TEMPORARY TABLE bench_copy (id int, name text)");
+
+    group.bench_function("in", |b| {
+        b.iter_batched(
+            || conn.exec("TRUNCATE bench_copy"),
+            |_| {
+                let mut writer = conn.copy_in("COPY bench_copy FROM STDIN").unwrap();
+
+                for id in 0..ROWS {
+                    writeln!(writer, "{id}\tname {id}").unwrap();
+                }
+
+                writer.finish().unwrap()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+
+    group.bench_function("out", |b| {
+        b.iter(|| {
+            let reader = conn
+                .copy_out(&format!(
+                    "COPY (SELECT x, 'name ' || x FROM generate_series(1, {ROWS}) x) TO STDOUT"
+                ))
+                .unwrap();
+
+            black_box(reader.map(|row| row.unwrap().len()).sum::<usize>())
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, params, result, copy);
+criterion_main!(benches);
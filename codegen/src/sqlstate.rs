@@ -105,11 +105,21 @@ fn make_type(errors: &BTreeMap<String, Error>, file: &mut BufWriter<File>) -> st
         "
 impl State {{
     /// Creates a `State` from its error code.
+    ///
+    /// Panics on an unknown code, like one raised by an extension or a newer server.
+    #[deprecated(since = \"4.3.0\", note = \"Use State::try_from_code instead\")]
     pub fn from_code(s: &str) -> State {{
-        match s {{
+        Self::try_from_code(s).unwrap_or_else(|| panic!(\"Unknown SQLSTATE code: {{s}}\"))
+    }}
+
+    /// Creates a `State` from its error code, `None` if the code is unknown.
+    pub fn try_from_code(s: &str) -> Option<State> {{
+        let state = match s {{
 {}
-            _ => unreachable!(),
-        }}
+            _ => return None,
+        }};
+
+        Some(state)
     }}
 }}
 ",
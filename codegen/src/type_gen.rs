@@ -330,9 +330,9 @@ pub const {ident}: Type = Type {{
             kind = match ty.kind.as_str() {
                 "A" => format!("Kind::Array({})", ty.element),
                 "B" => "Kind::Boolean".to_string(),
-                "C" => "Kind::Composite".to_string(),
+                "C" => "Kind::Composite(Vec::new())".to_string(),
                 "D" => "Kind::DateTime".to_string(),
-                "E" => "Kind::Enum".to_string(),
+                "E" => "Kind::Enum(Vec::new())".to_string(),
                 "G" => "Kind::Geometric".to_string(),
                 "I" => "Kind::Network".to_string(),
                 "N" => "Kind::Numeric".to_string(),
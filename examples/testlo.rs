@@ -62,10 +62,9 @@ fn pickout(
     let mut nread = 0;
 
     while len - nread > 0 {
-        let mut buf = lobj.read(len - nread)?;
-        let nbytes = buf.len();
-        buf.insert(nbytes, '\0');
-        eprint!(">>> {buf}");
+        let mut buf = vec![0; len - nread];
+        let nbytes = lobj.read(&mut buf)?;
+        eprint!(">>> {}", String::from_utf8_lossy(&buf[..nbytes]));
         nread += nbytes;
         if nbytes == 0 {
             break; /* no more data? */
@@ -85,12 +84,11 @@ fn overwrite(
     let lobj = libpq::lo::open(conn, lobj_id, libpq::lo::Inv::WRITE)?;
 
     lobj.lseek(start, libpq::lo::Seek::Set)?;
-    let mut buf = "X".repeat(len);
-    buf.insert(len - 1, '\0');
+    let buf = vec![b'X'; len];
 
     let mut nwritten = 0;
     while len - nwritten > 0 {
-        let nbytes = lobj.write(&buf[nwritten..len - nwritten])?;
+        let nbytes = lobj.write(&buf[nwritten..])?;
         nwritten += nbytes;
     }
     eprintln!();
@@ -0,0 +1,457 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State<'a> {
+    Normal,
+    Quote { escape: bool },
+    Identifier,
+    LineComment,
+    BlockComment(usize),
+    Dollar(&'a str),
+}
+
+/**
+ * Splits a SQL script into statements on semicolons, ignoring the ones inside quotes,
+ * identifiers, comments and dollar-quoted strings.
+ *
+ * Statements are trimmed and empty ones are skipped. The last statement is returned even if
+ * it isn't terminated by a semicolon (see [`is_complete`]), unless it only contains comments.
+ */
+pub fn split(sql: &str) -> Vec<&str> {
+    let (mut statements, start, _, pending) = scan(sql);
+
+    if pending {
+        statements.push(&sql[start..]);
+    }
+
+    statements
+        .into_iter()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+/**
+ * Returns `true` if `sql` ends with a terminated statement, i.e. there is nothing but blanks
+ * after the last semicolon and no quote or comment left open.
+ */
+pub fn is_complete(sql: &str) -> bool {
+    let (_, _, state, pending) = scan(sql);
+
+    !pending && matches!(state, State::Normal | State::LineComment)
+}
+
+/**
+ * Returns the terminated statements, the start of the last one, the state at the end of `sql`
+ * and whether the last statement contains something other than blanks and comments.
+ */
+fn scan(sql: &str) -> (Vec<&str>, usize, State<'_>, bool) {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut state = State::Normal;
+    let mut start = 0;
+    let mut pending = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        let next = bytes.get(i + 1).copied();
+
+        if state == State::Normal
+            && !c.is_ascii_whitespace()
+            && c != b';'
+            && !(c == b'-' && next == Some(b'-'))
+            && !(c == b'/' && next == Some(b'*'))
+        {
+            pending = true;
+        }
+
+        match state {
+            State::Normal => match c {
+                b'\'' => {
+                    let escape = i > 0
+                        && matches!(bytes[i - 1], b'e' | b'E')
+                        && (i < 2
+                            || !(bytes[i - 2].is_ascii_alphanumeric() || bytes[i - 2] == b'_'));
+                    state = State::Quote { escape };
+                }
+                b'"' => state = State::Identifier,
+                b'-' if next == Some(b'-') => {
+                    state = State::LineComment;
+                    i += 1;
+                }
+                b'/' if next == Some(b'*') => {
+                    state = State::BlockComment(1);
+                    i += 1;
+                }
+                // `$` is allowed in identifiers, like `a$b`.
+                b'$' if i > 0 && is_identifier_byte(bytes[i - 1]) => (),
+                b'$' => {
+                    if let Some(tag) = dollar_tag(&sql[i..]) {
+                        state = State::Dollar(tag);
+                        i += tag.len() - 1;
+                    }
+                }
+                b';' => {
+                    statements.push(&sql[start..i]);
+                    start = i + 1;
+                    pending = false;
+                }
+                _ => (),
+            },
+            State::Quote { escape } => match c {
+                b'\\' if escape => i += 1,
+                b'\'' if next == Some(b'\'') => i += 1,
+                b'\'' => state = State::Normal,
+                _ => (),
+            },
+            State::Identifier => match c {
+                b'"' if next == Some(b'"') => i += 1,
+                b'"' => state = State::Normal,
+                _ => (),
+            },
+            State::LineComment => {
+                if c == b'\n' {
+                    state = State::Normal;
+                }
+            }
+            State::BlockComment(depth) => {
+                if c == b'/' && next == Some(b'*') {
+                    state = State::BlockComment(depth + 1);
+                    i += 1;
+                } else if c == b'*' && next == Some(b'/') {
+                    state = if depth == 1 {
+                        State::Normal
+                    } else {
+                        State::BlockComment(depth - 1)
+                    };
+                    i += 1;
+                }
+            }
+            State::Dollar(tag) => {
+                if bytes[i..].starts_with(tag.as_bytes()) {
+                    state = State::Normal;
+                    i += tag.len() - 1;
+                }
+            }
+        }
+
+        i += 1;
+    }
+
+    (statements, start, state, pending)
+}
+
+/**
+ * Whether `c` can be part of an unquoted identifier: letters, digits, `_`, `$` and any non
+ * ASCII byte.
+ */
+fn is_identifier_byte(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || c == b'_' || c == b'$' || !c.is_ascii()
+}
+
+/**
+ * Returns the dollar quote tag (like `$$` or `$body$`) starting `sql`, if any.
+ */
+fn dollar_tag(sql: &str) -> Option<&str> {
+    let end = sql[1..].find('$')? + 2;
+    let tag = &sql[1..end - 1];
+
+    let valid = tag
+        .chars()
+        .enumerate()
+        .all(|(n, c)| c == '_' || c.is_alphabetic() || (n > 0 && c.is_ascii_digit()));
+
+    if valid {
+        Some(&sql[..end])
+    } else {
+        None
+    }
+}
+
+/**
+ * Formats a result as an aligned table, like psql does.
+ *
+ * Results without tuples are formatted as their command status.
+ */
+pub fn format_table(result: &crate::PQResult) -> String {
+    use std::fmt::Write;
+
+    let has_tuples = match result.status() {
+        crate::Status::TuplesOk | crate::Status::SingleTuple => true,
+        #[cfg(feature = "v17")]
+        crate::Status::TuplesChunk => true,
+        _ => false,
+    };
+
+    if !has_tuples {
+        return match result.error_message() {
+            Ok(Some(message)) if !message.is_empty() => message,
+            _ => result.cmd_status().ok().flatten().unwrap_or_default(),
+        };
+    }
+
+    let nfields = result.nfields();
+    let ntuples = result.ntuples();
+
+    let headers = (0..nfields)
+        .map(|x| result.field_name(x).ok().flatten().unwrap_or_default())
+        .collect::<Vec<_>>();
+    let numerics = (0..nfields)
+        .map(|x| {
+            crate::Type::try_from(result.field_type(x))
+                .is_ok_and(|x| x.kind == crate::types::Kind::Numeric)
+        })
+        .collect::<Vec<_>>();
+    let rows = (0..ntuples)
+        .map(|row| {
+            (0..nfields)
+                .map(|column| {
+                    result
+                        .value(row, column)
+                        .map(String::from_utf8_lossy)
+                        .unwrap_or_default()
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..nfields)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain(std::iter::once(headers[column].chars().count()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut table = String::new();
+
+    let header = headers
+        .iter()
+        .zip(&widths)
+        .map(|(header, width)| format!(" {header:^width$} "))
+        .collect::<Vec<_>>();
+    writeln!(table, "{}", header.join("|").trim_end()).ok();
+
+    let separator = widths
+        .iter()
+        .map(|width| "-".repeat(width + 2))
+        .collect::<Vec<_>>();
+    writeln!(table, "{}", separator.join("+")).ok();
+
+    for row in &rows {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .zip(&numerics)
+            .map(|((value, width), numeric)| {
+                if *numeric {
+                    format!(" {value:>width$} ")
+                } else {
+                    format!(" {value:<width$} ")
+                }
+            })
+            .collect::<Vec<_>>();
+        writeln!(table, "{}", line.join("|").trim_end()).ok();
+    }
+
+    write!(
+        table,
+        "({ntuples} {})",
+        if ntuples == 1 { "row" } else { "rows" }
+    )
+    .ok();
+
+    table
+}
+
+/**
+ * Duration of a command, like psql `\timing` displays it.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Timing {
+    pub elapsed: std::time::Duration,
+}
+
+impl std::fmt::Display for Timing {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Time: {:.3} ms", self.elapsed.as_secs_f64() * 1_000.)
+    }
+}
+
+/**
+ * Runs `f` and measures its duration.
+ */
+pub fn timed<F, T>(f: F) -> (T, Timing)
+where
+    F: FnOnce() -> T,
+{
+    let start = std::time::Instant::now();
+    let value = f();
+
+    (
+        value,
+        Timing {
+            elapsed: start.elapsed(),
+        },
+    )
+}
+
+/**
+ * Words for tab-completion, loaded from the server catalog.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Completions {
+    pub keywords: Vec<String>,
+    pub schemas: Vec<String>,
+    /** Visible tables, views, materialized views and foreign tables. */
+    pub relations: Vec<String>,
+    /** Columns of the visible relations. */
+    pub columns: Vec<String>,
+    /** Visible functions. */
+    pub functions: Vec<String>,
+}
+
+impl Completions {
+    pub fn load(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        let words = |query: &str| -> crate::errors::Result<Vec<String>> {
+            let result = conn.exec_checked(query)?;
+
+            (0..result.ntuples())
+                .map(|row| {
+                    std::str::from_utf8(result.value(row, 0).unwrap_or_default())
+                        .map(ToString::to_string)
+                        .map_err(Into::into)
+                })
+                .collect()
+        };
+
+        Ok(Self {
+            keywords: words("SELECT upper(word) FROM pg_catalog.pg_get_keywords() ORDER BY 1")?,
+            schemas: words("SELECT nspname FROM pg_catalog.pg_namespace ORDER BY 1")?,
+            relations: words(
+                "SELECT relname FROM pg_catalog.pg_class
+                    WHERE relkind IN ('r', 'v', 'm', 'f', 'p') AND pg_catalog.pg_table_is_visible(oid)
+                    ORDER BY 1",
+            )?,
+            columns: words(
+                "SELECT DISTINCT attname FROM pg_catalog.pg_attribute a
+                    JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+                    WHERE a.attnum > 0 AND NOT a.attisdropped
+                        AND c.relkind IN ('r', 'v', 'm', 'f', 'p')
+                        AND pg_catalog.pg_table_is_visible(c.oid)
+                    ORDER BY 1",
+            )?,
+            functions: words(
+                "SELECT DISTINCT proname FROM pg_catalog.pg_proc
+                    WHERE pg_catalog.pg_function_is_visible(oid)
+                    ORDER BY 1",
+            )?,
+        })
+    }
+
+    /**
+     * Returns all the words starting with `prefix`, case insensitively.
+     */
+    pub fn matches(&self, prefix: &str) -> Vec<&str> {
+        let prefix = prefix.to_lowercase();
+
+        let mut matches = self
+            .keywords
+            .iter()
+            .chain(&self.schemas)
+            .chain(&self.relations)
+            .chain(&self.columns)
+            .chain(&self.functions)
+            .filter(|x| x.to_lowercase().starts_with(&prefix))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        matches.sort_unstable();
+        matches.dedup();
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn split() {
+        let sql = r#"
+            SELECT 1; SELECT ';' AS "a;b";
+            -- comment; still comment
+            SELECT E'\';', $$ ; $$, $body$ $$; $body$;;
+            /* nested /* ; */ ; */ SELECT $1
+        "#;
+
+        assert_eq!(
+            crate::cli::split(sql),
+            [
+                "SELECT 1",
+                r#"SELECT ';' AS "a;b""#,
+                "-- comment; still comment\n            SELECT E'\\';', $$ ; $$, $body$ $$; $body$",
+                "/* nested /* ; */ ; */ SELECT $1",
+            ]
+        );
+
+        assert_eq!(
+            crate::cli::split("SELECT $$é;$$; SELECT $é$ $$; $é$; SELECT 2"),
+            ["SELECT $$é;$$", "SELECT $é$ $$; $é$", "SELECT 2"]
+        );
+        assert_eq!(
+            crate::cli::split("SELECT a$b, é$x$; SELECT 2"),
+            ["SELECT a$b, é$x$", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn is_complete() {
+        assert_eq!(crate::cli::split("SELECT 1; -- comment"), ["SELECT 1"]);
+
+        assert!(crate::cli::is_complete(""));
+        assert!(crate::cli::is_complete("SELECT 1;"));
+        assert!(crate::cli::is_complete("SELECT 1; -- comment"));
+        assert!(!crate::cli::is_complete("SELECT 1"));
+        assert!(!crate::cli::is_complete("SELECT ';"));
+        assert!(!crate::cli::is_complete("SELECT $$;"));
+        assert!(!crate::cli::is_complete("SELECT 1; /* ;"));
+    }
+
+    #[test]
+    fn format_table() {
+        let conn = crate::test::new_conn();
+
+        let result = conn.exec("SELECT 1 AS id, 'foo' AS name UNION SELECT 10, NULL ORDER BY 1");
+        assert_eq!(
+            crate::cli::format_table(&result),
+            " id | name
+----+------
+  1 | foo
+ 10 |
+(2 rows)"
+        );
+
+        let result = conn.exec("SET application_name = 'cli'");
+        assert_eq!(crate::cli::format_table(&result), "SET");
+    }
+
+    #[test]
+    fn timed() {
+        let (_, timing) =
+            crate::cli::timed(|| std::thread::sleep(std::time::Duration::from_millis(10)));
+
+        assert!(timing.elapsed >= std::time::Duration::from_millis(10));
+        assert!(timing.to_string().starts_with("Time: "));
+    }
+
+    #[test]
+    fn completions() {
+        let conn = crate::test::new_conn();
+        let completions = crate::cli::Completions::load(&conn).unwrap();
+
+        assert!(completions.matches("sel").contains(&"SELECT"));
+        assert!(completions.matches("pg_cat").contains(&"pg_catalog"));
+        assert!(completions.matches("pg_sleep").contains(&"pg_sleep"));
+    }
+}
@@ -9,6 +9,7 @@ impl Connection {
      * [PQsendQuery](https://www.postgresql.org/docs/current/libpq-async.html#LIBPQ-PQSENDQUERY).
      */
     pub fn send_query(&self, command: &str) -> crate::errors::Result {
+        let command = &self.commented(command);
         log::trace!("Sending query '{command}'");
 
         let c_command = crate::ffi::to_cstr(command);
@@ -16,6 +17,7 @@ impl Connection {
         let success = unsafe { pq_sys::PQsendQuery(self.into(), c_command.as_ptr()) };
 
         if success == 1 {
+            self.update_stats(|stats| stats.queries_sent += 1);
             Ok(())
         } else {
             self.error()
@@ -36,9 +38,10 @@ impl Connection {
         param_formats: &[crate::Format],
         result_format: crate::Format,
     ) -> crate::errors::Result {
-        let (values, formats, lengths) = Self::transform_params(param_values, param_formats);
+        let (values, formats, lengths, _buffers) = Self::transform_params(param_values, param_formats);
+        let command = &self.commented(command);
 
-        Self::trace_query("Sending", command, param_types, param_values, param_formats);
+        self.trace_query("Sending", command, param_types, param_values, param_formats);
 
         let c_command = crate::ffi::to_cstr(command);
 
@@ -68,12 +71,35 @@ impl Connection {
         };
 
         if success == 1 {
+            self.update_stats(|stats| stats.queries_sent += 1);
             Ok(())
         } else {
             self.error()
         }
     }
 
+    /**
+     * Submits a command with typed parameters to the server without waiting for the result.
+     *
+     * See `libpq::Connection::exec_typed`.
+     */
+    pub fn send_query_typed(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result {
+        let params = crate::types::Params::new(params);
+        params.check()?;
+
+        self.send_query_params(
+            command,
+            &self.param_types(&params),
+            &params.values(),
+            &params.formats,
+            crate::Format::Text,
+        )
+    }
+
     /**
      * Sends a request to create a prepared statement with the given parameters, without waiting
      * for completion.
@@ -88,7 +114,7 @@ impl Connection {
         param_types: &[crate::Oid],
     ) -> crate::errors::Result {
         let prefix = format!("Sending prepare {}", name.unwrap_or("anonymous"));
-        Self::trace_query(&prefix, query, param_types, &[], &[]);
+        self.trace_query(&prefix, query, param_types, &[], &[]);
 
         let c_name = crate::ffi::to_cstr(name.unwrap_or_default());
         let c_query = crate::ffi::to_cstr(query);
@@ -104,6 +130,7 @@ impl Connection {
         };
 
         if success == 1 {
+            self.update_stats(|stats| stats.queries_sent += 1);
             Ok(())
         } else {
             self.error()
@@ -123,9 +150,9 @@ impl Connection {
         result_format: crate::Format,
     ) -> crate::errors::Result {
         let prefix = format!("Send {} prepared query", name.unwrap_or("anonymous"));
-        Self::trace_query(&prefix, "", &[], param_values, param_formats);
+        self.trace_query(&prefix, "", &[], param_values, param_formats);
 
-        let (values, formats, lengths) = Self::transform_params(param_values, param_formats);
+        let (values, formats, lengths, _buffers) = Self::transform_params(param_values, param_formats);
 
         let c_name = crate::ffi::to_cstr(name.unwrap_or_default());
 
@@ -150,6 +177,7 @@ impl Connection {
         };
 
         if success == 1 {
+            self.update_stats(|stats| stats.queries_sent += 1);
             Ok(())
         } else {
             self.error()
@@ -205,14 +233,69 @@ impl Connection {
      */
     pub fn result(&self) -> Option<crate::PQResult> {
         let raw = unsafe { pq_sys::PQgetResult(self.into()) };
+        // The server reports the changed parameters just before ReadyForQuery, read with the
+        // last, null, result.
+        self.check_parameters();
 
         if raw.is_null() {
             None
         } else {
-            Some(raw.into())
+            let result: crate::PQResult = raw.into();
+            Self::trace_result(&result);
+            Some(result)
         }
     }
 
+    /**
+     * Returns an iterator draining all the pending results, for example the results of each
+     * statement of a multi-statement `libpq::Connection::send_query`.
+     *
+     * The iterator blocks while waiting for results and stops after a COPY result, since the
+     * data transfer must be handled before the next results are available.
+     */
+    pub fn results(&self) -> impl Iterator<Item = crate::PQResult> + '_ {
+        let mut copy = false;
+
+        std::iter::from_fn(move || {
+            if copy {
+                return None;
+            }
+
+            let result = self.result()?;
+            copy = matches!(
+                result.status(),
+                crate::Status::CopyIn | crate::Status::CopyOut | crate::Status::CopyBoth
+            );
+
+            Some(result)
+        })
+    }
+
+    /**
+     * Waits for all the results of the current command, in order, turning error results into
+     * `Err`.
+     *
+     * Unlike `libpq::Connection::exec_checked`, errors don't stop the collection: a statement
+     * failing in the middle of a multi-statement query is reported in place, followed by the
+     * results still sent by the server. On a lost connection, the last item is always an error.
+     *
+     * Like `libpq::Connection::results`, the collection stops after a COPY result.
+     */
+    pub fn collect_results(&self) -> Vec<crate::errors::Result<crate::PQResult>> {
+        let mut results = self
+            .results()
+            .map(|result| self.check_result(result))
+            .collect::<Vec<_>>();
+
+        if self.status() == crate::connection::Status::Bad && !matches!(results.last(), Some(Err(_)))
+        {
+            let message = self.error_message().unwrap_or_default().trim_end();
+            results.push(Err(crate::errors::Error::Backend(message.to_string())));
+        }
+
+        results
+    }
+
     /**
      * If input is available from the server, consume it.
      *
@@ -240,6 +323,31 @@ impl Connection {
         unsafe { pq_sys::PQisBusy(self.into()) == 1 }
     }
 
+    /**
+     * Returns the socket readiness to wait for before calling libpq again, `None` when
+     * `libpq::Connection::result` can be called without blocking.
+     *
+     * Queued output is sent first: `Interest::Write` means it could not be sent entirely, then
+     * call this method again once the socket is writable. With `Interest::Read`, call
+     * `libpq::Connection::consume_input` once the socket is readable.
+     *
+     * The socket is available with `libpq::Connection::socket`, or the `AsRawFd` and
+     * `AsRawSocket` implementations, to register the connection in an event loop.
+     */
+    pub fn poll_interest(&self) -> crate::errors::Result<Option<crate::poll::Interest>> {
+        match unsafe { pq_sys::PQflush(self.into()) } {
+            0 => (),
+            1 => return Ok(Some(crate::poll::Interest::Write)),
+            _ => return self.error(),
+        }
+
+        if self.is_busy() {
+            Ok(Some(crate::poll::Interest::Read))
+        } else {
+            Ok(None)
+        }
+    }
+
     /**
      * Sets the nonblocking status of the connection.
      *
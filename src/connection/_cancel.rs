@@ -12,4 +12,13 @@ impl Connection {
     pub fn cancel(&self) -> crate::connection::Cancel {
         unsafe { pq_sys::PQgetCancel(self.into()) }.into()
     }
+
+    /**
+     * Creates a handle to cancel the commands of this connection from another thread.
+     *
+     * See `libpq::connection::CancelToken`.
+     */
+    pub fn cancel_token(&self) -> crate::errors::Result<crate::connection::CancelToken> {
+        crate::connection::CancelToken::new(self)
+    }
 }
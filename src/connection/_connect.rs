@@ -21,6 +21,10 @@ impl Connection {
     /**
      * Makes a new connection to the database server.
      *
+     * When `expand_dbname` is `true` and `dbname` is a connection string or an URI, it is
+     * expanded into individual parameters. The other parameters take precedence over the
+     * expanded ones.
+     *
      * See [PQconnectdbParams](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-PQCONNECTDBPARAMS).
      */
     pub fn with_params(
@@ -29,8 +33,9 @@ impl Connection {
     ) -> crate::errors::Result<Self> {
         log::trace!("Connecting with params {params:?}");
 
-        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&params.keys().collect::<Vec<_>>());
-        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&params.values().collect::<Vec<_>>());
+        let (keywords, values) = Self::split_params(params);
+        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&keywords);
+        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&values);
 
         unsafe {
             pq_sys::PQconnectdbParams(ptr_keywords.as_ptr(), ptr_values.as_ptr(), expand_dbname as i32)
@@ -38,6 +43,198 @@ impl Connection {
         .try_into()
     }
 
+    /**
+     * Makes a new connection to the database server from a [`Config`](crate::connection::Config).
+     *
+     * See [PQconnectdbParams](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-PQCONNECTDBPARAMS).
+     */
+    pub fn connect(config: &crate::connection::Config) -> crate::errors::Result<Self> {
+        let config = config.resolved()?;
+
+        #[cfg(unix)]
+        if let Some(handshake_timeout) = config.handshake_timeout {
+            return Self::connect_each(&config, handshake_timeout);
+        }
+
+        Self::connect_with(&config, |keywords, values| unsafe {
+            pq_sys::PQconnectdbParams(keywords, values, 0)
+        })
+    }
+
+    /**
+     * Tries the hosts one by one, libpq can't be told to give up on a host when its handshake
+     * is too long.
+     */
+    #[cfg(unix)]
+    fn connect_each(
+        config: &crate::connection::Config,
+        handshake_timeout: std::time::Duration,
+    ) -> crate::errors::Result<Self> {
+        // libpq looks for a standby first, then for any server.
+        let passes = match config.target_session_attrs {
+            Some(crate::connection::TargetSessionAttrs::PreferStandby) => vec![
+                Some(crate::connection::TargetSessionAttrs::Standby),
+                Some(crate::connection::TargetSessionAttrs::Any),
+            ],
+            attrs => vec![attrs],
+        };
+
+        let mut attempts = Vec::new();
+        let mut timed_out = false;
+
+        for target_session_attrs in passes {
+            for host in config.split_hosts() {
+                let host = crate::connection::Config {
+                    target_session_attrs,
+                    ..host
+                };
+
+                let conn = Self::connect_with(&host, |keywords, values| unsafe {
+                    pq_sys::PQconnectStartParams(keywords, values, 0)
+                })
+                .and_then(|conn| conn.complete(host.connect_timeout, handshake_timeout));
+
+                match conn {
+                    Ok(conn) => return Ok(conn),
+                    Err(err) if !err.connect_attempts().is_empty() => {
+                        attempts.extend(err.connect_attempts());
+                        timed_out = false;
+                    }
+                    Err(err) => {
+                        log::trace!("Connection to {:?} failed: {err}", host.host);
+
+                        attempts.push(crate::errors::ConnectAttempt {
+                            host: host.host.clone(),
+                            address: host.hostaddr.clone(),
+                            port: host.port,
+                            message: err.to_string(),
+                        });
+                        timed_out = err == crate::errors::Error::Timeout;
+                    }
+                }
+            }
+        }
+
+        // Like with a single host, a timeout of the last one is reported as such.
+        if timed_out {
+            Err(crate::errors::Error::Timeout)
+        } else {
+            Err(crate::errors::Error::Connect(attempts))
+        }
+    }
+
+    /**
+     * Calls `f` with the keywords and values of `config`, wiping the credentials once sent.
+     */
+    fn connect_with(
+        config: &crate::connection::Config,
+        f: impl FnOnce(*const *const raw::c_char, *const *const raw::c_char) -> *mut pq_sys::PGconn,
+    ) -> crate::errors::Result<Self> {
+        let (keywords, values): (Vec<_>, Vec<_>) = config.params().into_iter().unzip();
+
+        log::trace!("Connecting with params {keywords:?}");
+
+        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&keywords);
+        // Built here rather than with `vec_to_nta`, to wipe the credentials once sent.
+        let c_values = values
+            .iter()
+            .map(|x| crate::ffi::to_cstr(x))
+            .collect::<Vec<_>>();
+        let mut ptr_values = c_values.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
+        ptr_values.push(std::ptr::null());
+
+        let conn = f(ptr_keywords.as_ptr(), ptr_values.as_ptr()).try_into();
+
+        crate::ffi::wipe(c_values);
+
+        conn
+    }
+
+    /**
+     * Like `libpq::Connection::connect`, retrying according to `policy`.
+     *
+     * With the default policy, any connection failure is retried, including a rejected
+     * authentication: use `libpq::retry::Policy::retry_on` to be more selective.
+     */
+    pub fn connect_with_retry(
+        config: &crate::connection::Config,
+        policy: &crate::retry::Policy,
+    ) -> crate::errors::Result<Self> {
+        policy.run(|_| Self::connect(config))
+    }
+
+    /**
+     * Drives a started connection to its end, with `connect_timeout` while the TCP connection
+     * is established, then `handshake_timeout`.
+     */
+    #[cfg(unix)]
+    fn complete(
+        self,
+        connect_timeout: Option<std::time::Duration>,
+        handshake_timeout: std::time::Duration,
+    ) -> crate::errors::Result<Self> {
+        let mut phase = None;
+        let mut deadline = None;
+        let mut hint = self.hint(crate::poll::Status::Writing);
+
+        loop {
+            match hint.status {
+                crate::poll::Status::Ok => return Ok(self),
+                crate::poll::Status::Failed => return self.error(),
+                _ => (),
+            }
+
+            let connecting = self.status() == crate::connection::Status::Started;
+
+            // A new socket means libpq moved on to the next host.
+            if phase != Some((hint.socket, connecting)) {
+                phase = Some((hint.socket, connecting));
+
+                let timeout = if connecting {
+                    connect_timeout
+                } else {
+                    Some(handshake_timeout)
+                };
+                deadline = timeout.map(|x| std::time::Instant::now() + x);
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(std::time::Instant::now())
+                {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => {
+                        log::trace!("Connection timeout in {:?} status", self.status());
+                        return Err(crate::errors::Error::Timeout);
+                    }
+                },
+                None => None,
+            };
+
+            // On timeout, the deadline check above ends the loop.
+            if hint.wait(remaining)? {
+                hint = self.poll_hint();
+            }
+        }
+    }
+
+    /**
+     * Opens a new connection to the database server, using the same connection options as
+     * this one.
+     *
+     * A `Connection` owns its underlying libpq connection, so it can't be cloned: this is the
+     * way to get a second, independent, connection to the same server.
+     */
+    pub fn try_clone(&self) -> crate::errors::Result<Self> {
+        let info = self.info()?;
+
+        let params = info
+            .values()
+            .filter_map(|x| x.val.as_deref().map(|val| (x.keyword.as_str(), val)))
+            .collect::<HashMap<_, _>>();
+
+        Self::with_params(&params, false)
+    }
+
     /**
      * Make a connection to the database server in a nonblocking manner.
      *
@@ -54,6 +251,8 @@ impl Connection {
     /**
      * Make a connection to the database server in a nonblocking manner.
      *
+     * See `libpq::Connection::with_params` for `expand_dbname`.
+     *
      * See [PQconnectStartParams](https://www.postgresql.org/docs/current/libpq-connect.html#LIBPQ-PQCONNECTSTARTPARAMS).
      */
     pub fn start_params(
@@ -62,8 +261,9 @@ impl Connection {
     ) -> crate::errors::Result<Self> {
         log::trace!("Starting connection with params {params:?}");
 
-        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&params.keys().collect::<Vec<_>>());
-        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&params.values().collect::<Vec<_>>());
+        let (keywords, values) = Self::split_params(params);
+        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&keywords);
+        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&values);
 
         unsafe {
             pq_sys::PQconnectStartParams(ptr_keywords.as_ptr(), ptr_values.as_ptr(), expand_dbname as i32)
@@ -71,6 +271,19 @@ impl Connection {
         .try_into()
     }
 
+    /**
+     * Splits `params` into keywords and values, `dbname` first.
+     *
+     * libpq only lets the parameters following `dbname` override the ones of an expanded
+     * connection string, the order of a `HashMap` is arbitrary.
+     */
+    fn split_params<K: AsRef<str>, V>(params: &HashMap<K, V>) -> (Vec<&K>, Vec<&V>) {
+        let mut params = params.iter().collect::<Vec<_>>();
+        params.sort_by_key(|(keyword, _)| keyword.as_ref() != "dbname");
+
+        params.into_iter().unzip()
+    }
+
     /**
      * Makes a new connection to the database server.
      *
@@ -130,6 +343,14 @@ impl Connection {
         unsafe { pq_sys::PQconnectPoll(self.into()) }.into()
     }
 
+    /**
+     * Like `libpq::Connection::poll`, but also returns the socket and direction to wait on before
+     * the next call.
+     */
+    pub fn poll_hint(&self) -> crate::poll::Hint {
+        self.hint(self.poll())
+    }
+
     /**
      * Resets the communication channel to the server.
      *
@@ -156,6 +377,21 @@ impl Connection {
         unsafe { pq_sys::PQresetPoll(self.into()) }.into()
     }
 
+    /**
+     * Like `libpq::Connection::reset_poll`, but also returns the socket and direction to wait on
+     * before the next call.
+     */
+    pub fn reset_poll_hint(&self) -> crate::poll::Hint {
+        self.hint(self.reset_poll())
+    }
+
+    fn hint(&self, status: crate::poll::Status) -> crate::poll::Hint {
+        crate::poll::Hint {
+            status,
+            socket: self.socket().ok(),
+        }
+    }
+
     /**
      * Reports the status of the server.
      *
@@ -172,8 +408,9 @@ impl Connection {
     ) -> crate::ping::Status {
         log::trace!("Ping with params {params:?}");
 
-        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&params.keys().collect::<Vec<_>>());
-        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&params.values().collect::<Vec<_>>());
+        let (keywords, values) = Self::split_params(params);
+        let (_c_keywords, ptr_keywords) = crate::ffi::vec_to_nta(&keywords);
+        let (_c_values, ptr_values) = crate::ffi::vec_to_nta(&values);
 
         unsafe { pq_sys::PQpingParams(ptr_keywords.as_ptr(), ptr_values.as_ptr(), expand_dbname as i32) }
             .into()
@@ -205,26 +442,48 @@ impl Connection {
      */
     pub fn info(&self) -> crate::errors::Result<HashMap<String, crate::connection::Info>> {
         let mut infos = HashMap::new();
+        let raw = unsafe { pq_sys::PQconninfo(self.into()) };
 
-        unsafe {
-            let mut i = 0;
-            let raw = pq_sys::PQconninfo(self.into());
+        if raw.is_null() {
+            return Err(crate::errors::Error::Backend(
+                "Unable to retrieve the connection options".to_string(),
+            ));
+        }
 
-            loop {
-                let current = raw.offset(i);
+        let mut i = 0;
 
-                if (*current).keyword.is_null() {
-                    break;
-                }
+        let result = loop {
+            let current = unsafe { raw.offset(i) };
 
-                let info: crate::connection::Info = current.try_into()?;
-                infos.insert(info.keyword.clone(), info);
-                i += 1;
+            if unsafe { (*current).keyword.is_null() } {
+                break Ok(());
             }
 
-            pq_sys::PQconninfoFree(raw);
-        }
+            match crate::connection::Info::try_from(current) {
+                Ok(info) => infos.insert(info.keyword.clone(), info),
+                Err(err) => break Err(err),
+            };
+
+            i += 1;
+        };
+
+        unsafe { pq_sys::PQconninfoFree(raw) };
+
+        result.map(|_| infos)
+    }
+
+    /**
+     * Returns the value of the connection option `keyword`, `None` if it's unset.
+     *
+     * See `libpq::Connection::info`.
+     */
+    pub fn info_value(&self, keyword: &str) -> crate::errors::Result<Option<String>> {
+        let mut infos = self.info()?;
+
+        let info = infos
+            .remove(keyword)
+            .ok_or_else(|| crate::errors::Error::Backend(format!("Unknow option '{keyword}'")))?;
 
-        Ok(infos)
+        Ok(info.val)
     }
 }
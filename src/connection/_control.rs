@@ -38,4 +38,45 @@ impl Connection {
 
         unsafe { pq_sys::PQsetErrorVerbosity(self.into(), verbosity.into()) }.into()
     }
+
+    /**
+     * Returns the current `statement_timeout` of the session, `None` if disabled.
+     *
+     * See [statement_timeout](https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-STATEMENT-TIMEOUT).
+     */
+    pub fn statement_timeout(&self) -> crate::errors::Result<Option<std::time::Duration>> {
+        let result = self.exec_checked(
+            "SELECT setting FROM pg_catalog.pg_settings WHERE name = 'statement_timeout'",
+        )?;
+
+        let ms = std::str::from_utf8(result.value(0, 0).unwrap_or(b"0"))?.parse::<u64>()?;
+
+        if ms == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(std::time::Duration::from_millis(ms)))
+        }
+    }
+
+    /**
+     * Aborts any statement that takes more than `timeout` for the rest of the session, or
+     * disables the timeout with `None`.
+     *
+     * To apply it from the connection startup, use
+     * [`Config::statement_timeout`](crate::connection::Config::statement_timeout).
+     *
+     * See [statement_timeout](https://www.postgresql.org/docs/current/runtime-config-client.html#GUC-STATEMENT-TIMEOUT).
+     */
+    pub fn set_statement_timeout(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> crate::errors::Result {
+        let ms = timeout.map(|x| x.as_millis().max(1)).unwrap_or_default();
+
+        log::trace!("Setting statement timeout to {ms}ms");
+
+        self.exec_checked(&format!("SET statement_timeout = {ms}"))?;
+
+        Ok(())
+    }
 }
@@ -3,6 +3,50 @@
  * Command](https://www.postgresql.org/docs/current/libpq-copy.html)
  */
 impl Connection {
+    /**
+     * Executes a `COPY … FROM STDIN` command and returns a writer for its data.
+     *
+     * See `libpq::connection::CopyInWriter`.
+     */
+    pub fn copy_in(&self, query: &str) -> crate::errors::Result<crate::connection::CopyInWriter<'_>> {
+        let result = self.check_result(self.exec(query))?;
+
+        if result.status() != crate::Status::CopyIn {
+            self.ensure_idle()?;
+
+            return Err(crate::errors::Error::Backend(format!(
+                "Expected a COPY FROM STDIN command, got {:?}",
+                result.status()
+            )));
+        }
+
+        let format = crate::connection::CopyFormatInfo::from_result(&result);
+
+        Ok(crate::connection::CopyInWriter::new(self, format))
+    }
+
+    /**
+     * Executes a `COPY … TO STDOUT` command and returns a reader for its data.
+     *
+     * See `libpq::connection::CopyOutReader`.
+     */
+    pub fn copy_out(&self, query: &str) -> crate::errors::Result<crate::connection::CopyOutReader<'_>> {
+        let result = self.check_result(self.exec(query))?;
+
+        if result.status() != crate::Status::CopyOut {
+            self.ensure_idle()?;
+
+            return Err(crate::errors::Error::Backend(format!(
+                "Expected a COPY TO STDOUT command, got {:?}",
+                result.status()
+            )));
+        }
+
+        let format = crate::connection::CopyFormatInfo::from_result(&result);
+
+        Ok(crate::connection::CopyOutReader::new(self, format))
+    }
+
     /**
      * Sends data to the server during `libpq::Status::CopyIn` state.
      *
@@ -10,7 +54,7 @@ impl Connection {
      * [PQputCopyData](https://www.postgresql.org/docs/current/libpq-copy.html#LIBPQ-PQPUTCOPYDATA).
      */
     pub fn put_copy_data(&self, buffer: &[u8]) -> crate::errors::Result {
-        log::trace!("Sending copy data");
+        log::trace!("Sending copy data ({} bytes)", buffer.len());
 
         let success = unsafe {
             pq_sys::PQputCopyData(
@@ -23,7 +67,10 @@ impl Connection {
         match success {
             -1 => self.error(),
             0 => Err(crate::errors::Error::Backend("Full buffers".to_string())),
-            1 => Ok(()),
+            1 => {
+                self.count_copy_sent(buffer.len());
+                Ok(())
+            }
             _ => self.error(),
         }
     }
@@ -35,7 +82,10 @@ impl Connection {
      * [PQputCopyEnd](https://www.postgresql.org/docs/current/libpq-copy.html#LIBPQ-PQPUTCOPYEND).
      */
     pub fn put_copy_end(&self, errormsg: Option<&str>) -> crate::errors::Result {
-        log::trace!("End of copy");
+        match errormsg {
+            Some(errormsg) => log::trace!("Abort copy: {errormsg}"),
+            None => log::trace!("End of copy"),
+        }
 
         let cstr = errormsg.map(crate::ffi::to_cstr);
         let ptr = if let Some(ref cstr) = cstr {
@@ -69,9 +119,127 @@ impl Connection {
 
         match success {
             -2 => self.error(),
-            -1 => Err(crate::errors::Error::Backend("COPY is done".to_string())),
+            -1 => {
+                log::trace!("End of copy data");
+                Err(crate::errors::Error::Backend("COPY is done".to_string()))
+            }
             0 => Err(crate::errors::Error::Backend("COPY still in progress".to_string())),
-            nbytes => Ok(PqBytes::from_raw(ptr as *const u8, nbytes as usize)),
+            nbytes => {
+                log::trace!("Receiving copy data ({nbytes} bytes)");
+                self.count_copy_received(nbytes as usize);
+
+                Ok(PqBytes::from_raw(ptr as *const u8, nbytes as usize))
+            }
+        }
+    }
+
+    /**
+     * Reads a newline-terminated line of a `COPY … TO STDOUT` into `line`, without the newline.
+     *
+     * Returns `false`, leaving `line` untouched, at the end-of-data marker `\.`: call
+     * `libpq::Connection::endcopy` then.
+     *
+     * See [PQgetline](https://www.postgresql.org/docs/current/libpq-copy.html#LIBPQ-PQGETLINE).
+     */
+    #[deprecated(since = "4.3.0", note = "Use Connection::copy_out instead")]
+    pub fn get_line(&self, line: &mut String) -> crate::errors::Result<bool> {
+        let mut buffer = vec![0_u8; 8 * 1024];
+        let mut bytes = Vec::new();
+
+        loop {
+            let status = unsafe {
+                pq_sys::PQgetline(
+                    self.into(),
+                    buffer.as_mut_ptr() as *mut raw::c_char,
+                    buffer.len() as i32,
+                )
+            };
+
+            if status == -1 {
+                return self.error();
+            }
+
+            let len = buffer.iter().position(|x| *x == b'\0').unwrap_or_default();
+            bytes.extend_from_slice(&buffer[..len]);
+
+            // 1 means the buffer is full before the end of the line.
+            if status == 0 {
+                break;
+            }
+        }
+
+        if bytes == b"\\." {
+            return Ok(false);
+        }
+
+        self.count_copy_received(bytes.len() + 1);
+        line.push_str(&String::from_utf8_lossy(&bytes));
+
+        Ok(true)
+    }
+
+    /**
+     * Sends `line` during a `COPY … FROM STDIN`. Unlike `libpq::Connection::get_line`, the
+     * newline has to be part of `line`; end the data with the line `\.\n` then call
+     * `libpq::Connection::endcopy`.
+     *
+     * See [PQputline](https://www.postgresql.org/docs/current/libpq-copy.html#LIBPQ-PQPUTLINE).
+     */
+    #[deprecated(since = "4.3.0", note = "Use Connection::copy_in instead")]
+    pub fn put_line(&self, line: &str) -> crate::errors::Result {
+        let success = unsafe {
+            pq_sys::PQputnbytes(
+                self.into(),
+                line.as_ptr() as *const raw::c_char,
+                line.len() as i32,
+            )
+        };
+
+        if success == 0 {
+            self.count_copy_sent(line.len());
+            Ok(())
+        } else {
+            self.error()
+        }
+    }
+
+    /**
+     * Synchronizes with the server at the end of a COPY started with `libpq::Connection::exec`
+     * and driven with `get_line` or `put_line`.
+     *
+     * See [PQendcopy](https://www.postgresql.org/docs/current/libpq-copy.html#LIBPQ-PQENDCOPY).
+     */
+    #[deprecated(
+        since = "4.3.0",
+        note = "Use Connection::copy_in or Connection::copy_out instead"
+    )]
+    pub fn endcopy(&self) -> crate::errors::Result {
+        if unsafe { pq_sys::PQendcopy(self.into()) } == 0 {
+            return Ok(());
         }
+
+        // Once `get_line` reached the end of a COPY OUT, libpq considers the COPY done and
+        // only the command result is left to read.
+        let mut status = self.error();
+
+        while let Some(result) = self.result() {
+            status = self.check_result(result).map(|_| ());
+        }
+
+        status
+    }
+
+    pub(crate) fn count_copy_sent(&self, len: usize) {
+        self.update_stats(|stats| {
+            stats.copy_bytes_sent += len as u64;
+            stats.copy_messages_sent += 1;
+        });
+    }
+
+    pub(crate) fn count_copy_received(&self, len: usize) {
+        self.update_stats(|stats| {
+            stats.copy_bytes_received += len as u64;
+            stats.copy_messages_received += 1;
+        });
     }
 }
@@ -0,0 +1,35 @@
+/**
+ * [Event System](https://www.postgresql.org/docs/current/libpq-events.html)
+ */
+impl Connection {
+    /**
+     * Registers the event procedure allowing to attach data to the results of this connection,
+     * with `libpq::PQResult::set_instance_data`.
+     *
+     * Only the results created after the registration accept data. Registering twice returns an
+     * error.
+     *
+     * See
+     * [PQregisterEventProc](https://www.postgresql.org/docs/current/libpq-events.html#LIBPQ-PQREGISTEREVENTPROC).
+     */
+    pub fn register_result_data(&self) -> crate::errors::Result {
+        let name = crate::ffi::to_cstr("libpq.rs result data");
+
+        let success = unsafe {
+            pq_sys::PQregisterEventProc(
+                self.into(),
+                Some(crate::result::event_proc),
+                name.as_ptr(),
+                std::ptr::null_mut(),
+            )
+        };
+
+        if success == 1 {
+            Ok(())
+        } else {
+            Err(crate::errors::Error::Backend(
+                "Unable to register the result data event procedure".to_string(),
+            ))
+        }
+    }
+}
@@ -8,10 +8,111 @@ impl Connection {
      * See [PQexec](https://www.postgresql.org/docs/current/libpq-exec.html#LIBPQ-PQEXEC).
      */
     pub fn exec(&self, query: &str) -> crate::PQResult {
+        let query = &self.commented(query);
         log::trace!("Execute query '{query}'");
 
         let c_query = crate::ffi::to_cstr(query);
-        unsafe { pq_sys::PQexec(self.into(), c_query.as_ptr()) }.into()
+        let start = std::time::Instant::now();
+        let result = unsafe { pq_sys::PQexec(self.into(), c_query.as_ptr()) }.into();
+        self.update_stats(|stats| stats.queries_sent += 1);
+        Self::trace_result(&result);
+        self.check_parameters();
+        self.log_query(query, None, &[], &[], &[], start, &result);
+
+        result
+    }
+
+    /**
+     * Same as `libpq::Connection::exec`, but returns an `Error::Query` error for
+     * `libpq::Status::FatalError` and `libpq::Status::BadResponse` results.
+     */
+    pub fn exec_checked(&self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        self.check_result(self.exec(query))
+    }
+
+    /**
+     * Starts a transaction block, see [`Transaction`](crate::transaction::Transaction).
+     *
+     * Returns an error if a transaction is already in progress.
+     */
+    pub fn transaction(&self) -> crate::errors::Result<crate::transaction::Transaction<'_>> {
+        crate::transaction::Transaction::begin(self)
+    }
+
+    /**
+     * Submits a command to the server and waits for the result, requesting its cancellation as
+     * soon as `token` becomes `true`.
+     *
+     * The token is checked between socket wakeups (at least every 100ms), so it can safely be
+     * set from a signal handler, for example to handle Ctrl-C in a command line tool.
+     *
+     * Like `libpq::Connection::exec`, this returns the last result: a canceled command returns
+     * a `libpq::Status::FatalError` result.
+     */
+    #[cfg(unix)]
+    pub fn exec_interruptible(
+        &self,
+        query: &str,
+        token: &std::sync::atomic::AtomicBool,
+    ) -> crate::errors::Result<crate::PQResult> {
+        log::trace!("Execute interruptible query '{query}'");
+
+        self.send_query(query)?;
+
+        let socket = self.socket()?;
+        let mut canceled = false;
+        let mut last = None;
+
+        loop {
+            while !self.is_busy() {
+                let Some(result) = self.result() else {
+                    return match last {
+                        Some(result) => Ok(result),
+                        None => self.error(),
+                    };
+                };
+
+                if matches!(
+                    result.status(),
+                    crate::Status::CopyIn | crate::Status::CopyOut | crate::Status::CopyBoth
+                ) {
+                    return Ok(result);
+                }
+
+                last = Some(result);
+            }
+
+            if !canceled && token.load(std::sync::atomic::Ordering::Relaxed) {
+                log::trace!("Interrupting query '{query}'");
+
+                self.cancel().request()?;
+                canceled = true;
+            }
+
+            wait_socket(
+                socket,
+                true,
+                false,
+                Some(std::time::Duration::from_millis(100)),
+            )?;
+            self.consume_input()?;
+        }
+    }
+
+    /**
+     * Runs `EXPLAIN` on `query` and returns its typed plan.
+     *
+     * With `options.analyze`, the query is really executed.
+     *
+     * See [EXPLAIN](https://www.postgresql.org/docs/current/sql-explain.html).
+     */
+    #[cfg(feature = "explain")]
+    pub fn explain(
+        &self,
+        query: &str,
+        options: &crate::explain::Options,
+    ) -> crate::errors::Result<crate::explain::Explain> {
+        crate::explain::explain(self, query, options)
     }
 
     /**
@@ -28,13 +129,15 @@ impl Connection {
         param_formats: &[crate::Format],
         result_format: crate::Format,
     ) -> crate::PQResult {
-        let (values, formats, lengths) = Self::transform_params(param_values, param_formats);
+        let (values, formats, lengths, _buffers) = Self::transform_params(param_values, param_formats);
+        let command = &self.commented(command);
 
-        Self::trace_query("Sending", command, param_types, param_values, param_formats);
+        self.trace_query("Sending", command, param_types, param_values, param_formats);
 
         let c_command = crate::ffi::to_cstr(command);
+        let start = std::time::Instant::now();
 
-        unsafe {
+        let result = unsafe {
             pq_sys::PQexecParams(
                 self.into(),
                 c_command.as_ptr(),
@@ -58,7 +161,238 @@ impl Connection {
                 result_format as i32,
             )
         }
-        .into()
+        .into();
+        self.update_stats(|stats| stats.queries_sent += 1);
+        self.check_parameters();
+        self.log_query(command, None, param_types, param_values, param_formats, start, &result);
+
+        result
+    }
+
+    /**
+     * Same as `libpq::Connection::exec_params`, but returns an `Error::Query` error for
+     * `libpq::Status::FatalError` and `libpq::Status::BadResponse` results.
+     */
+    pub fn exec_params_checked(
+        &self,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.check_result(self.exec_params(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        ))
+    }
+
+    /**
+     * Submits a command with typed parameters to the server and waits for the result.
+     *
+     * Parameter types and formats are inferred from the Rust values, see
+     * `libpq::types::ToParam` and `libpq::Connection::set_param_type`: each parameter is sent
+     * in binary when the crate has a codec for its type, in text otherwise.
+     *
+     * Returns an `Error::NulError` error, without sending the command, if a text value
+     * contains a NUL byte.
+     */
+    pub fn exec_typed(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result<crate::PQResult> {
+        let params = crate::types::Params::new(params);
+        params.check()?;
+
+        Ok(self.exec_params(
+            command,
+            &self.param_types(&params),
+            &params.values(),
+            &params.formats,
+            crate::Format::Text,
+        ))
+    }
+
+    /**
+     * Sends the untyped parameters of Rust type `T` as `oid` in `libpq::Connection::exec_typed`
+     * and `libpq::Connection::send_query_typed`, instead of letting the server infer their
+     * type.
+     *
+     * This only applies to parameters without a type, like `str` and `String`, or their null
+     * values. `T` is matched exactly, through references and `Option`: `str` covers `&str` and
+     * `Some(&str)` but not `String`, `None::<&str>` has no default, see
+     * `libpq::types::ToParam::null_param_type_id`.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * conn.set_param_type::<str>(libpq::types::TEXT.oid);
+     * conn.set_param_type::<String>(libpq::types::TEXT.oid);
+     *
+     * // Without them, the server can't determine the type of `$1` and `$2`.
+     * conn.exec_typed("SELECT $1 || 'x', $2 IS NULL", &[&"a", &None::<String>])?;
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn set_param_type<T: crate::types::ToParam + ?Sized + 'static>(&self, oid: crate::Oid) {
+        self.param_types
+            .borrow_mut()
+            .insert(std::any::TypeId::of::<T>(), oid);
+    }
+
+    /**
+     * Removes the default type set by `libpq::Connection::set_param_type`.
+     */
+    pub fn unset_param_type<T: crate::types::ToParam + ?Sized + 'static>(&self) {
+        self.param_types
+            .borrow_mut()
+            .remove(&std::any::TypeId::of::<T>());
+    }
+
+    /**
+     * Prefixes the queries with a [sqlcommenter](https://google.github.io/sqlcommenter/) comment
+     * built from the tags returned by `hook`, to correlate `pg_stat_activity` entries or logs
+     * with distributed traces.
+     *
+     * The hook is called for every query sent by `libpq::Connection::exec`,
+     * `libpq::Connection::exec_params`, `libpq::Connection::send_query`,
+     * `libpq::Connection::send_query_params` and the methods built on them. Prepared statements
+     * are left untouched, their text being fixed at preparation. No comment is added when the
+     * hook returns no tags.
+     *
+     * The hook can use the connection, for example to replace itself, but the queries it sends
+     * call it again.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * conn.set_comment_hook(|| {
+     *     vec![(
+     *         "traceparent".to_string(),
+     *         "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01".to_string(),
+     *     )]
+     * });
+     *
+     * // Sent as /*traceparent='00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01'*/ SELECT 1
+     * conn.exec("SELECT 1");
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn set_comment_hook<F>(&self, hook: F)
+    where
+        F: Fn() -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        *self.comment_hook.borrow_mut() = Some(std::sync::Arc::new(hook));
+    }
+
+    /**
+     * Removes the hook set by `libpq::Connection::set_comment_hook`.
+     */
+    pub fn unset_comment_hook(&self) {
+        *self.comment_hook.borrow_mut() = None;
+    }
+
+    /**
+     * Calls `logger` after each query run by `libpq::Connection::exec`,
+     * `libpq::Connection::exec_params` and `libpq::Connection::exec_prepared`, with its
+     * duration and number of rows.
+     *
+     * The parameters are redacted according to `libpq::Connection::set_log_redaction`. The
+     * logger must not use the connection.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * conn.set_log_redaction(libpq::connection::Redaction::Hash);
+     * conn.set_query_logger(|log| {
+     *     eprintln!("{:?} {} {:?}", log.duration, log.command, log.params);
+     * });
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn set_query_logger<F>(&self, logger: F)
+    where
+        F: Fn(&crate::connection::QueryLog<'_>) + Send + 'static,
+    {
+        *self.query_logger.borrow_mut() = Some(Box::new(logger));
+    }
+
+    /**
+     * Removes the logger set by `libpq::Connection::set_query_logger`.
+     */
+    pub fn unset_query_logger(&self) {
+        *self.query_logger.borrow_mut() = None;
+    }
+
+    /**
+     * Sets how the parameter values appear in the query logger and in the trace logs, hashed
+     * by default.
+     */
+    pub fn set_log_redaction(&self, redaction: crate::connection::Redaction) {
+        self.log_redaction.set(redaction);
+    }
+
+    /**
+     * Adds the comment set by `libpq::Connection::set_comment_hook` to a query about to be sent.
+     */
+    pub(crate) fn commented<'a>(&self, query: &'a str) -> std::borrow::Cow<'a, str> {
+        // Released before calling the hook, which may use the connection.
+        let Some(hook) = self.comment_hook.borrow().clone() else {
+            return query.into();
+        };
+        let tags = hook();
+
+        if tags.is_empty() {
+            return query.into();
+        }
+
+        let mut tags = tags
+            .iter()
+            .map(|(key, value)| format!("{}='{}'", Self::url_encode(key), Self::url_encode(value)))
+            .collect::<Vec<_>>();
+        tags.sort();
+
+        format!("/*{}*/ {query}", tags.join(",")).into()
+    }
+
+    /**
+     * Percent-encodes everything but the unreserved characters, which also keeps quotes and the
+     * end of comment sequence out of the comment.
+     */
+    fn url_encode(s: &str) -> String {
+        use std::fmt::Write;
+
+        s.bytes().fold(String::with_capacity(s.len()), |mut encoded, byte| {
+            if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+                encoded.push(byte as char);
+            } else {
+                write!(encoded, "%{byte:02X}").ok();
+            }
+
+            encoded
+        })
+    }
+
+    /**
+     * Types looked up by `libpq::Type::from_database`.
+     */
+    pub(crate) fn types(&self) -> &std::cell::RefCell<std::collections::HashMap<crate::Oid, crate::Type>> {
+        &self.types
+    }
+
+    pub(crate) fn param_types(&self, params: &crate::types::Params) -> Vec<crate::Oid> {
+        let defaults = self.param_types.borrow();
+
+        params
+            .types
+            .iter()
+            .zip(&params.type_ids)
+            .map(|(oid, type_id)| match (oid, type_id) {
+                (0, Some(type_id)) => defaults.get(type_id).copied().unwrap_or_default(),
+                (oid, _) => *oid,
+            })
+            .collect()
     }
 
     /**
@@ -73,12 +407,12 @@ impl Connection {
         param_types: &[crate::Oid],
     ) -> crate::PQResult {
         let prefix = format!("Prepare {}", name.unwrap_or("anonymous"));
-        Self::trace_query(&prefix, query, param_types, &[], &[]);
+        self.trace_query(&prefix, query, param_types, &[], &[]);
 
         let c_name = crate::ffi::to_cstr(name.unwrap_or_default());
         let c_query = crate::ffi::to_cstr(query);
 
-        unsafe {
+        let result = unsafe {
             pq_sys::PQprepare(
                 self.into(),
                 c_name.as_ptr(),
@@ -87,7 +421,27 @@ impl Connection {
                 param_types.as_ptr(),
             )
         }
-        .into()
+        .into();
+        self.update_stats(|stats| stats.queries_sent += 1);
+
+        result
+    }
+
+    /**
+     * Creates a named prepared statement, deallocated when the returned
+     * [`Statement`](crate::connection::Statement) is dropped.
+     *
+     * See `libpq::Connection::prepare`.
+     */
+    pub fn prepare_statement(
+        &self,
+        name: &str,
+        query: &str,
+        param_types: &[crate::Oid],
+    ) -> crate::errors::Result<crate::connection::Statement<'_>> {
+        self.check_result(self.prepare(Some(name), query, param_types))?;
+
+        Ok(crate::connection::Statement::new(self, name))
     }
 
     /**
@@ -104,13 +458,14 @@ impl Connection {
         result_format: crate::Format,
     ) -> crate::PQResult {
         let prefix = format!("Execute {} prepared query", name.unwrap_or("anonymous"));
-        Self::trace_query(&prefix, "", &[], param_values, param_formats);
+        self.trace_query(&prefix, "", &[], param_values, param_formats);
 
-        let (values, formats, lengths) = Self::transform_params(param_values, param_formats);
+        let (values, formats, lengths, _buffers) = Self::transform_params(param_values, param_formats);
 
         let c_name = crate::ffi::to_cstr(name.unwrap_or_default());
+        let start = std::time::Instant::now();
 
-        unsafe {
+        let result = unsafe {
             pq_sys::PQexecPrepared(
                 self.into(),
                 c_name.as_ptr(),
@@ -129,7 +484,57 @@ impl Connection {
                 result_format as i32,
             )
         }
-        .into()
+        .into();
+        self.update_stats(|stats| stats.queries_sent += 1);
+        self.check_parameters();
+        self.log_query("", Some(name.unwrap_or_default()), &[], param_values, param_formats, start, &result);
+
+        result
+    }
+
+    /**
+     * Same as `libpq::Connection::exec_prepared`, but returns an `Error::Query` error for
+     * `libpq::Status::FatalError` and `libpq::Status::BadResponse` results.
+     */
+    pub fn exec_prepared_checked(
+        &self,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.check_result(self.exec_prepared(name, param_values, param_formats, result_format))
+    }
+
+    fn check_result(&self, result: crate::PQResult) -> crate::errors::Result<crate::PQResult> {
+        let status = result.status();
+
+        if !matches!(status, crate::Status::FatalError | crate::Status::BadResponse) {
+            return Ok(result);
+        }
+
+        let message = match result.error_message()? {
+            Some(message) if !message.is_empty() => message,
+            _ => self.error_message().unwrap_or_default().to_string(),
+        };
+
+        Err(crate::errors::Error::Query {
+            status,
+            sqlstate: result
+                .error_field(crate::result::ErrorField::Sqlstate)?
+                .map(ToString::to_string),
+            message: message.trim_end().to_string(),
+            detail: result
+                .error_field(crate::result::ErrorField::MessageDetail)?
+                .map(ToString::to_string),
+            hint: result
+                .error_field(crate::result::ErrorField::MessageHint)?
+                .map(ToString::to_string),
+            position: result
+                .error_field(crate::result::ErrorField::StatementPosition)?
+                .map(str::parse)
+                .transpose()?,
+        })
     }
 
     /**
@@ -202,6 +607,89 @@ impl Connection {
         crate::escape::bytea_conn(self, from)
     }
 
+    /**
+     * Escapes binary data for use within an SQL command with the type bytea, in the requested
+     * format.
+     *
+     * Unlike `libpq::Connection::escape_bytea`, the result isn't NUL terminated. The escaping
+     * is done in Rust, only `standard_conforming_strings` is read from the connection.
+     */
+    pub fn escape_bytea_as(
+        &self,
+        from: &[u8],
+        format: crate::escape::ByteaFormat,
+    ) -> crate::errors::Result<String> {
+        crate::escape::bytea_as(self, from, format)
+    }
+
+    /**
+     * Reads and discards the results of the command in progress, aborting a COPY IN and
+     * reading the remaining data of a COPY OUT, so the connection accepts a new command.
+     */
+    pub(crate) fn discard_results(&self) -> crate::errors::Result {
+        log::debug!("Discarding pending results");
+
+        while let Some(result) = self.result() {
+            match result.status() {
+                crate::Status::CopyIn => self.put_copy_end(Some("connection cleanup"))?,
+                crate::Status::CopyOut => while self.copy_data(false).is_ok() {},
+                crate::Status::CopyBoth => {
+                    return Err(crate::errors::Error::Backend(
+                        "Unable to leave COPY BOTH mode".to_string(),
+                    ))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Cancels the command in progress, if any, then discards its results. Used when a stream
+     * is dropped before its end, to not transfer the data nobody will read.
+     *
+     * The cancel request is ignored by a server already done with the command.
+     */
+    pub(crate) fn cancel_and_discard(&self) -> crate::errors::Result {
+        if let Err(err) = self.cancel().request() {
+            log::trace!("Unable to cancel the command in progress: {err}");
+        }
+
+        self.discard_results()
+    }
+
+    /**
+     * Ensures the connection is idle, outside of any transaction block.
+     *
+     * Pending results of an abandoned command are discarded (aborting a COPY in progress) and
+     * an open or failed transaction is rolled back. Returns the transaction status found
+     * before the cleanup, so `libpq::transaction::Status::Idle` means nothing had to be done.
+     */
+    pub fn ensure_idle(&self) -> crate::errors::Result<crate::transaction::Status> {
+        let status = self.transaction_status();
+
+        if status == crate::transaction::Status::Active {
+            self.discard_results()?;
+        }
+
+        match self.transaction_status() {
+            crate::transaction::Status::Idle => (),
+            crate::transaction::Status::InTrans | crate::transaction::Status::InError => {
+                log::debug!("Rolling back {status:?} transaction");
+
+                if self.exec("ROLLBACK").status() != crate::Status::CommandOk {
+                    return self.error();
+                }
+            }
+            crate::transaction::Status::Active | crate::transaction::Status::Unknow => {
+                return self.error()
+            }
+        }
+
+        Ok(status)
+    }
+
     /**
      * Submits a request to close the specified prepared statement, and waits for completion.
      *
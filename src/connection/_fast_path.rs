@@ -0,0 +1,85 @@
+/**
+ * [The Fast-Path Interface](https://www.postgresql.org/docs/current/libpq-fastpath.html)
+ */
+impl Connection {
+    /**
+     * Calls the server function `fnid` with `args`, returns `None` if the function returned
+     * NULL.
+     *
+     * Prefer a prepared statement calling the function: this interface is obsolete, but the
+     * server functions are identified by OID and there is no query to parse.
+     *
+     * See [PQfn](https://www.postgresql.org/docs/current/libpq-fastpath.html).
+     *
+     * # Safety
+     *
+     * libpq copies the result into a buffer of `max_len` bytes for
+     * `libpq::connection::FnResultKind::Bytes`, without checking its size: the function must
+     * not return more.
+     */
+    pub unsafe fn fn_call(
+        &self,
+        fnid: crate::Oid,
+        args: &[crate::connection::FnArg<'_>],
+        result_kind: crate::connection::FnResultKind,
+    ) -> crate::errors::Result<Option<crate::connection::FnValue>> {
+        log::trace!("Calling function {fnid} with {} argument(s)", args.len());
+
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                crate::connection::FnArg::Int(value) => pq_sys::PQArgBlock {
+                    len: 4,
+                    isint: 1,
+                    u: pq_sys::PQArgBlock__bindgen_ty_1 { integer: *value },
+                },
+                crate::connection::FnArg::Bytes(value) => pq_sys::PQArgBlock {
+                    len: value.len() as i32,
+                    isint: 0,
+                    u: pq_sys::PQArgBlock__bindgen_ty_1 {
+                        ptr: value.as_ptr() as *mut i32,
+                    },
+                },
+            })
+            .collect::<Vec<_>>();
+
+        // `result_buf` is an `int *`, allocated as such to be aligned.
+        let (mut buffer, result_is_int) = match result_kind {
+            crate::connection::FnResultKind::Int => (vec![0_i32; 1], 1),
+            crate::connection::FnResultKind::Bytes { max_len } => {
+                (vec![0_i32; max_len.div_ceil(4).max(1)], 0)
+            }
+        };
+        let mut result_len = 0;
+
+        let result: crate::PQResult = pq_sys::PQfn(
+            self.into(),
+            fnid as i32,
+            buffer.as_mut_ptr(),
+            &mut result_len,
+            result_is_int,
+            args.as_ptr(),
+            args.len() as i32,
+        )
+        .into();
+        self.check_result(result)?;
+
+        if result_len < 0 {
+            return Ok(None);
+        }
+
+        let value = match result_kind {
+            crate::connection::FnResultKind::Int => crate::connection::FnValue::Int(buffer[0]),
+            crate::connection::FnResultKind::Bytes { .. } => {
+                let bytes = buffer
+                    .iter()
+                    .flat_map(|x| x.to_ne_bytes())
+                    .take(result_len as usize)
+                    .collect();
+                crate::connection::FnValue::Bytes(bytes)
+            }
+        };
+
+        Ok(Some(value))
+    }
+}
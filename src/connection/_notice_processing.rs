@@ -25,6 +25,61 @@ impl Connection {
         proc: NoticeReceiver,
         arg: *mut raw::c_void,
     ) -> NoticeReceiver {
+        self.notice_arg.set(arg);
         pq_sys::PQsetNoticeReceiver(self.into(), proc, arg)
     }
+
+    /**
+     * Calls `handler` with each notice or warning sent by the server, instead of printing it
+     * on stderr.
+     *
+     * The handler lives as long as the connection; the previous notice receiver is restored by
+     * `libpq::Connection::unset_notice_handler` or when the connection is dropped. It's called
+     * while libpq reads the server messages, it must not use the connection.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * conn.set_notice_handler(|notice| {
+     *     eprintln!("{}: {}", notice.severity, notice.message);
+     * });
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn set_notice_handler<F>(&self, handler: F)
+    where
+        F: Fn(crate::connection::Notice) + Send + 'static,
+    {
+        let id = crate::connection::NoticeTrampoline::register(Box::new(handler));
+        let arg = id as *mut raw::c_void;
+
+        let previous = unsafe {
+            pq_sys::PQsetNoticeReceiver(
+                self.into(),
+                Some(crate::connection::notice_trampoline),
+                arg,
+            )
+        };
+        let previous = (previous, self.notice_arg.replace(arg));
+
+        let mut trampoline = self.notice_trampoline.borrow_mut();
+        // Replacing a handler keeps the receiver it replaced.
+        let previous = trampoline.take().map_or(previous, |x| x.previous);
+
+        *trampoline = Some(crate::connection::NoticeTrampoline { previous, id });
+    }
+
+    /**
+     * Removes the handler set by `libpq::Connection::set_notice_handler` and restores the
+     * previous notice receiver.
+     */
+    pub fn unset_notice_handler(&self) {
+        let Some(trampoline) = self.notice_trampoline.borrow_mut().take() else {
+            return;
+        };
+
+        let (proc, arg) = trampoline.previous;
+        unsafe {
+            self.set_notice_receiver(proc, arg);
+        }
+    }
 }
@@ -15,4 +15,11 @@ impl Connection {
             Some(raw.into())
         }
     }
+
+    /**
+     * Returns a handle to listen to channels and wait for notifications.
+     */
+    pub fn notifications(&self) -> crate::connection::Notifications<'_> {
+        crate::connection::Notifications::new(self)
+    }
 }
@@ -20,6 +20,98 @@ impl Connection {
         }
     }
 
+    /**
+     * Sends `command` with typed parameters and iterates over its rows one at a time, in
+     * single-row mode.
+     *
+     * This waits on the connection socket while the results are not available, so it works with
+     * blocking and non-blocking connections alike. See `libpq::connection::RowStream`.
+     */
+    #[cfg(unix)]
+    pub fn stream_query(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result<crate::connection::RowStream<'_>> {
+        self.send_query_typed(command, params)?;
+
+        if let Err(err) = self.set_single_row_mode() {
+            while self.result().is_some() {}
+            return Err(err);
+        }
+
+        crate::connection::RowStream::new(self)
+    }
+
+    /**
+     * Like `libpq::Connection::stream_query`, in chunked mode: each item holds up to
+     * `max_rows` rows, which saves the overhead of a result per row.
+     */
+    #[cfg(all(unix, feature = "v17"))]
+    pub fn stream_query_chunked(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+        max_rows: i32,
+    ) -> crate::errors::Result<crate::connection::RowStream<'_>> {
+        self.send_query_typed(command, params)?;
+
+        if let Err(err) = self.set_chunked_rows_mode(max_rows) {
+            while self.result().is_some() {}
+            return Err(err);
+        }
+
+        crate::connection::RowStream::new(self)
+    }
+
+    /**
+     * Executes `query` in single-row mode, calling `callback` for each row as it arrives.
+     *
+     * Only one row is held in memory at a time. When `callback` returns
+     * `ControlFlow::Break`, the query is canceled and its remaining rows are discarded.
+     */
+    pub fn exec_foreach<F>(&self, query: &str, mut callback: F) -> crate::errors::Result
+    where
+        F: FnMut(crate::result::Row<'_>) -> std::ops::ControlFlow<()>,
+    {
+        self.send_query(query)?;
+
+        if let Err(err) = self.set_single_row_mode() {
+            while self.result().is_some() {}
+            return Err(err);
+        }
+
+        let mut error = None;
+        let mut stopped = false;
+
+        while let Some(result) = self.result() {
+            if stopped || error.is_some() {
+                continue;
+            }
+
+            let result = match self.check_result(result) {
+                Ok(result) => result,
+                Err(err) => {
+                    error = Some(err);
+                    continue;
+                }
+            };
+
+            for row in result.rows() {
+                if callback(row).is_break() {
+                    log::trace!("Canceling the remaining rows");
+
+                    stopped = true;
+                    // The remaining results are drained before returning the error.
+                    error = self.cancel().request().err();
+                    break;
+                }
+            }
+        }
+
+        error.map_or(Ok(()), Err)
+    }
+
     /**
      * Select chunked mode for the currently-executing query.
      *
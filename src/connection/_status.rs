@@ -55,6 +55,21 @@ impl Connection {
         crate::ffi::to_string(unsafe { pq_sys::PQhostaddr(self.into()) })
     }
 
+    /**
+     * Returns the server socket address of the active connection, `None` for a Unix-domain
+     * socket.
+     *
+     * Useful in the diagnostics of a host name resolving to several addresses, see
+     * `libpq::connection::Config::address_family`.
+     */
+    #[cfg(feature = "v12")]
+    pub fn server_addr(&self) -> Option<std::net::SocketAddr> {
+        let ip = self.hostaddr().ok()?.parse().ok()?;
+        let port = self.port().ok()?.parse().ok()?;
+
+        Some(std::net::SocketAddr::new(ip, port))
+    }
+
     /**
      * Returns the port of the active connection.
      *
@@ -114,6 +129,94 @@ impl Connection {
         crate::ffi::to_string(unsafe { pq_sys::PQparameterStatus(self.into(), c_param.as_ptr()) })
     }
 
+    /**
+     * Calls `hook` for each change of a `libpq::connection::REPORTED_PARAMETERS` parameter,
+     * after a `SET` or a configuration reload for example, replacing the previous hook.
+     *
+     * The server reports the changes along with the query results: they are detected when a
+     * result is received by `exec`, `exec_params`, `exec_prepared` or `result`. The hook must
+     * not use the connection.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * conn.set_parameter_hook(|change| {
+     *     println!("{} changed from {:?} to {:?}", change.name, change.old, change.new);
+     * });
+     *
+     * conn.exec("SET TimeZone = 'UTC'");
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn set_parameter_hook<F>(&self, hook: F)
+    where
+        F: Fn(&crate::connection::ParameterChange) + Send + 'static,
+    {
+        let values = crate::connection::REPORTED_PARAMETERS
+            .iter()
+            .map(|name| self.reported_parameter(name))
+            .collect();
+
+        *self.parameter_watch.borrow_mut() = Some(crate::connection::ParameterWatch {
+            hook: Box::new(hook),
+            values,
+        });
+    }
+
+    /**
+     * Removes the hook set by `libpq::Connection::set_parameter_hook`.
+     */
+    pub fn unset_parameter_hook(&self) {
+        *self.parameter_watch.borrow_mut() = None;
+    }
+
+    /**
+     * Calls the parameter hook for the parameters changed since the last check.
+     */
+    pub(crate) fn check_parameters(&self) {
+        let mut watch = self.parameter_watch.borrow_mut();
+        let Some(watch) = &mut *watch else {
+            return;
+        };
+
+        for (name, old) in crate::connection::REPORTED_PARAMETERS
+            .iter()
+            .zip(watch.values.iter_mut())
+        {
+            let new = self.reported_parameter(name);
+
+            if new != *old {
+                log::trace!("Parameter {name} changed from {old:?} to {new:?}");
+
+                let change = crate::connection::ParameterChange {
+                    name,
+                    old: std::mem::replace(old, new.clone()),
+                    new,
+                };
+                (watch.hook)(&change);
+            }
+        }
+    }
+
+    fn reported_parameter(&self, name: &str) -> Option<String> {
+        let c_name = crate::ffi::to_cstr(name);
+        let value = unsafe { pq_sys::PQparameterStatus(self.into(), c_name.as_ptr()) };
+
+        if value.is_null() {
+            None
+        } else {
+            crate::ffi::to_string(value).ok()
+        }
+    }
+
+    /**
+     * Takes a snapshot of all the server run-time parameters.
+     *
+     * See [pg_settings](https://www.postgresql.org/docs/current/view-pg-settings.html).
+     */
+    pub fn settings(&self) -> crate::errors::Result<crate::settings::Settings> {
+        crate::settings::Settings::load(self)
+    }
+
     /**
      * Interrogates the frontend/backend protocol being used.
      *
@@ -204,6 +307,62 @@ impl Connection {
         unsafe { pq_sys::PQconnectionUsedPassword(self.into()) == 1 }
     }
 
+    /**
+     * Returns the server version, cluster identifier, timeline, database and user of the
+     * connection.
+     *
+     * Runs `IDENTIFY_SYSTEM` on a replication connection, a query otherwise.
+     */
+    pub fn identity(&self) -> crate::errors::Result<crate::connection::Identity> {
+        crate::connection::Identity::new(self)
+    }
+
+    /**
+     * Returns the channel binding mode of the connection, `None` if libpq doesn't support it.
+     */
+    pub fn channel_binding(
+        &self,
+    ) -> crate::errors::Result<Option<crate::connection::ChannelBinding>> {
+        let Some(info) = self.info()?.remove("channel_binding") else {
+            return Ok(None);
+        };
+
+        info.val.as_deref().unwrap_or("prefer").parse().map(Some)
+    }
+
+    /**
+     * Whether the authentication is guaranteed to have used channel binding.
+     *
+     * libpq doesn't report if channel binding was actually used, but fails the connection when
+     * it's required and the server doesn't use it: this returns `true` for an established
+     * connection with `libpq::connection::ChannelBinding::Require` only. With the `prefer` mode,
+     * a man-in-the-middle can downgrade the authentication.
+     */
+    pub fn channel_binding_enforced(&self) -> crate::errors::Result<bool> {
+        Ok(self.status() == crate::connection::Status::Ok
+            && self.channel_binding()? == Some(crate::connection::ChannelBinding::Require))
+    }
+
+    /**
+     * Returns the traffic counters of the connection, see `libpq::connection::Stats`.
+     */
+    pub fn stats(&self) -> crate::connection::Stats {
+        self.stats.get()
+    }
+
+    /**
+     * Resets the counters returned by `libpq::Connection::stats`.
+     */
+    pub fn reset_stats(&self) {
+        self.stats.take();
+    }
+
+    pub(crate) fn update_stats(&self, f: impl FnOnce(&mut crate::connection::Stats)) {
+        let mut stats = self.stats.get();
+        f(&mut stats);
+        self.stats.set(stats);
+    }
+
     /**
      * Returns `true` if the connection uses SSL, `false` if not.
      *
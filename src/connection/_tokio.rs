@@ -0,0 +1,246 @@
+/**
+ * Asynchronous API for the [tokio](https://tokio.rs) runtime.
+ *
+ * These methods drive the non-blocking libpq functions, waiting for the connection socket with
+ * tokio's `AsyncFd`. The connection must be in non-blocking mode, as set by
+ * `libpq::Connection::connect_async`, otherwise sending data may block the runtime.
+ *
+ * Only one asynchronous operation can be in progress on a connection at a time. Since a
+ * `Connection` can't be shared between threads, the futures are not `Send`: run them on a
+ * current thread runtime or in a `tokio::task::LocalSet`.
+ */
+impl Connection {
+    /**
+     * Makes a new non-blocking connection to the database server.
+     *
+     * See `libpq::Connection::start` and `libpq::Connection::poll_hint`.
+     */
+    pub async fn connect_async(dsn: &str) -> crate::errors::Result<Self> {
+        let conn = Self::start(dsn)?;
+        let mut hint = crate::poll::Hint {
+            status: crate::poll::Status::Writing,
+            socket: conn.socket().ok(),
+        };
+
+        loop {
+            match hint.status {
+                crate::poll::Status::Ok => break,
+                crate::poll::Status::Failed => return conn.error(),
+                _ => (),
+            }
+
+            if let (Some(socket), Some(interest)) = (hint.socket, hint.interest()) {
+                wait_async(socket, interest).await?;
+            }
+
+            hint = conn.poll_hint();
+        }
+
+        conn.set_non_blocking(true)?;
+
+        Ok(conn)
+    }
+
+    /**
+     * Asynchronous version of `libpq::Connection::exec`.
+     */
+    pub async fn exec_async(&self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        self.send_query(query)?;
+        self.last_result_async().await
+    }
+
+    /**
+     * Asynchronous version of `libpq::Connection::exec_typed`.
+     */
+    pub async fn exec_typed_async(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.send_query_typed(command, params)?;
+        self.last_result_async().await
+    }
+
+    /**
+     * Waits for the next result, `None` when the command is done.
+     *
+     * Asynchronous version of `libpq::Connection::result`.
+     */
+    pub async fn result_async(&self) -> crate::errors::Result<Option<crate::PQResult>> {
+        self.flush_async().await?;
+
+        while self.is_busy() {
+            wait_async(self.socket()?, crate::poll::Interest::Read).await?;
+            self.consume_input()?;
+        }
+
+        Ok(self.result())
+    }
+
+    /**
+     * Sends the queued data to the server.
+     *
+     * Asynchronous version of `libpq::Connection::flush`.
+     */
+    pub async fn flush_async(&self) -> crate::errors::Result {
+        loop {
+            match unsafe { pq_sys::PQflush(self.into()) } {
+                0 => return Ok(()),
+                1 => {
+                    wait_async(self.socket()?, crate::poll::Interest::Write).await?;
+                    // The server may be waiting for us to read its data before reading ours.
+                    self.consume_input()?;
+                }
+                _ => return self.error(),
+            }
+        }
+    }
+
+    /**
+     * Asynchronous version of `libpq::Connection::put_copy_data`.
+     */
+    pub async fn put_copy_data_async(&self, buffer: &[u8]) -> crate::errors::Result {
+        log::trace!("Sending copy data ({} bytes)", buffer.len());
+
+        loop {
+            let success = unsafe {
+                pq_sys::PQputCopyData(
+                    self.into(),
+                    buffer.as_ptr() as *const raw::c_char,
+                    buffer.len() as i32,
+                )
+            };
+
+            match success {
+                1 => {
+                    self.count_copy_sent(buffer.len());
+                    return Ok(());
+                }
+                0 => wait_async(self.socket()?, crate::poll::Interest::Write).await?,
+                _ => return self.error(),
+            }
+        }
+    }
+
+    /**
+     * Ends the COPY and waits for its result.
+     *
+     * Asynchronous version of `libpq::Connection::put_copy_end`.
+     */
+    pub async fn put_copy_end_async(
+        &self,
+        errormsg: Option<&str>,
+    ) -> crate::errors::Result<crate::PQResult> {
+        let cstr = errormsg.map(crate::ffi::to_cstr);
+        let ptr = cstr.as_ref().map_or(std::ptr::null(), |x| x.as_ptr());
+
+        loop {
+            match unsafe { pq_sys::PQputCopyEnd(self.into(), ptr) } {
+                1 => break,
+                0 => wait_async(self.socket()?, crate::poll::Interest::Write).await?,
+                _ => return self.error(),
+            }
+        }
+
+        self.last_result_async().await
+    }
+
+    /**
+     * Receives the next COPY data row, `None` at the end of the COPY.
+     *
+     * Asynchronous version of `libpq::Connection::copy_data`.
+     */
+    pub async fn copy_data_async(&self) -> crate::errors::Result<Option<PqBytes>> {
+        loop {
+            let mut ptr = std::ptr::null_mut();
+            let nbytes = unsafe { pq_sys::PQgetCopyData(self.into(), &mut ptr, 1) };
+
+            match nbytes {
+                0 => {
+                    wait_async(self.socket()?, crate::poll::Interest::Read).await?;
+                    self.consume_input()?;
+                }
+                -1 => {
+                    log::trace!("End of copy data");
+                    self.check_result(self.last_result_async().await?)?;
+
+                    return Ok(None);
+                }
+                -2 => return self.error(),
+                nbytes => {
+                    log::trace!("Receiving copy data ({nbytes} bytes)");
+                    self.count_copy_received(nbytes as usize);
+
+                    return Ok(Some(PqBytes::from_raw(ptr as *const u8, nbytes as usize)));
+                }
+            }
+        }
+    }
+
+    /**
+     * Waits for the next notification.
+     *
+     * Call it in a loop to process the notifications as a stream, see
+     * `libpq::Connection::notifies`.
+     */
+    pub async fn notifies_async(&self) -> crate::errors::Result<crate::connection::Notify> {
+        loop {
+            self.consume_input()?;
+
+            if let Some(notify) = self.notifies() {
+                return Ok(notify);
+            }
+
+            wait_async(self.socket()?, crate::poll::Interest::Read).await?;
+        }
+    }
+
+    async fn last_result_async(&self) -> crate::errors::Result<crate::PQResult> {
+        let mut last = None;
+
+        while let Some(result) = self.result_async().await? {
+            let status = result.status();
+            last = Some(result);
+
+            if matches!(
+                status,
+                crate::Status::CopyIn | crate::Status::CopyOut | crate::Status::CopyBoth
+            ) {
+                break;
+            }
+        }
+
+        last.map_or_else(|| self.error(), Ok)
+    }
+}
+
+struct Fd(i32);
+
+impl std::os::fd::AsRawFd for Fd {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        self.0
+    }
+}
+
+/**
+ * Waits until `socket` is ready.
+ *
+ * The socket is registered for this wait only: libpq may replace it, while connecting to
+ * another host for example.
+ */
+async fn wait_async(socket: i32, interest: crate::poll::Interest) -> crate::errors::Result {
+    let interest = match interest {
+        crate::poll::Interest::Read => ::tokio::io::Interest::READABLE,
+        crate::poll::Interest::Write => ::tokio::io::Interest::WRITABLE,
+    };
+
+    let fd = ::tokio::io::unix::AsyncFd::with_interest(Fd(socket), interest)
+        .map_err(|err| crate::errors::Error::Backend(err.to_string()))?;
+
+    fd.ready(interest)
+        .await
+        .map_err(|err| crate::errors::Error::Backend(err.to_string()))?
+        .retain_ready();
+
+    Ok(())
+}
@@ -4,16 +4,17 @@ impl Connection {
      *
      * See [PQtrace](https://www.postgresql.org/docs/current/libpq-control.html#LIBPQ-PQTRACE).
      */
-    #[cfg(unix)]
     pub fn trace(&self, file: std::fs::File) {
-        use std::os::unix::io::IntoRawFd;
-
         log::trace!("Enable trace");
 
-        let c_mode = c"w";
+        let stream = crate::ffi::into_stream(file);
+
+        if stream.is_null() {
+            log::error!("Unable to open the trace file");
+            return;
+        }
 
         unsafe {
-            let stream = libc::fdopen(file.into_raw_fd(), c_mode.as_ptr());
             pq_sys::PQtrace(self.into(), stream as *mut _);
         }
     }
@@ -23,7 +24,6 @@ impl Connection {
      *
      * See [PQuntrace](https://www.postgresql.org/docs/current/libpq-control.html#LIBPQ-PQUNTRACE).
      */
-    #[cfg(unix)]
     pub fn untrace(&self) {
         log::trace!("Disable trace");
 
@@ -98,6 +98,26 @@ impl PqBytes {
         );
         PqBytes { ptr, len }
     }
+
+    /**
+     * Copies the data into an owned vector and frees the libpq buffer.
+     */
+    pub fn into_vec(self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+impl From<PqBytes> for Vec<u8> {
+    fn from(bytes: PqBytes) -> Self {
+        bytes.into_vec()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl From<PqBytes> for bytes::Bytes {
+    fn from(bytes: PqBytes) -> Self {
+        bytes.into_vec().into()
+    }
 }
 
 /**
@@ -218,4 +238,13 @@ impl PqString {
     pub unsafe fn to_str_unchecked(&self) -> &str {
         std::str::from_utf8_unchecked(self.as_ref())
     }
+
+    /**
+     * Copies the string into an owned [`String`] and frees the libpq buffer.
+     *
+     * Returns an error if the string isn't valid UTF-8.
+     */
+    pub fn into_string(self) -> crate::errors::Result<String> {
+        Ok(self.to_str()?.to_string())
+    }
 }
@@ -0,0 +1,381 @@
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Key {
+    query: String,
+    param_types: Vec<crate::Oid>,
+    param_values: Vec<Option<Vec<u8>>>,
+    param_formats: Vec<crate::Format>,
+    result_format: crate::Format,
+}
+
+#[derive(Debug)]
+struct Entry {
+    result: crate::PQResult,
+    inserted: std::time::Instant,
+}
+
+/**
+ * A connection caching the results of read-only queries on the client side.
+ *
+ * Results are keyed on the query text and its parameters, and kept for `ttl` with at most
+ * `capacity` entries; when the cache is full, the oldest entry is evicted. Only successful
+ * results returning tuples are cached, but it's up to the caller to send only queries without
+ * side effects through [`CachedConnection::exec`] and [`CachedConnection::exec_params`]: other
+ * commands should be executed with [`CachedConnection::exec_uncached`].
+ *
+ * The cache can be invalidated manually, or when a notification is received on a channel
+ * registered with [`CachedConnection::invalidate_on`].
+ *
+ * The underlying connection isn't reachable while cached, as taking its notifications would
+ * leave the cache stale: only the methods not touching the notifications are forwarded.
+ */
+#[derive(Debug)]
+pub struct CachedConnection {
+    conn: crate::Connection,
+    ttl: std::time::Duration,
+    capacity: usize,
+    entries: std::collections::HashMap<Key, Entry>,
+    channels: std::collections::HashSet<String>,
+    pending: std::collections::VecDeque<crate::connection::Notify>,
+}
+
+impl CachedConnection {
+    pub fn new(conn: crate::Connection, ttl: std::time::Duration, capacity: usize) -> Self {
+        Self {
+            conn,
+            ttl,
+            capacity,
+            entries: std::collections::HashMap::new(),
+            channels: std::collections::HashSet::new(),
+            pending: std::collections::VecDeque::new(),
+        }
+    }
+
+    /**
+     * Executes a query, or returns a copy of its cached result.
+     */
+    pub fn exec(&mut self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        self.exec_params(query, &[], &[], &[], crate::Format::Text)
+    }
+
+    /**
+     * Executes a query with parameters, or returns a copy of its cached result.
+     *
+     * See `libpq::Connection::exec_params`.
+     */
+    pub fn exec_params(
+        &mut self,
+        query: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.process_invalidations()?;
+
+        let key = Key {
+            query: query.to_string(),
+            param_types: param_types.to_vec(),
+            param_values: param_values.iter().map(|x| x.map(<[u8]>::to_vec)).collect(),
+            param_formats: param_formats.to_vec(),
+            result_format,
+        };
+
+        if let Some(entry) = self.entries.get(&key) {
+            if entry.inserted.elapsed() < self.ttl {
+                log::trace!("Cache hit for query '{query}'");
+
+                return entry.result.copy(Self::COPY_FLAGS);
+            }
+
+            self.entries.remove(&key);
+        }
+
+        log::trace!("Cache miss for query '{query}'");
+
+        let result = self.conn.exec_params(
+            query,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        );
+
+        if result.status() == crate::Status::TuplesOk && self.capacity > 0 {
+            self.insert(key, result.copy(Self::COPY_FLAGS)?);
+        }
+
+        Ok(result)
+    }
+
+    /**
+     * Executes a command without caching its result, like a command with side effects.
+     *
+     * See `libpq::Connection::exec`.
+     */
+    pub fn exec_uncached(&self, query: &str) -> crate::PQResult {
+        self.conn.exec(query)
+    }
+
+    const COPY_FLAGS: i32 = (pq_sys::PG_COPYRES_ATTRS | pq_sys::PG_COPYRES_TUPLES) as i32;
+
+    fn insert(&mut self, key: Key, result: crate::PQResult) {
+        let ttl = self.ttl;
+        self.entries
+            .retain(|_, entry| entry.inserted.elapsed() < ttl);
+
+        while self.entries.len() >= self.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.inserted)
+                .map(|(key, _)| key.clone());
+
+            match oldest {
+                Some(oldest) => self.entries.remove(&oldest),
+                None => break,
+            };
+        }
+
+        self.entries.insert(
+            key,
+            Entry {
+                result,
+                inserted: std::time::Instant::now(),
+            },
+        );
+    }
+
+    /**
+     * Removes all cached results of `query`, whatever their parameters.
+     */
+    pub fn invalidate(&mut self, query: &str) {
+        self.entries.retain(|key, _| key.query != query);
+    }
+
+    /**
+     * Removes all cached results.
+     */
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /**
+     * Number of cached results, including expired ones not yet evicted.
+     */
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /**
+     * Listens on `channel` and clears the cache each time a notification is received on it.
+     *
+     * Notifications are checked before each query; notifications on other channels are kept
+     * and returned by [`CachedConnection::notifies`].
+     */
+    pub fn invalidate_on(&mut self, channel: &str) -> crate::errors::Result {
+        let identifier = self.conn.escape_identifier(channel)?;
+        let result = self
+            .conn
+            .exec(&format!("LISTEN {}", identifier.to_string_lossy()));
+
+        if result.status() != crate::Status::CommandOk {
+            return self.conn.error();
+        }
+
+        self.channels.insert(channel.to_string());
+
+        Ok(())
+    }
+
+    /**
+     * Returns the next notification not used to invalidate the cache.
+     *
+     * See `libpq::Connection::notifies`.
+     */
+    pub fn notifies(&mut self) -> crate::errors::Result<Option<crate::connection::Notify>> {
+        self.process_invalidations()?;
+
+        Ok(self.pending.pop_front())
+    }
+
+    fn process_invalidations(&mut self) -> crate::errors::Result {
+        if self.channels.is_empty() {
+            return Ok(());
+        }
+
+        self.conn.consume_input()?;
+
+        while let Some(notify) = self.conn.notifies() {
+            if self.channels.contains(&notify.relname()?) {
+                log::trace!("Cache invalidated by notification");
+                self.entries.clear();
+            } else {
+                self.pending.push_back(notify);
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * See `libpq::Connection::status`.
+     */
+    pub fn status(&self) -> crate::connection::Status {
+        self.conn.status()
+    }
+
+    /**
+     * See `libpq::Connection::transaction_status`.
+     */
+    pub fn transaction_status(&self) -> crate::transaction::Status {
+        self.conn.transaction_status()
+    }
+
+    /**
+     * See `libpq::Connection::parameter_status`.
+     */
+    pub fn parameter_status(&self, param: &str) -> crate::errors::Result<String> {
+        self.conn.parameter_status(param)
+    }
+
+    /**
+     * See `libpq::Connection::error_message`.
+     */
+    pub fn error_message(&self) -> Option<&str> {
+        self.conn.error_message()
+    }
+
+    /**
+     * See `libpq::Connection::escape_literal`.
+     */
+    pub fn escape_literal(&self, str: &str) -> crate::errors::Result<crate::connection::PqString> {
+        self.conn.escape_literal(str)
+    }
+
+    /**
+     * See `libpq::Connection::escape_identifier`.
+     */
+    pub fn escape_identifier(
+        &self,
+        str: &str,
+    ) -> crate::errors::Result<crate::connection::PqString> {
+        self.conn.escape_identifier(str)
+    }
+
+    /**
+     * Returns the underlying connection, dropping the cache.
+     */
+    pub fn into_inner(self) -> crate::Connection {
+        self.conn
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn cache() {
+        let mut conn = crate::connection::CachedConnection::new(
+            crate::test::new_conn(),
+            std::time::Duration::from_secs(60),
+            2,
+        );
+
+        let first = conn.exec("SELECT clock_timestamp()").unwrap();
+        let second = conn.exec("SELECT clock_timestamp()").unwrap();
+        assert_eq!(first.value(0, 0), second.value(0, 0));
+
+        conn.exec("SELECT 1").unwrap();
+        conn.exec("SELECT 2").unwrap();
+        assert_eq!(conn.len(), 2);
+
+        let third = conn.exec("SELECT clock_timestamp()").unwrap();
+        assert_ne!(first.value(0, 0), third.value(0, 0));
+
+        conn.invalidate("SELECT clock_timestamp()");
+        assert_eq!(conn.len(), 1);
+
+        conn.exec("SELECT 1/0").unwrap();
+        assert_eq!(conn.len(), 1);
+    }
+
+    #[test]
+    fn params() {
+        let mut conn = crate::connection::CachedConnection::new(
+            crate::test::new_conn(),
+            std::time::Duration::from_secs(60),
+            10,
+        );
+
+        let query = "SELECT $1::int";
+        let one = conn
+            .exec_params(query, &[], &[Some(b"1\0")], &[], crate::Format::Text)
+            .unwrap();
+        let two = conn
+            .exec_params(query, &[], &[Some(b"2\0")], &[], crate::Format::Text)
+            .unwrap();
+
+        assert_eq!(one.value(0, 0), Some(&b"1"[..]));
+        assert_eq!(two.value(0, 0), Some(&b"2"[..]));
+        assert_eq!(conn.len(), 2);
+    }
+
+    #[test]
+    fn ttl() {
+        let mut conn = crate::connection::CachedConnection::new(
+            crate::test::new_conn(),
+            std::time::Duration::ZERO,
+            10,
+        );
+
+        let first = conn.exec("SELECT clock_timestamp()").unwrap();
+        let second = conn.exec("SELECT clock_timestamp()").unwrap();
+        assert_ne!(first.value(0, 0), second.value(0, 0));
+    }
+
+    #[test]
+    fn invalidate_on() {
+        let mut conn = crate::connection::CachedConnection::new(
+            crate::test::new_conn(),
+            std::time::Duration::from_secs(60),
+            10,
+        );
+        conn.invalidate_on("cache_invalidation").unwrap();
+        conn.exec_uncached("LISTEN other");
+
+        conn.exec("SELECT 1").unwrap();
+        assert_eq!(conn.len(), 1);
+
+        let other = crate::test::new_conn();
+        other.exec("NOTIFY other");
+        other.exec("NOTIFY cache_invalidation");
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        let notify = conn.notifies().unwrap().unwrap();
+        assert_eq!(notify.relname().unwrap(), "other");
+        assert!(conn.is_empty());
+    }
+
+    #[test]
+    fn exec_uncached() {
+        let mut conn = crate::connection::CachedConnection::new(
+            crate::test::new_conn(),
+            std::time::Duration::from_secs(60),
+            10,
+        );
+        conn.invalidate_on("cache_invalidation").unwrap();
+
+        conn.exec("SELECT 1").unwrap();
+        conn.exec_uncached("SELECT 2");
+        assert_eq!(conn.len(), 1);
+
+        // The notification received while executing an uncached command is kept for the cache.
+        conn.exec_uncached("NOTIFY cache_invalidation");
+        conn.exec("SELECT 3").unwrap();
+        assert_eq!(conn.len(), 1);
+    }
+}
@@ -48,3 +48,227 @@ impl Drop for Cancel {
         }
     }
 }
+
+/**
+ * Handle to cancel the commands of a connection, independently of it.
+ *
+ * Created by `libpq::Connection::cancel_token`. Unlike [`Cancel`], it's cheap to clone and can be
+ * sent to another thread, to cancel a long-running query while the connection is busy waiting
+ * for it. The token stays valid after the connection is closed, requests then fail.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let token = conn.cancel_token()?;
+ *
+ * std::thread::spawn(move || {
+ *     std::thread::sleep(std::time::Duration::from_secs(1));
+ *     token.cancel_with_timeout(std::time::Duration::from_secs(5)).ok();
+ * });
+ *
+ * let result = conn.exec("SELECT pg_sleep(60)");
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Clone, Debug)]
+pub struct CancelToken {
+    cancel: std::sync::Arc<Cancel>,
+    #[cfg(feature = "v17")]
+    conn: std::sync::Arc<std::sync::Mutex<CancelConn>>,
+}
+
+/*
+ * `PQcancel` is documented as safe to call from any thread, and the pg_cancel object is never
+ * modified after its creation.
+ */
+unsafe impl Send for Cancel {}
+unsafe impl Sync for Cancel {}
+
+impl CancelToken {
+    pub(crate) fn new(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        let cancel = unsafe { pq_sys::PQgetCancel(conn.into()) };
+
+        if cancel.is_null() {
+            return Err(crate::errors::Error::Backend(
+                "Unable to create the cancel object".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            cancel: std::sync::Arc::new(cancel.into()),
+            #[cfg(feature = "v17")]
+            conn: std::sync::Arc::new(std::sync::Mutex::new(CancelConn::new(conn)?)),
+        })
+    }
+
+    /**
+     * Requests that the server abandon processing of the current command, blocking until the
+     * request is sent.
+     *
+     * See `libpq::connection::Cancel::request`.
+     */
+    pub fn request(&self) -> crate::errors::Result {
+        self.cancel.request()
+    }
+
+    /**
+     * Requests that the server abandon processing of the current command, waiting at most
+     * `timeout` for the request to be sent.
+     *
+     * Returns `libpq::errors::Error::Timeout` when it expires.
+     */
+    pub fn cancel_with_timeout(&self, timeout: std::time::Duration) -> crate::errors::Result {
+        #[cfg(all(feature = "v17", unix))]
+        {
+            let deadline = std::time::Instant::now() + timeout;
+            self.start()?;
+
+            loop {
+                let hint = self.poll();
+
+                match hint.status {
+                    crate::poll::Status::Ok => return Ok(()),
+                    crate::poll::Status::Failed => return Err(self.conn()?.error()),
+                    _ => (),
+                }
+
+                let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+
+                if remaining.is_zero() {
+                    return Err(crate::errors::Error::Timeout);
+                }
+
+                hint.wait(Some(remaining))?;
+            }
+        }
+
+        #[cfg(not(all(feature = "v17", unix)))]
+        {
+            // `PQcancel` has no timeout: run it in its own thread and stop waiting for it.
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let token = self.clone();
+
+            std::thread::spawn(move || {
+                sender.send(token.request()).ok();
+            });
+
+            receiver
+                .recv_timeout(timeout)
+                .unwrap_or(Err(crate::errors::Error::Timeout))
+        }
+    }
+
+    /**
+     * Starts a non-blocking cancel request, drive it with [`CancelToken::poll`].
+     *
+     * Only one request at a time can be in progress with a token and its clones.
+     *
+     * See [PQcancelStart](https://www.postgresql.org/docs/current/libpq-cancel.html#LIBPQ-PQCANCELSTART).
+     */
+    #[cfg(feature = "v17")]
+    pub fn start(&self) -> crate::errors::Result {
+        let conn = self.conn()?;
+
+        unsafe { pq_sys::PQcancelReset(conn.conn) };
+
+        if unsafe { pq_sys::PQcancelStart(conn.conn) } == 1 {
+            Ok(())
+        } else {
+            Err(conn.error())
+        }
+    }
+
+    /**
+     * Polls the cancel request started with [`CancelToken::start`], until `libpq::poll::Status::Ok`
+     * or `libpq::poll::Status::Failed`.
+     *
+     * See [PQcancelPoll](https://www.postgresql.org/docs/current/libpq-cancel.html#LIBPQ-PQCANCELPOLL).
+     */
+    #[cfg(feature = "v17")]
+    pub fn poll(&self) -> crate::poll::Hint {
+        let Ok(conn) = self.conn() else {
+            return crate::poll::Hint {
+                status: crate::poll::Status::Failed,
+                socket: None,
+            };
+        };
+
+        let status = unsafe { pq_sys::PQcancelPoll(conn.conn) }.into();
+        let socket = unsafe { pq_sys::PQcancelSocket(conn.conn) };
+
+        crate::poll::Hint {
+            status,
+            socket: (socket >= 0).then_some(socket),
+        }
+    }
+
+    #[cfg(feature = "v17")]
+    fn conn(&self) -> crate::errors::Result<std::sync::MutexGuard<'_, CancelConn>> {
+        self.conn.lock().map_err(|_| crate::errors::Error::Poisoned)
+    }
+}
+
+/**
+ * Owned `PGcancelConn`, the v17 cancel connection.
+ */
+#[cfg(feature = "v17")]
+#[derive(Debug)]
+struct CancelConn {
+    conn: *mut pq_sys::PGcancelConn,
+}
+
+#[cfg(feature = "v17")]
+unsafe impl Send for CancelConn {}
+
+#[cfg(feature = "v17")]
+impl CancelConn {
+    fn new(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        let conn = unsafe { pq_sys::PQcancelCreate(conn.into()) };
+
+        if conn.is_null() {
+            return Err(crate::errors::Error::Backend(
+                "Unable to create the cancel connection".to_string(),
+            ));
+        }
+
+        Ok(Self { conn })
+    }
+
+    fn error(&self) -> crate::errors::Error {
+        let message = crate::ffi::to_string(unsafe { pq_sys::PQcancelErrorMessage(self.conn) })
+            .unwrap_or_default();
+
+        crate::errors::Error::Backend(message.trim_end().to_string())
+    }
+}
+
+#[cfg(feature = "v17")]
+impl Drop for CancelConn {
+    fn drop(&mut self) {
+        unsafe { pq_sys::PQcancelFinish(self.conn) };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn cancel_token() {
+        let conn = crate::test::new_conn();
+        let token = conn.cancel_token().unwrap();
+
+        let thread = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            token
+                .cancel_with_timeout(std::time::Duration::from_secs(5))
+                .unwrap();
+        });
+
+        let result = conn.exec("SELECT pg_sleep(10)");
+        thread.join().unwrap();
+
+        assert_eq!(result.status(), crate::Status::FatalError);
+        assert_eq!(
+            result.error_field(crate::result::ErrorField::Sqlstate),
+            Ok(Some("57014"))
+        );
+    }
+}
@@ -0,0 +1,502 @@
+/**
+ * Size of the chunks sent to the server with `PQputCopyData`.
+ */
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/**
+ * Formats announced by the server when a COPY starts, see
+ * [COPY Protocol](https://www.postgresql.org/docs/current/protocol-flow.html#PROTOCOL-COPY).
+ *
+ * The text and CSV formats are both reported as `libpq::Format::Text`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CopyFormatInfo {
+    /** Overall format of the COPY data. */
+    pub format: crate::Format,
+    /** Format of each column, all equal to `format` with the current protocol. */
+    pub column_formats: Vec<crate::Format>,
+}
+
+impl CopyFormatInfo {
+    pub(crate) fn from_result(result: &crate::PQResult) -> Self {
+        let format = if result.binary_tuples() {
+            crate::Format::Binary
+        } else {
+            crate::Format::Text
+        };
+
+        Self {
+            format,
+            column_formats: (0..result.nfields())
+                .map(|x| result.field_format(x))
+                .collect(),
+        }
+    }
+
+    /**
+     * Whether the COPY data is in binary format.
+     */
+    pub fn is_binary(&self) -> bool {
+        self.format == crate::Format::Binary
+    }
+}
+
+/**
+ * Writer sending data to a `COPY … FROM STDIN` command.
+ *
+ * Created by `libpq::Connection::copy_in`. Data is buffered and sent by chunks, the COPY
+ * succeeds only once [`CopyInWriter::finish`] is called: a writer dropped before aborts the
+ * COPY, so a failure while producing the data never commits partial data.
+ *
+ * ```no_run
+ * use std::io::Write;
+ *
+ * # let conn = libpq::Connection::new("")?;
+ * let mut writer = conn.copy_in("COPY item (id, name) FROM STDIN")?;
+ * writeln!(writer, "1\tfoo").unwrap();
+ * writeln!(writer, "2\tbar").unwrap();
+ * writer.finish()?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct CopyInWriter<'c> {
+    conn: &'c crate::Connection,
+    format: CopyFormatInfo,
+    buffer: Vec<u8>,
+    throttle: Option<crate::connection::Throttle>,
+    done: bool,
+}
+
+impl<'c> CopyInWriter<'c> {
+    pub(crate) fn new(conn: &'c crate::Connection, format: CopyFormatInfo) -> Self {
+        Self {
+            conn,
+            format,
+            buffer: Vec::with_capacity(CHUNK_SIZE),
+            throttle: None,
+            done: false,
+        }
+    }
+
+    /**
+     * Formats the server expects the data in.
+     */
+    pub fn format_info(&self) -> &CopyFormatInfo {
+        &self.format
+    }
+
+    /**
+     * Limits the data sent to `bytes_per_second`, so a bulk load doesn't saturate a shared link.
+     *
+     * The writer sleeps before sending a chunk as long as needed to stay under the limit.
+     */
+    pub fn throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(crate::connection::Throttle::new(bytes_per_second));
+        self
+    }
+
+    /**
+     * Sends the remaining data and ends the COPY.
+     *
+     * Returns the final `libpq::Status::CommandOk` result, or the error reported by the server,
+     * like a malformed row.
+     */
+    pub fn finish(mut self) -> crate::errors::Result<crate::PQResult> {
+        self.send()?;
+        self.end(None)
+    }
+
+    /**
+     * Aborts the COPY, the server reports `message` as the failure reason.
+     */
+    pub fn abort(mut self, message: &str) -> crate::errors::Result {
+        match self.end(Some(message)) {
+            // The expected outcome of an aborted COPY.
+            Err(crate::errors::Error::Query { .. }) => Ok(()),
+            Err(err) => Err(err),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn send(&mut self) -> crate::errors::Result {
+        if !self.buffer.is_empty() {
+            if let Some(throttle) = &mut self.throttle {
+                throttle.consume(self.buffer.len());
+            }
+
+            self.conn.put_copy_data(&self.buffer)?;
+            self.buffer.clear();
+        }
+
+        Ok(())
+    }
+
+    fn end(&mut self, errormsg: Option<&str>) -> crate::errors::Result<crate::PQResult> {
+        self.done = true;
+        self.conn.put_copy_end(errormsg)?;
+
+        let mut last = None;
+
+        while let Some(result) = self.conn.result() {
+            last = Some(self.conn.check_result(result));
+        }
+
+        last.unwrap_or_else(|| self.conn.error())
+    }
+}
+
+impl std::io::Write for CopyInWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send().map_err(std::io::Error::other)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.send().map_err(std::io::Error::other)
+    }
+}
+
+impl Drop for CopyInWriter<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        log::trace!("Aborting unfinished COPY");
+
+        self.end(Some("COPY aborted by the client")).ok();
+    }
+}
+
+/**
+ * Reader receiving the data of a `COPY … TO STDOUT` command.
+ *
+ * Created by `libpq::Connection::copy_out`. The data can be read as a byte stream with
+ * `std::io::Read`, or row by row as an iterator: with the text and CSV formats, each item is a
+ * row. The iteration ends at the end of the COPY, or after an `Err` item if it fails.
+ *
+ * Dropping the reader before its end reads and discards the remaining data, so the connection
+ * is ready for the next command. See [`CopyOutReader::cancel_on_drop`] to avoid transferring
+ * it.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let reader = conn.copy_out("COPY item TO STDOUT")?;
+ *
+ * for row in reader {
+ *     print!("{}", String::from_utf8_lossy(&row?));
+ * }
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct CopyOutReader<'c> {
+    conn: &'c crate::Connection,
+    format: CopyFormatInfo,
+    chunk: Option<crate::connection::PqBytes>,
+    pos: usize,
+    result: Option<crate::PQResult>,
+    throttle: Option<crate::connection::Throttle>,
+    cancel_on_drop: bool,
+    done: bool,
+}
+
+impl<'c> CopyOutReader<'c> {
+    pub(crate) fn new(conn: &'c crate::Connection, format: CopyFormatInfo) -> Self {
+        Self {
+            conn,
+            format,
+            chunk: None,
+            pos: 0,
+            result: None,
+            throttle: None,
+            cancel_on_drop: false,
+            done: false,
+        }
+    }
+
+    /**
+     * Cancels the COPY when the reader is dropped before its end, instead of receiving all the
+     * remaining data.
+     *
+     * A canceled COPY aborts the current transaction block, if any.
+     */
+    pub fn cancel_on_drop(mut self) -> Self {
+        self.cancel_on_drop = true;
+        self
+    }
+
+    /**
+     * Formats the server sends the data in.
+     */
+    pub fn format_info(&self) -> &CopyFormatInfo {
+        &self.format
+    }
+
+    /**
+     * Limits the data received to `bytes_per_second`, so an export doesn't saturate a shared
+     * link.
+     *
+     * The reader sleeps after receiving a row as long as needed to stay under the limit, the
+     * server is slowed down once the socket buffers are full.
+     */
+    pub fn throttle(mut self, bytes_per_second: u64) -> Self {
+        self.throttle = Some(crate::connection::Throttle::new(bytes_per_second));
+        self
+    }
+
+    /**
+     * Reads the remaining data, if any, and returns the final `libpq::Status::CommandOk` result.
+     */
+    pub fn finish(mut self) -> crate::errors::Result<crate::PQResult> {
+        for row in self.by_ref() {
+            row?;
+        }
+
+        self.result.take().map_or_else(|| self.conn.error(), Ok)
+    }
+
+    fn next_chunk(&mut self) -> crate::errors::Result<Option<crate::connection::PqBytes>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let mut ptr = std::ptr::null_mut();
+        let nbytes = unsafe { pq_sys::PQgetCopyData(self.conn.into(), &mut ptr, 0) };
+
+        match nbytes {
+            -1 => {
+                log::trace!("End of copy data");
+
+                self.done = true;
+                self.end()?;
+
+                Ok(None)
+            }
+            nbytes if nbytes > 0 => {
+                log::trace!("Receiving copy data ({nbytes} bytes)");
+                self.conn.count_copy_received(nbytes as usize);
+
+                if let Some(throttle) = &mut self.throttle {
+                    throttle.consume(nbytes as usize);
+                }
+
+                Ok(Some(crate::connection::PqBytes::from_raw(
+                    ptr as *const u8,
+                    nbytes as usize,
+                )))
+            }
+            _ => {
+                self.done = true;
+                let err = self.conn.error();
+                // Drain the results to leave the connection usable.
+                while self.conn.result().is_some() {}
+
+                err
+            }
+        }
+    }
+
+    fn end(&mut self) -> crate::errors::Result {
+        while let Some(result) = self.conn.result() {
+            let result = self.conn.check_result(result);
+
+            if result.is_err() {
+                while self.conn.result().is_some() {}
+            }
+
+            self.result = Some(result?);
+        }
+
+        Ok(())
+    }
+}
+
+impl std::io::Read for CopyOutReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        loop {
+            if let Some(chunk) = &self.chunk {
+                if self.pos < chunk.len() {
+                    let len = buf.len().min(chunk.len() - self.pos);
+                    buf[..len].copy_from_slice(&chunk[self.pos..self.pos + len]);
+                    self.pos += len;
+
+                    return Ok(len);
+                }
+            }
+
+            self.pos = 0;
+            self.chunk = self.next_chunk().map_err(std::io::Error::other)?;
+
+            if self.chunk.is_none() {
+                return Ok(0);
+            }
+        }
+    }
+}
+
+impl Iterator for CopyOutReader<'_> {
+    type Item = crate::errors::Result<crate::connection::PqBytes>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Data partially consumed by `read` is lost, mixing both interfaces makes no sense.
+        self.chunk = None;
+
+        self.next_chunk().transpose()
+    }
+}
+
+impl Drop for CopyOutReader<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        log::trace!("Draining unfinished COPY");
+
+        if self.cancel_on_drop {
+            if let Err(err) = self.conn.cancel().request() {
+                log::trace!("Unable to cancel the COPY: {err}");
+            }
+        }
+
+        for _ in self.by_ref() {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write;
+
+    #[test]
+    fn copy_in() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE copy_in (id int, name text)");
+
+        let mut writer = conn.copy_in("COPY copy_in FROM STDIN").unwrap();
+        for id in 0..10_000 {
+            writeln!(writer, "{id}\tname {id}").unwrap();
+        }
+        let result = writer.finish().unwrap();
+        assert_eq!(result.cmd_tuples(), Ok(10_000));
+
+        let writer = conn.copy_in("COPY copy_in FROM STDIN").unwrap();
+        drop(writer);
+
+        let mut writer = conn.copy_in("COPY copy_in FROM STDIN").unwrap();
+        writeln!(writer, "1\tfoo").unwrap();
+        writer.abort("canceled").unwrap();
+
+        let mut writer = conn.copy_in("COPY copy_in FROM STDIN").unwrap();
+        writeln!(writer, "not a number\tfoo").unwrap();
+        assert!(writer.finish().is_err());
+
+        let result = conn.exec("SELECT count(*) FROM copy_in");
+        assert_eq!(result.value(0, 0), Some(&b"10000"[..]));
+
+        assert!(conn.copy_in("SELECT 1").is_err());
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+
+        conn.reset_stats();
+        let start = std::time::Instant::now();
+        let mut writer = conn
+            .copy_in("COPY copy_in FROM STDIN")
+            .unwrap()
+            .throttle(20_000);
+        for id in 0..1_000 {
+            writeln!(writer, "{id}\tname {id}").unwrap();
+        }
+        writer.flush().unwrap();
+        writer.abort("throttled").unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(500));
+
+        let stats = conn.stats();
+        assert_eq!(stats.queries_sent, 1);
+        assert_eq!(stats.copy_messages_sent, 1);
+        assert_eq!(stats.copy_bytes_sent, 12_780);
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    fn copy_out() {
+        use std::io::Read;
+
+        let conn = crate::test::new_conn();
+
+        let rows = conn
+            .copy_out("COPY (SELECT x, 'name ' || x FROM generate_series(1, 3) x) TO STDOUT")
+            .unwrap()
+            .map(|row| String::from_utf8(row.unwrap().into_vec()).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec!["1\tname 1\n", "2\tname 2\n", "3\tname 3\n"]);
+        assert_eq!(conn.stats().copy_messages_received, 3);
+        assert_eq!(conn.stats().copy_bytes_received, 27);
+
+        let mut reader = conn
+            .copy_out("COPY (SELECT x FROM generate_series(1, 10000) x) TO STDOUT")
+            .unwrap();
+        let mut data = String::new();
+        reader.read_to_string(&mut data).unwrap();
+        assert_eq!(data.lines().count(), 10_000);
+        assert_eq!(reader.finish().unwrap().cmd_tuples(), Ok(10_000));
+
+        let mut reader = conn
+            .copy_out("COPY (SELECT 1 / (2 - x) FROM generate_series(1, 3) x) TO STDOUT")
+            .unwrap();
+        assert!(reader.any(|row| row.is_err()));
+        assert!(reader.next().is_none());
+
+        let mut reader = conn
+            .copy_out("COPY (SELECT generate_series(1, 10000)) TO STDOUT")
+            .unwrap();
+        assert!(reader.next().unwrap().is_ok());
+        drop(reader);
+
+        let mut reader = conn
+            .copy_out("COPY (SELECT generate_series(1, 100000000)) TO STDOUT")
+            .unwrap()
+            .cancel_on_drop();
+        assert!(reader.next().unwrap().is_ok());
+        let start = std::time::Instant::now();
+        drop(reader);
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+
+        assert!(conn.copy_out("COPY copy_out FROM STDIN").is_err());
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    fn format_info() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE format_info (id int, name text)");
+
+        let writer = conn
+            .copy_in("COPY format_info FROM STDIN (FORMAT csv)")
+            .unwrap();
+        assert_eq!(
+            writer.format_info(),
+            &super::CopyFormatInfo {
+                format: crate::Format::Text,
+                column_formats: vec![crate::Format::Text; 2],
+            }
+        );
+        writer.abort("done").unwrap();
+
+        let reader = conn
+            .copy_out("COPY format_info TO STDOUT (FORMAT binary)")
+            .unwrap();
+        assert!(reader.format_info().is_binary());
+        assert_eq!(
+            reader.format_info().column_formats,
+            vec![crate::Format::Binary; 2]
+        );
+        reader.finish().unwrap();
+    }
+}
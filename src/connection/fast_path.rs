@@ -0,0 +1,30 @@
+/**
+ * Argument of a fast-path function call, see `libpq::Connection::fn_call`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FnArg<'a> {
+    /** A 4 bytes integer, like `int4` or `oid`. */
+    Int(i32),
+    /** Any value, in the binary format of its type. */
+    Bytes(&'a [u8]),
+}
+
+/**
+ * Expected result of a fast-path function call, see `libpq::Connection::fn_call`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FnResultKind {
+    /** A 4 bytes integer. */
+    Int,
+    /** A value of at most `max_len` bytes, in the binary format of its type. */
+    Bytes { max_len: usize },
+}
+
+/**
+ * Value returned by a fast-path function call.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FnValue {
+    Int(i32),
+    Bytes(Vec<u8>),
+}
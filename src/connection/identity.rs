@@ -0,0 +1,91 @@
+/**
+ * Identity of the server a connection is attached to.
+ *
+ * Created by `libpq::Connection::identity`. Compare the `system_identifier` of two identities
+ * to check that a reconnection, after a failover for example, reached the same cluster or one
+ * of its physical replicas.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Identity {
+    /** See `libpq::Connection::server_version`. */
+    pub server_version: i32,
+    /**
+     * Unique identifier of the cluster, shared by its physical replicas.
+     *
+     * `None` without the privilege to execute `pg_control_system()`.
+     */
+    pub system_identifier: Option<u64>,
+    /**
+     * Timeline of the server: the current one on a replication connection, the one of the last
+     * checkpoint otherwise.
+     *
+     * `None` without the privilege to execute `pg_control_checkpoint()`.
+     */
+    pub timeline: Option<u32>,
+    /** Current database, `None` on a physical replication connection. */
+    pub database: Option<String>,
+    pub user: String,
+}
+
+impl Identity {
+    pub(crate) fn new(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        if Self::is_replication(conn)? {
+            Self::identify_system(conn)
+        } else {
+            Self::query(conn)
+        }
+    }
+
+    fn is_replication(conn: &crate::Connection) -> crate::errors::Result<bool> {
+        let replication = conn.info_value("replication")?;
+
+        Ok(!matches!(
+            replication
+                .as_deref()
+                .map(str::to_ascii_lowercase)
+                .as_deref(),
+            None | Some("" | "false" | "off" | "no" | "0")
+        ))
+    }
+
+    fn query(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        // The control functions are restricted to superusers by default, check the privilege
+        // first to not abort the current transaction.
+        let result = conn.exec_checked(
+            "SELECT current_database(), current_user,
+                CASE WHEN has_function_privilege('pg_control_system()', 'EXECUTE')
+                    THEN (SELECT system_identifier::text FROM pg_control_system())
+                END,
+                CASE WHEN has_function_privilege('pg_control_checkpoint()', 'EXECUTE')
+                    THEN (SELECT timeline_id::text FROM pg_control_checkpoint())
+                END",
+        )?;
+
+        Ok(Self {
+            server_version: conn.server_version(),
+            database: result.get(0, 0)?,
+            user: result.get(0, 1)?,
+            system_identifier: Self::parse(result.get(0, 2)?)?,
+            timeline: Self::parse(result.get(0, 3)?)?,
+        })
+    }
+
+    fn identify_system(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        let result = conn.exec_checked("IDENTIFY_SYSTEM")?;
+
+        Ok(Self {
+            server_version: conn.server_version(),
+            system_identifier: Self::parse(result.get(0, 0)?)?,
+            timeline: Self::parse(result.get(0, 1)?)?,
+            database: result.get(0, 3)?,
+            user: conn.user()?,
+        })
+    }
+
+    fn parse<T: std::str::FromStr>(value: Option<String>) -> crate::errors::Result<Option<T>>
+    where
+        crate::errors::Error: From<T::Err>,
+    {
+        Ok(value.map(|x| x.parse()).transpose()?)
+    }
+}
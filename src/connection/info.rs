@@ -1,5 +1,18 @@
 use std::os::raw;
 
+/**
+ * How a connection option should be displayed in a connect dialog, see `Info::dispchar`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisplayMode {
+    /** Display the value as is. */
+    Normal,
+    /** Hide the value, like a password. */
+    Password,
+    /** Debug option, don't show it by default. */
+    Debug,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Info {
     pub keyword: String,
@@ -25,6 +38,13 @@ impl Info {
     pub fn defaults() -> crate::errors::Result<Vec<Self>> {
         unsafe {
             let raw = pq_sys::PQconndefaults();
+
+            if raw.is_null() {
+                return Err(crate::errors::Error::Backend(
+                    "Unable to read the connection defaults".to_string(),
+                ));
+            }
+
             let info = Self::vec_from_nta(raw);
             pq_sys::PQconninfoFree(raw);
 
@@ -32,6 +52,53 @@ impl Info {
         }
     }
 
+    /**
+     * Returns the default connection options, keyed by keyword.
+     */
+    pub fn defaults_map() -> crate::errors::Result<std::collections::BTreeMap<String, Self>> {
+        let defaults = Self::defaults()?
+            .into_iter()
+            .map(|x| (x.keyword.clone(), x))
+            .collect();
+
+        Ok(defaults)
+    }
+
+    /**
+     * Returns the default of the connection option `keyword`, `None` if this option is unknown
+     * to libpq.
+     */
+    pub fn get(keyword: &str) -> crate::errors::Result<Option<Self>> {
+        let info = Self::defaults()?.into_iter().find(|x| x.keyword == keyword);
+
+        Ok(info)
+    }
+
+    /**
+     * Returns how the option should be displayed, according to `dispchar`.
+     */
+    pub fn display_mode(&self) -> DisplayMode {
+        match self.dispchar.as_str() {
+            "*" => DisplayMode::Password,
+            "D" => DisplayMode::Debug,
+            _ => DisplayMode::Normal,
+        }
+    }
+
+    /**
+     * Returns `true` if the value should be hidden, like a password.
+     */
+    pub fn is_password(&self) -> bool {
+        self.display_mode() == DisplayMode::Password
+    }
+
+    /**
+     * Returns `true` for debug options, not shown by default.
+     */
+    pub fn is_debug(&self) -> bool {
+        self.display_mode() == DisplayMode::Debug
+    }
+
     /**
      * Returns parsed connection options from the provided connection string.
      *
@@ -112,14 +179,30 @@ impl Info {
     }
 }
 
+/**
+ * Deprecated since 4.3.0, use `Info::defaults` instead: it returns all the options and reports
+ * the errors. Rust can't mark a trait implementation as deprecated.
+ *
+ * Returns the first default option, or an empty option, logging the error, if libpq can't
+ * return them.
+ */
 impl Default for Info {
     fn default() -> Self {
-        unsafe {
-            let raw = pq_sys::PQconndefaults();
-            let info = raw.try_into().unwrap();
-            pq_sys::PQconninfoFree(raw);
+        match Self::defaults().map(Vec::into_iter).map(|mut x| x.next()) {
+            Ok(Some(info)) => info,
+            err => {
+                log::error!("Unable to read the connection defaults: {err:?}");
 
-            info
+                Self {
+                    keyword: String::new(),
+                    envvar: None,
+                    compiled: None,
+                    val: None,
+                    label: None,
+                    dispchar: String::new(),
+                    dispsize: 0,
+                }
+            }
         }
     }
 }
@@ -148,6 +231,31 @@ mod test {
 
     #[test]
     fn defaults() {
-        let _ = crate::connection::Info::defaults();
+        let defaults = crate::connection::Info::defaults().unwrap();
+        assert_eq!(crate::connection::Info::default(), defaults[0]);
+    }
+
+    #[test]
+    fn defaults_map() {
+        let defaults = crate::connection::Info::defaults_map().unwrap();
+        assert_eq!(
+            defaults.get("host").map(|x| x.envvar.clone()),
+            Some(Some("PGHOST".to_string()))
+        );
+
+        let password = crate::connection::Info::get("password").unwrap().unwrap();
+        assert_eq!(
+            password.display_mode(),
+            crate::connection::DisplayMode::Password
+        );
+        assert!(password.is_password());
+
+        let options = crate::connection::Info::get("options").unwrap().unwrap();
+        assert_eq!(
+            options.display_mode(),
+            crate::connection::DisplayMode::Normal
+        );
+
+        assert!(crate::connection::Info::get("unknow").unwrap().is_none());
     }
 }
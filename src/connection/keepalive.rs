@@ -0,0 +1,168 @@
+/**
+ * Keeps an idle connection alive, preventing NAT or firewall drops.
+ *
+ * Call [`Keepalive::touch`] after using the connection and [`Keepalive::tick`] periodically:
+ * an empty query is sent when the connection has been idle for longer than `interval`.
+ *
+ * See also `libpq::connection::SyncConnection::spawn_keepalive` to run it in a thread.
+ */
+#[derive(Clone, Debug)]
+pub struct Keepalive {
+    interval: std::time::Duration,
+    last_activity: std::time::Instant,
+}
+
+impl Keepalive {
+    pub fn new(interval: std::time::Duration) -> Self {
+        Self {
+            interval,
+            last_activity: std::time::Instant::now(),
+        }
+    }
+
+    /**
+     * Records an activity on the connection.
+     */
+    pub fn touch(&mut self) {
+        self.last_activity = std::time::Instant::now();
+    }
+
+    /**
+     * Time left before the connection is considered idle.
+     */
+    pub fn remaining(&self) -> std::time::Duration {
+        self.interval.saturating_sub(self.last_activity.elapsed())
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.remaining().is_zero()
+    }
+
+    /**
+     * Sends an empty query if the connection is idle for longer than the interval.
+     *
+     * Nothing is sent while a command is in progress. Returns `true` if the query was sent.
+     */
+    pub fn tick(&mut self, conn: &crate::Connection) -> crate::errors::Result<bool> {
+        if !self.is_due() || conn.transaction_status() == crate::transaction::Status::Active {
+            return Ok(false);
+        }
+
+        log::trace!("Sending keepalive");
+
+        if conn.exec(";").status() != crate::Status::EmptyQuery {
+            return conn.error();
+        }
+
+        self.touch();
+
+        Ok(true)
+    }
+}
+
+/**
+ * A keepalive running in a background thread, stopped when dropped.
+ *
+ * See `libpq::connection::SyncConnection::spawn_keepalive`.
+ */
+#[derive(Debug)]
+pub struct KeepaliveHandle {
+    stop: Option<std::sync::mpsc::Sender<()>>,
+    thread: Option<std::thread::JoinHandle<crate::errors::Result>>,
+}
+
+impl KeepaliveHandle {
+    pub(crate) fn spawn(
+        conn: std::sync::Arc<crate::connection::SyncConnection>,
+        interval: std::time::Duration,
+    ) -> Self {
+        let (stop, stopped) = std::sync::mpsc::channel();
+
+        let thread = std::thread::spawn(move || {
+            let mut keepalive = Keepalive::new(interval);
+
+            loop {
+                keepalive.last_activity = keepalive.last_activity.max(conn.last_activity());
+
+                match stopped.recv_timeout(keepalive.remaining()) {
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => (),
+                    _ => return Ok(()),
+                }
+
+                keepalive.last_activity = keepalive.last_activity.max(conn.last_activity());
+
+                if !keepalive.is_due() {
+                    continue;
+                }
+
+                match conn.try_lock() {
+                    Some(conn) => {
+                        let conn = conn?;
+                        keepalive.tick(&conn)?;
+                    }
+                    None => keepalive.touch(),
+                }
+            }
+        });
+
+        Self {
+            stop: Some(stop),
+            thread: Some(thread),
+        }
+    }
+
+    /**
+     * Stops the keepalive thread and returns the error which stopped it, if any.
+     */
+    pub fn stop(mut self) -> crate::errors::Result {
+        self.join()
+    }
+
+    fn join(&mut self) -> crate::errors::Result {
+        self.stop.take();
+
+        match self.thread.take().map(std::thread::JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(crate::errors::Error::Unknow),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for KeepaliveHandle {
+    fn drop(&mut self) {
+        self.join().ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn tick() {
+        let conn = crate::test::new_conn();
+        let mut keepalive = crate::connection::Keepalive::new(std::time::Duration::from_millis(50));
+
+        assert_eq!(keepalive.tick(&conn), Ok(false));
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(keepalive.is_due());
+        assert_eq!(keepalive.tick(&conn), Ok(true));
+        assert!(!keepalive.is_due());
+    }
+
+    #[test]
+    fn spawn() {
+        let conn = std::sync::Arc::new(crate::connection::SyncConnection::new(
+            crate::test::new_conn(),
+        ));
+        let keepalive = conn.spawn_keepalive(std::time::Duration::from_millis(20));
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(
+            conn.exec("SELECT 1").unwrap().status(),
+            crate::Status::TuplesOk
+        );
+
+        assert_eq!(keepalive.stop(), Ok(()));
+    }
+}
@@ -1,14 +1,45 @@
 mod buffer;
+mod cache;
 mod cancel;
+mod config;
+mod copy;
+mod fast_path;
+mod identity;
 mod info;
+mod keepalive;
+mod notice;
 mod notify;
+mod parameter;
+mod passfile;
+mod query_log;
+mod read_only;
+mod resolve;
+mod service;
+mod statement;
+mod stats;
 mod status;
+mod stream;
+mod sync;
 
 pub use buffer::*;
+pub use cache::*;
 pub use cancel::*;
+pub use config::*;
+pub use copy::*;
+pub use fast_path::*;
+pub use identity::*;
 pub use info::*;
+pub use keepalive::*;
+pub use notice::*;
 pub use notify::*;
+pub use parameter::*;
+pub use query_log::*;
+pub use read_only::*;
+pub use statement::*;
+pub use stats::*;
 pub use status::*;
+pub use stream::*;
+pub use sync::*;
 
 pub type NoticeProcessor = pq_sys::PQnoticeProcessor;
 pub type NoticeReceiver = pq_sys::PQnoticeReceiver;
@@ -46,11 +77,90 @@ pub fn socket_poll(
     }
 }
 
-#[derive(Clone)]
+/**
+ * Waits until `sock` is ready for reading and/or writing, or until `timeout` expires.
+ *
+ * Returns `false` on timeout or when the wait is interrupted by a signal.
+ */
+#[cfg(unix)]
+pub(crate) fn wait_socket(
+    sock: i32,
+    for_read: bool,
+    for_write: bool,
+    timeout: Option<std::time::Duration>,
+) -> crate::errors::Result<bool> {
+    let mut events = 0;
+
+    if for_read {
+        events |= libc::POLLIN;
+    }
+
+    if for_write {
+        events |= libc::POLLOUT;
+    }
+
+    let mut fd = libc::pollfd {
+        fd: sock,
+        events,
+        revents: 0,
+    };
+
+    let timeout = timeout
+        .map(|x| x.as_millis().min(i32::MAX as u128) as i32)
+        .unwrap_or(-1);
+
+    let status = unsafe { libc::poll(&mut fd, 1, timeout) };
+
+    if status < 0 {
+        let error = std::io::Error::last_os_error();
+
+        // Interrupted by a signal: report it as a timeout so the caller can check its state.
+        if error.kind() == std::io::ErrorKind::Interrupted {
+            return Ok(false);
+        }
+
+        return Err(crate::errors::Error::Backend(error.to_string()));
+    }
+
+    Ok(status > 0)
+}
+
 pub struct Connection {
     conn: *mut pq_sys::PGconn,
+    param_types: std::cell::RefCell<std::collections::HashMap<std::any::TypeId, crate::Oid>>,
+    comment_hook: std::cell::RefCell<Option<std::sync::Arc<CommentHook>>>,
+    parameter_watch: std::cell::RefCell<Option<ParameterWatch>>,
+    notice_trampoline: std::cell::RefCell<Option<NoticeTrampoline>>,
+    notice_arg: std::cell::Cell<*mut raw::c_void>,
+    query_logger: std::cell::RefCell<Option<Box<QueryLogger>>>,
+    log_redaction: std::cell::Cell<Redaction>,
+    stats: std::cell::Cell<Stats>,
+    types: std::cell::RefCell<std::collections::HashMap<crate::Oid, crate::Type>>,
 }
 
+/**
+ * Callback returning the tags of the comment prefixing each query, see
+ * `libpq::Connection::set_comment_hook`.
+ */
+pub type CommentHook = dyn Fn() -> Vec<(String, String)> + Send + Sync;
+
+/**
+ * Callback called with each change of a server parameter, see
+ * `libpq::Connection::set_parameter_hook`.
+ */
+pub type ParameterHook = dyn Fn(&ParameterChange) + Send;
+
+/**
+ * Callback called with each notice sent by the server, see
+ * `libpq::Connection::set_notice_handler`.
+ */
+pub type NoticeHandler = dyn Fn(Notice) + Send;
+
+/**
+ * Callback called after each query, see `libpq::Connection::set_query_logger`.
+ */
+pub type QueryLogger = dyn Fn(&QueryLog<'_>) + Send;
+
 unsafe impl Send for Connection {}
 
 include!("_async.rs");
@@ -58,7 +168,9 @@ include!("_cancel.rs");
 include!("_connect.rs");
 include!("_control.rs");
 include!("_copy.rs");
+include!("_events.rs");
 include!("_exec.rs");
+include!("_fast_path.rs");
 #[cfg(feature = "v12")]
 include!("_gss.rs");
 include!("_notice_processing.rs");
@@ -67,6 +179,8 @@ include!("_single_row_mode.rs");
 include!("_ssl.rs");
 include!("_status.rs");
 include!("_threading.rs");
+#[cfg(all(feature = "tokio", unix))]
+include!("_tokio.rs");
 include!("_trace.rs");
 
 impl Connection {
@@ -147,10 +261,15 @@ impl Connection {
         crate::pipeline::flush_request(self)
     }
 
+    /**
+     * Text values without a trailing null byte are copied into null terminated buffers, returned
+     * last: they must outlive the pointers.
+     */
+    #[allow(clippy::type_complexity)]
     fn transform_params(
         param_values: &[Option<&[u8]>],
         param_formats: &[crate::Format],
-    ) -> (Vec<*const raw::c_char>, Vec<i32>, Vec<i32>) {
+    ) -> (Vec<*const raw::c_char>, Vec<i32>, Vec<i32>, Vec<Vec<u8>>) {
         if param_values.is_empty() {
             return Default::default();
         }
@@ -158,6 +277,7 @@ impl Connection {
         let mut values = Vec::new();
         let mut formats = Vec::new();
         let mut lengths = Vec::new();
+        let mut buffers = Vec::new();
 
         for (x, value) in param_values.iter().enumerate() {
             let format = param_formats.get(x).unwrap_or(&crate::Format::Text);
@@ -165,9 +285,15 @@ impl Connection {
 
             if let Some(v) = value {
                 if format == &crate::Format::Text && v.last() != Some(&b'\0') {
-                    panic!("Param value as text should be null terminated");
+                    let mut buffer = Vec::with_capacity(v.len() + 1);
+                    buffer.extend_from_slice(v);
+                    buffer.push(b'\0');
+
+                    values.push(buffer.as_ptr() as *const raw::c_char);
+                    buffers.push(buffer);
+                } else {
+                    values.push(v.as_ptr() as *const raw::c_char);
                 }
-                values.push(v.as_ptr() as *const raw::c_char);
                 lengths.push(v.len() as i32);
             } else {
                 values.push(std::ptr::null());
@@ -175,10 +301,11 @@ impl Connection {
             }
         }
 
-        (values, formats, lengths)
+        (values, formats, lengths, buffers)
     }
 
     fn trace_query(
+        &self,
         prefix: &str,
         command: &str,
         param_types: &[crate::Oid],
@@ -190,36 +317,114 @@ impl Connection {
         if log::log_enabled!(log::Level::Trace) {
             let mut msg = prefix.to_string();
 
-            let mut p = Vec::new();
-
-            for (x, value) in param_values.iter().enumerate() {
-                let v = if let Some(s) = value {
-                    match param_formats.get(x) {
-                        Some(crate::Format::Binary) => format!("{s:?}"),
-                        _ => String::from_utf8(s.to_vec()).unwrap_or_else(|_| "�".to_string()),
-                    }
-                } else {
-                    "null".to_string()
-                };
-                let default_type = crate::types::UNKNOWN;
-                let t = crate::Type::try_from(*param_types.get(x).unwrap_or(&default_type.oid))
-                    .unwrap_or(default_type);
-
-                p.push(format!("'{v}'::{}", t.name));
-            }
-
             if !command.is_empty() {
                 write!(msg, " query '{command}'").ok();
             }
 
-            if !p.is_empty() {
-                write!(msg, " with params [{}]", p.join(", ")).ok();
+            if !param_values.is_empty() {
+                match self.format_params(param_types, param_values, param_formats) {
+                    Some(p) => write!(msg, " with params [{}]", p.join(", ")),
+                    None => write!(msg, " with {} params", param_values.len()),
+                }
+                .ok();
             }
 
             log::trace!("{msg}");
         }
     }
 
+    /**
+     * Formats the parameters as `'value'::type`, or `NULL::type`, for the logs, with the
+     * redaction set by `libpq::Connection::set_log_redaction`; `None` if the values are
+     * omitted.
+     *
+     * The NUL terminator of text values isn't part of the value.
+     */
+    fn format_params(
+        &self,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+    ) -> Option<Vec<String>> {
+        let redaction = self.log_redaction.get();
+        let mut p = Vec::new();
+
+        for (x, value) in param_values.iter().enumerate() {
+            let default_type = crate::types::UNKNOWN;
+            let t = crate::Type::try_from(*param_types.get(x).unwrap_or(&default_type.oid))
+                .unwrap_or(default_type);
+
+            let Some(s) = value else {
+                p.push(format!("NULL::{}", t.name));
+                continue;
+            };
+
+            let v = match param_formats.get(x) {
+                Some(crate::Format::Binary) => format!("{s:?}"),
+                _ => {
+                    let s = s.strip_suffix(b"\0").unwrap_or(s);
+                    String::from_utf8(s.to_vec()).unwrap_or_else(|_| "�".to_string())
+                }
+            };
+
+            p.push(format!("'{}'::{}", redaction.apply(&v)?, t.name));
+        }
+
+        Some(p)
+    }
+
+    /**
+     * Calls the query logger set by `libpq::Connection::set_query_logger`, if any.
+     */
+    #[allow(clippy::too_many_arguments)]
+    fn log_query(
+        &self,
+        command: &str,
+        statement: Option<&str>,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        start: std::time::Instant,
+        result: &crate::PQResult,
+    ) {
+        let logger = self.query_logger.borrow();
+        let Some(logger) = &*logger else {
+            return;
+        };
+
+        let status = result.status();
+        let rows = match status {
+            crate::Status::TuplesOk | crate::Status::SingleTuple => Some(result.ntuples()),
+            crate::Status::CommandOk => result.cmd_tuples().ok(),
+            _ => None,
+        };
+
+        logger(&crate::connection::QueryLog {
+            command,
+            statement,
+            params: self
+                .format_params(param_types, param_values, param_formats)
+                .unwrap_or_default(),
+            nparams: param_values.len(),
+            duration: start.elapsed(),
+            status,
+            rows,
+        });
+    }
+
+    fn trace_result(result: &crate::PQResult) {
+        match result.status() {
+            crate::Status::CopyIn => log::trace!("Start of copy in"),
+            crate::Status::CopyOut => log::trace!("Start of copy out"),
+            crate::Status::CopyBoth => log::trace!("Start of copy both"),
+            #[cfg(feature = "v14")]
+            crate::Status::PipelineSync => log::trace!("Pipeline synchronized"),
+            #[cfg(feature = "v14")]
+            crate::Status::PipelineAborted => log::trace!("Pipeline aborted"),
+            _ => (),
+        }
+    }
+
     pub(crate) fn error<T>(&self) -> crate::errors::Result<T> {
         Err(self
             .error_message()
@@ -254,7 +459,18 @@ impl TryFrom<*mut pq_sys::pg_conn> for Connection {
     type Error = crate::errors::Error;
 
     fn try_from(conn: *mut pq_sys::pg_conn) -> std::result::Result<Self, Self::Error> {
-        let s = Self { conn };
+        let s = Self {
+            conn,
+            param_types: Default::default(),
+            comment_hook: Default::default(),
+            parameter_watch: Default::default(),
+            notice_trampoline: Default::default(),
+            notice_arg: std::cell::Cell::new(std::ptr::null_mut()),
+            query_logger: Default::default(),
+            log_redaction: Default::default(),
+            stats: Default::default(),
+            types: Default::default(),
+        };
 
         if s.status() == crate::connection::Status::Bad {
             s.error()
@@ -264,8 +480,35 @@ impl TryFrom<*mut pq_sys::pg_conn> for Connection {
     }
 }
 
+/**
+ * The connection socket, `-1` if there is none, see `libpq::Connection::socket`.
+ */
+#[cfg(unix)]
+impl std::os::fd::AsRawFd for Connection {
+    fn as_raw_fd(&self) -> std::os::fd::RawFd {
+        unsafe { pq_sys::PQsocket(self.into()) }
+    }
+}
+
+/**
+ * The connection socket, `INVALID_SOCKET` if there is none, see
+ * `libpq::Connection::socket`.
+ */
+#[cfg(windows)]
+impl std::os::windows::io::AsRawSocket for Connection {
+    fn as_raw_socket(&self) -> std::os::windows::io::RawSocket {
+        let socket = unsafe { pq_sys::PQsocket(self.into()) };
+
+        // `PQsocket` returns the `SOCKET` handle as an int, -1 is sign-extended to
+        // `INVALID_SOCKET`.
+        socket as std::os::windows::io::RawSocket
+    }
+}
+
 impl Drop for Connection {
     fn drop(&mut self) {
+        self.unset_notice_handler();
+
         unsafe {
             pq_sys::PQfinish(self.into());
         }
@@ -293,6 +536,7 @@ impl std::fmt::Debug for Connection {
             .field("needs_password", &self.needs_password())
             .field("used_password", &self.used_password())
             .field("ssl_in_use", &self.ssl_in_use())
+            .field("param_types", &self.param_types)
             .finish()
     }
 }
@@ -311,6 +555,95 @@ mod test {
         thread.join().ok();
     }
 
+    #[test]
+    fn try_clone() {
+        let conn = crate::test::new_conn();
+        let clone = conn.try_clone().unwrap();
+
+        assert_ne!(conn.backend_pid(), clone.backend_pid());
+        assert_eq!(conn.db(), clone.db());
+        assert_eq!(conn.user(), clone.user());
+
+        drop(conn);
+        assert_eq!(clone.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    fn expand_dbname() {
+        let dsn = format!("{} application_name=expanded", crate::test::dsn());
+
+        let mut params = std::collections::HashMap::new();
+        params.insert("dbname", dsn.as_str());
+        params.insert("application_name", "explicit");
+
+        let conn = crate::Connection::with_params(&params, true).unwrap();
+        assert_eq!(
+            conn.parameter_status("application_name").unwrap(),
+            "explicit"
+        );
+
+        params.remove("application_name");
+        let conn = crate::Connection::with_params(&params, true).unwrap();
+        assert_eq!(
+            conn.parameter_status("application_name").unwrap(),
+            "expanded"
+        );
+
+        assert!(crate::Connection::with_params(&params, false).is_err());
+
+        let params = params
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let conn = crate::Connection::start_params(&params, true).unwrap();
+        assert_ne!(conn.status(), crate::connection::Status::Bad);
+    }
+
+    #[cfg(all(feature = "tokio", unix))]
+    #[tokio::test]
+    async fn tokio() {
+        let conn = crate::Connection::connect_async(&crate::test::dsn())
+            .await
+            .unwrap();
+        assert!(conn.is_non_blocking());
+
+        let result = conn
+            .exec_typed_async("SELECT $1::int4 + 1", &[&"41"])
+            .await
+            .unwrap();
+        assert_eq!(result.get::<i32>(0, 0), Ok(42));
+
+        let result = conn.exec_async("SELECT 1/0").await.unwrap();
+        assert_eq!(result.status(), crate::Status::FatalError);
+
+        conn.exec_async("CREATE TEMPORARY TABLE tokio (id int)")
+            .await
+            .unwrap();
+        let result = conn.exec_async("COPY tokio FROM STDIN").await.unwrap();
+        assert_eq!(result.status(), crate::Status::CopyIn);
+        for id in 0..1_000 {
+            conn.put_copy_data_async(format!("{id}\n").as_bytes())
+                .await
+                .unwrap();
+        }
+        let result = conn.put_copy_end_async(None).await.unwrap();
+        assert_eq!(result.cmd_tuples(), Ok(1_000));
+
+        let result = conn.exec_async("COPY tokio TO STDOUT").await.unwrap();
+        assert_eq!(result.status(), crate::Status::CopyOut);
+        let mut count = 0;
+        while conn.copy_data_async().await.unwrap().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 1_000);
+
+        conn.exec_async("LISTEN tokio").await.unwrap();
+        let other = crate::test::new_conn();
+        other.exec("NOTIFY tokio, 'payload'");
+        let notify = conn.notifies_async().await.unwrap();
+        assert_eq!(notify.relname().unwrap(), "tokio");
+    }
+
     #[test]
     fn reset() {
         let conn = crate::test::new_conn();
@@ -326,6 +659,67 @@ mod test {
         assert_eq!(conn.reset_poll(), crate::poll::Status::Writing);
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn poll_hint() {
+        let conn = crate::Connection::start(&crate::test::dsn()).unwrap();
+
+        let mut hint = crate::poll::Hint {
+            status: crate::poll::Status::Writing,
+            socket: conn.socket().ok(),
+        };
+
+        while hint.interest().is_some() {
+            assert!(hint.wait(Some(std::time::Duration::from_secs(5))).unwrap());
+            hint = conn.poll_hint();
+        }
+
+        assert_eq!(hint.status, crate::poll::Status::Ok);
+        assert_eq!(hint.socket, conn.socket().ok());
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+
+        conn.reset_start();
+        let mut hint = conn.reset_poll_hint();
+        assert_eq!(hint.interest(), Some(crate::poll::Interest::Write));
+        assert!(hint.socket.is_some());
+
+        while hint.interest().is_some() {
+            assert!(hint.wait(Some(std::time::Duration::from_secs(5))).unwrap());
+            hint = conn.reset_poll_hint();
+        }
+
+        assert_eq!(hint.status, crate::poll::Status::Ok);
+        assert_eq!(conn.status(), crate::connection::Status::Ok);
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn poll_failed() {
+        // A port nobody listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let conn = crate::Connection::start(&format!("host=127.0.0.1 port={port} dbname=postgres"))
+            .unwrap();
+
+        let mut hint = crate::poll::Hint {
+            status: crate::poll::Status::Writing,
+            socket: conn.socket().ok(),
+        };
+
+        while hint.interest().is_some() {
+            assert!(hint.wait(Some(std::time::Duration::from_secs(5))).unwrap());
+            hint = conn.poll_hint();
+        }
+
+        assert_eq!(hint.status, crate::poll::Status::Failed);
+        assert_eq!(conn.status(), crate::connection::Status::Bad);
+        assert!(conn.error_message().is_some());
+    }
+
     #[test]
     fn exec() {
         let conn = crate::test::new_conn();
@@ -338,6 +732,36 @@ mod test {
         assert_eq!(results.value(0, 1), Some(&b"2"[..]));
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn exec_interruptible() {
+        let conn = crate::test::new_conn();
+        let token = std::sync::atomic::AtomicBool::new(false);
+
+        let results = conn.exec_interruptible("SELECT 1", &token).unwrap();
+        assert_eq!(results.status(), crate::Status::TuplesOk);
+        assert_eq!(results.value(0, 0), Some(&b"1"[..]));
+
+        let token = std::sync::Arc::new(token);
+        let interrupter = {
+            let token = token.clone();
+
+            std::thread::spawn(move || {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                token.store(true, std::sync::atomic::Ordering::Relaxed);
+            })
+        };
+
+        let start = std::time::Instant::now();
+        let results = conn
+            .exec_interruptible("SELECT pg_sleep(10)", &token)
+            .unwrap();
+        interrupter.join().unwrap();
+
+        assert_eq!(results.status(), crate::Status::FatalError);
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
     #[test]
     fn exec_null() {
         let conn = crate::test::new_conn();
@@ -375,10 +799,152 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
+    fn exec_checked() {
+        let conn = crate::test::new_conn();
+
+        let results = conn.exec_checked("SELECT 1").unwrap();
+        assert_eq!(results.value(0, 0), Some(&b"1"[..]));
+
+        let error = conn.exec_checked("SELECT 1/0").unwrap_err();
+        assert_eq!(
+            error,
+            crate::errors::Error::Query {
+                status: crate::Status::FatalError,
+                sqlstate: Some("22012".to_string()),
+                message: "ERROR:  division by zero".to_string(),
+                detail: None,
+                hint: None,
+                position: None,
+            }
+        );
+
+        let error = conn
+            .exec_params_checked(
+                "SELECT $1",
+                &[crate::types::INT4.oid],
+                &[Some(b"foo\0")],
+                &[],
+                crate::Format::Text,
+            )
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::errors::Error::Query { sqlstate: Some(ref x), .. } if x == "22P02")
+        );
+
+        let error = conn
+            .exec_prepared_checked(Some("unknow"), &[], &[], crate::Format::Text)
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::errors::Error::Query { sqlstate: Some(ref x), .. } if x == "26000")
+        );
+
+        conn.exec("CREATE TEMPORARY TABLE exec_checked (id int PRIMARY KEY)");
+        conn.exec("INSERT INTO exec_checked VALUES (1)");
+        let error = conn
+            .exec_checked("INSERT INTO exec_checked VALUES (1)")
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::errors::Error::Query { detail: Some(ref x), hint: None, .. } if x == "Key (id)=(1) already exists.")
+        );
+
+        let error = conn.exec_checked("SELECT unknow_function()").unwrap_err();
+        assert!(matches!(
+            error,
+            crate::errors::Error::Query { hint: Some(_), .. }
+        ));
+    }
+
+    #[test]
+    fn ensure_idle() {
+        let conn = crate::test::new_conn();
+        assert_eq!(conn.ensure_idle(), Ok(crate::transaction::Status::Idle));
+
+        conn.exec("BEGIN");
+        assert_eq!(conn.ensure_idle(), Ok(crate::transaction::Status::InTrans));
+        assert_eq!(conn.transaction_status(), crate::transaction::Status::Idle);
+
+        conn.exec("BEGIN");
+        conn.exec("SELECT 1/0");
+        assert_eq!(conn.ensure_idle(), Ok(crate::transaction::Status::InError));
+        assert_eq!(conn.transaction_status(), crate::transaction::Status::Idle);
+
+        conn.send_query("BEGIN; COPY (SELECT 1) TO STDOUT").unwrap();
+        assert_eq!(conn.ensure_idle(), Ok(crate::transaction::Status::Active));
+        assert_eq!(conn.transaction_status(), crate::transaction::Status::Idle);
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    fn statement_timeout() {
+        let conn = crate::test::new_conn();
+        assert_eq!(conn.statement_timeout(), Ok(None));
+
+        conn.set_statement_timeout(Some(std::time::Duration::from_millis(100)))
+            .unwrap();
+        assert_eq!(
+            conn.statement_timeout(),
+            Ok(Some(std::time::Duration::from_millis(100)))
+        );
+
+        let error = conn.exec_checked("SELECT pg_sleep(1)").unwrap_err();
+        assert!(
+            matches!(error, crate::errors::Error::Query { sqlstate: Some(ref x), .. } if x == "57014")
+        );
+
+        conn.set_statement_timeout(None).unwrap();
+        assert_eq!(conn.statement_timeout(), Ok(None));
+
+        let dsn = format!("{} options='-c statement_timeout=2000'", crate::test::dsn());
+        let conn = crate::Connection::new(&dsn).unwrap();
+        assert_eq!(
+            conn.statement_timeout(),
+            Ok(Some(std::time::Duration::from_secs(2)))
+        );
+    }
+
+    #[test]
+    fn fn_call() {
+        let conn = crate::test::new_conn();
+        let oid = |name: &str| {
+            conn.exec(&format!("SELECT '{name}'::regproc::oid"))
+                .get::<crate::Oid>(0, 0)
+                .unwrap()
+        };
+
+        let value = unsafe {
+            conn.fn_call(
+                oid("int4pl"),
+                &[super::FnArg::Int(1), super::FnArg::Int(2)],
+                super::FnResultKind::Int,
+            )
+        };
+        assert_eq!(value, Ok(Some(super::FnValue::Int(3))));
+
+        let value = unsafe {
+            conn.fn_call(
+                oid("textcat"),
+                &[super::FnArg::Bytes(b"foo"), super::FnArg::Bytes(b"bar")],
+                super::FnResultKind::Bytes { max_len: 6 },
+            )
+        };
+        assert_eq!(value, Ok(Some(super::FnValue::Bytes(b"foobar".to_vec()))));
+
+        let value = unsafe {
+            conn.fn_call(
+                oid("int4pl"),
+                &[super::FnArg::Int(i32::MAX), super::FnArg::Int(1)],
+                super::FnResultKind::Int,
+            )
+        };
+        assert!(value.is_err());
+    }
+
+    #[test]
     fn exec_text() {
         let conn = crate::test::new_conn();
-        let _ = conn.exec_params("SELECT $1", &[], &[Some(b"foo")], &[], crate::Format::Text);
+        let results = conn.exec_params("SELECT $1", &[], &[Some(b"foo")], &[], crate::Format::Text);
+        assert_eq!(results.status(), crate::Status::TuplesOk);
+        assert_eq!(results.value(0, 0), Some(&b"foo"[..]));
     }
 
     #[test]
@@ -412,6 +978,47 @@ mod test {
         }
     }
 
+    #[test]
+    fn results() {
+        let conn = crate::test::new_conn();
+        conn.send_query("SELECT 1; ; SELECT 1/0").unwrap();
+
+        let results = conn.results().collect::<Vec<_>>();
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].is_error());
+        assert!(results[1].is_error());
+
+        conn.send_query("").unwrap();
+        let results = conn.results().collect::<Vec<_>>();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_empty_query());
+
+        conn.send_query("COPY (SELECT 1) TO STDOUT; SELECT 1")
+            .unwrap();
+        assert_eq!(conn.results().count(), 1);
+    }
+
+    #[test]
+    fn collect_results() {
+        let conn = crate::test::new_conn();
+        conn.send_query("SELECT 1; SELECT 1/0; SELECT 2").unwrap();
+
+        let results = conn.collect_results();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().value(0, 0), Some(&b"1"[..]));
+        assert!(matches!(
+            &results[1],
+            Err(crate::errors::Error::Query { sqlstate: Some(sqlstate), .. }) if sqlstate == "22012"
+        ));
+
+        conn.send_query("SELECT 1; SELECT pg_terminate_backend(pg_backend_pid())")
+            .unwrap();
+        let results = conn.collect_results();
+        assert!(results[0].is_ok());
+        assert!(results.last().unwrap().is_err());
+        assert!(conn.collect_results()[0].is_err());
+    }
+
     #[test]
     fn send_query_params() {
         let conn = crate::test::new_conn();
@@ -431,7 +1038,6 @@ mod test {
         let result = conn.result().unwrap();
         assert_eq!(result.value(0, 0), Some(&b"fooo"[..]));
 
-        #[cfg(unix)]
         {
             let options = crate::print::Options {
                 header: true,
@@ -496,10 +1102,272 @@ mod test {
         assert_eq!(conn.client_encoding(), crate::Encoding::SQL_ASCII);
     }
 
+    #[test]
+    fn comment_hook() {
+        let conn = crate::test::new_conn();
+        conn.set_comment_hook(|| {
+            vec![
+                ("traceparent".to_string(), "00-abc-01".to_string()),
+                ("action".to_string(), "it's */ done".to_string()),
+            ]
+        });
+
+        assert_eq!(
+            conn.commented("SELECT 1"),
+            "/*action='it%27s%20%2A%2F%20done',traceparent='00-abc-01'*/ SELECT 1"
+        );
+
+        let result = conn.exec("SELECT query FROM pg_stat_activity WHERE pid = pg_backend_pid()");
+        assert!(result
+            .get::<String>(0, 0)
+            .unwrap()
+            .starts_with("/*action='it%27s%20%2A%2F%20done',traceparent='00-abc-01'*/ SELECT"));
+
+        let result = conn.exec_typed("SELECT $1::int4 + 1", &[&"1"]).unwrap();
+        assert_eq!(result.get::<i32>(0, 0), Ok(2));
+
+        conn.unset_comment_hook();
+        assert_eq!(conn.commented("SELECT 1"), "SELECT 1");
+    }
+
+    #[test]
+    fn reentrant_comment_hook() {
+        struct Conn(*const crate::Connection);
+
+        // The hook only runs on the thread owning the connection, during a query.
+        unsafe impl Send for Conn {}
+        unsafe impl Sync for Conn {}
+
+        impl Conn {
+            fn get(&self) -> &crate::Connection {
+                unsafe { &*self.0 }
+            }
+        }
+
+        let conn = crate::test::new_conn();
+        let ptr = Conn(&conn);
+
+        conn.set_comment_hook(move || {
+            let conn = ptr.get();
+            conn.unset_comment_hook();
+            conn.exec_checked("SELECT 1").unwrap();
+
+            vec![("action".to_string(), "once".to_string())]
+        });
+
+        assert_eq!(conn.commented("SELECT 1"), "/*action='once'*/ SELECT 1");
+        assert_eq!(conn.commented("SELECT 1"), "SELECT 1");
+    }
+
+    #[test]
+    fn format_params() {
+        let conn = crate::test::new_conn();
+        let types = [0, crate::types::INT4.oid];
+        let formats = [crate::Format::Text, crate::Format::Binary];
+
+        conn.set_log_redaction(crate::connection::Redaction::Full);
+        assert_eq!(
+            conn.format_params(&types, &[Some(b"abc\0"), None], &formats),
+            Some(vec!["'abc'::unknown".to_string(), "NULL::int4".to_string()])
+        );
+
+        conn.set_log_redaction(crate::connection::Redaction::Hash);
+        assert_eq!(
+            conn.format_params(&types, &[Some(b"abc\0")], &formats),
+            conn.format_params(&types, &[Some(b"abc")], &formats),
+        );
+
+        let logs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let l = logs.clone();
+        conn.set_log_redaction(crate::connection::Redaction::Full);
+        conn.set_query_logger(move |log| l.lock().unwrap().push(log.params.clone()));
+
+        conn.exec_typed("select $1::text, $2", &[&"abc", &None::<i32>])
+            .unwrap();
+        assert_eq!(
+            *logs.lock().unwrap(),
+            [vec!["'abc'::unknown".to_string(), "NULL::int4".to_string()]]
+        );
+    }
+
+    #[test]
+    fn query_logger() {
+        let conn = crate::test::new_conn();
+        let logs = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let l = logs.clone();
+        conn.set_log_redaction(crate::connection::Redaction::Truncate(2));
+        conn.set_query_logger(move |log| {
+            l.lock().unwrap().push((
+                log.command.to_string(),
+                log.params.clone(),
+                log.nparams,
+                log.rows,
+            ))
+        });
+
+        conn.exec("select generate_series(1, 3)");
+        conn.exec_params(
+            "select $1::text",
+            &[],
+            &[Some(b"secret")],
+            &[],
+            crate::Format::Text,
+        );
+        conn.set_log_redaction(crate::connection::Redaction::Omit);
+        conn.exec_params(
+            "select $1::text",
+            &[],
+            &[Some(b"secret")],
+            &[],
+            crate::Format::Text,
+        );
+        conn.unset_query_logger();
+        conn.exec("select 1");
+
+        let logs = logs.lock().unwrap();
+        assert_eq!(
+            *logs,
+            [
+                (
+                    "select generate_series(1, 3)".to_string(),
+                    Vec::new(),
+                    0,
+                    Some(3)
+                ),
+                (
+                    "select $1::text".to_string(),
+                    vec!["'se…'::unknown".to_string()],
+                    1,
+                    Some(1)
+                ),
+                ("select $1::text".to_string(), Vec::new(), 1, Some(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn notice_handler() {
+        let conn = crate::test::new_conn();
+        let notices = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let n = notices.clone();
+        conn.set_notice_handler(move |notice| n.lock().unwrap().push(notice));
+        conn.exec("do $$ begin raise notice 'hello' using hint = 'wave back'; end $$");
+
+        let notice = notices.lock().unwrap().pop().unwrap();
+        assert_eq!(notice.severity, "NOTICE");
+        assert_eq!(notice.sqlstate.as_deref(), Some("00000"));
+        assert_eq!(notice.message, "hello");
+        assert_eq!(notice.hint.as_deref(), Some("wave back"));
+
+        let result = conn.exec("select 1");
+        conn.unset_notice_handler();
+        conn.exec("do $$ begin raise notice 'ignored'; end $$");
+        assert!(notices.lock().unwrap().is_empty());
+
+        // The result keeps the removed handler as notice receiver.
+        assert_eq!(result.value(0, 1), None);
+        assert!(notices.lock().unwrap().is_empty());
+
+        let n = notices.clone();
+        conn.set_notice_handler(move |notice| n.lock().unwrap().push(notice));
+        drop(conn);
+        assert_eq!(std::sync::Arc::strong_count(&notices), 1);
+    }
+
+    #[test]
+    fn parameter_hook() {
+        let conn = crate::test::new_conn();
+        conn.exec("SET TimeZone = 'UTC'");
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        conn.set_parameter_hook(move |change| tx.send(change.clone()).unwrap());
+
+        conn.exec("SET TimeZone = 'Europe/Paris'");
+        assert_eq!(
+            rx.try_recv(),
+            Ok(super::ParameterChange {
+                name: "TimeZone",
+                old: Some("UTC".to_string()),
+                new: Some("Europe/Paris".to_string()),
+            })
+        );
+
+        conn.send_query("SET application_name = 'parameter_hook'")
+            .unwrap();
+        while conn.result().is_some() {}
+        let change = rx.try_recv().unwrap();
+        assert_eq!(change.name, "application_name");
+        assert_eq!(change.new.as_deref(), Some("parameter_hook"));
+
+        conn.exec("SELECT 1");
+        assert!(rx.try_recv().is_err());
+
+        conn.unset_parameter_hook();
+        conn.exec("SET TimeZone = 'UTC'");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn channel_binding() {
+        let conn = crate::test::new_conn();
+        assert!(conn.channel_binding().unwrap().is_some());
+        assert!(!conn.channel_binding_enforced().unwrap());
+
+        let dsn = format!("{} channel_binding=require", crate::test::dsn());
+        if let Ok(conn) = crate::Connection::new(&dsn) {
+            assert_eq!(
+                conn.channel_binding().unwrap(),
+                Some(crate::connection::ChannelBinding::Require)
+            );
+            assert!(conn.channel_binding_enforced().unwrap());
+        }
+    }
+
+    #[test]
+    fn identity() {
+        let conn = crate::test::new_conn();
+        let identity = conn.identity().unwrap();
+
+        assert_eq!(identity.server_version, conn.server_version());
+        assert_eq!(identity.database, conn.db().ok());
+        assert_eq!(identity.user, conn.user().unwrap());
+
+        let result = conn.exec("SELECT rolsuper FROM pg_roles WHERE rolname = current_user");
+        if result.get::<bool>(0, 0).unwrap() {
+            assert!(identity.system_identifier.is_some());
+            assert!(identity.timeline.is_some());
+        }
+
+        let mut dsn = crate::test::dsn();
+        dsn.push_str(" replication=true");
+        if let Ok(replication) = crate::Connection::new(&dsn) {
+            let replication = replication.identity().unwrap();
+            assert_eq!(replication.database, None);
+
+            if identity.system_identifier.is_some() {
+                assert_eq!(replication.system_identifier, identity.system_identifier);
+            }
+        }
+    }
+
     #[test]
     fn info() {
         let conn = crate::test::new_conn();
-        let _ = conn.info();
+        let infos = conn.info().unwrap();
+
+        let dbname = infos.get("dbname").unwrap();
+        assert_eq!(dbname.keyword, "dbname");
+        assert_eq!(dbname.val, conn.db().ok());
+        assert_eq!(
+            infos.get("password").unwrap().display_mode(),
+            crate::connection::DisplayMode::Password
+        );
+
+        assert_eq!(conn.info_value("dbname").unwrap(), dbname.val);
+        assert_eq!(conn.info_value("service").unwrap(), None);
+        assert!(conn.info_value("unknown").is_err());
     }
 
     #[test]
@@ -545,6 +1413,14 @@ mod test {
         let notify = conn.notifies().unwrap();
         assert_eq!(notify.relname(), Ok("test".to_string()));
         assert_eq!(notify.extra(), Ok("foo".to_string()));
+        assert!(notify.is_self(&conn));
+
+        let other = crate::test::new_conn();
+        other.exec("NOTIFY test, 'bar'");
+        conn.exec("SELECT 1");
+
+        let notify = conn.notifies().unwrap();
+        assert!(!notify.is_self(&conn));
     }
 
     #[test]
@@ -567,6 +1443,37 @@ mod test {
         assert_eq!(&*conn.copy_data(false).unwrap(), b"1\n");
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn copy_line() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE copy_line (id integer, name text)");
+
+        let result = conn.exec("COPY copy_line FROM STDIN");
+        assert_eq!(result.status(), crate::Status::CopyIn);
+        conn.put_line("1\tfoo\n").unwrap();
+        conn.put_line(&format!("2\t{}\n", "x".repeat(10_000)))
+            .unwrap();
+        conn.put_line("\\.\n").unwrap();
+        conn.endcopy().unwrap();
+
+        let result = conn.exec("COPY copy_line TO STDOUT");
+        assert_eq!(result.status(), crate::Status::CopyOut);
+
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while conn.get_line(&mut line).unwrap() {
+            lines.push(std::mem::take(&mut line));
+        }
+        conn.endcopy().unwrap();
+
+        assert_eq!(
+            lines,
+            vec!["1\tfoo".to_string(), format!("2\t{}", "x".repeat(10_000))]
+        );
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
     #[test]
     fn copy_binary() {
         /* Construct binary data with only one tuple */
@@ -633,7 +1540,6 @@ mod test {
     }
 
     #[test]
-    #[cfg(unix)]
     fn trace() {
         let conn = crate::test::new_conn();
         let file = std::fs::File::create("trace.txt").unwrap();
@@ -735,6 +1641,60 @@ B	5	ReadyForQuery	 I
         assert!(conn.send_close_portal(Some("curs2")).is_ok());
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn poll_interest() {
+        use std::os::fd::AsRawFd;
+
+        let conn = crate::test::new_conn();
+        assert_eq!(conn.as_raw_fd(), conn.socket().unwrap());
+        assert_eq!(conn.poll_interest(), Ok(None));
+
+        conn.send_query("SELECT pg_sleep(0.1)").unwrap();
+        assert_eq!(conn.poll_interest(), Ok(Some(crate::poll::Interest::Read)));
+
+        while conn.poll_interest().unwrap().is_some() {
+            crate::connection::wait_socket(conn.as_raw_fd(), true, false, None).unwrap();
+            conn.consume_input().unwrap();
+        }
+
+        assert_eq!(conn.result().unwrap().status(), crate::Status::TuplesOk);
+        assert!(conn.result().is_none());
+    }
+
+    #[test]
+    fn exec_foreach() {
+        let conn = crate::test::new_conn();
+
+        let mut rows = Vec::new();
+        conn.exec_foreach("SELECT generate_series(1, 3)", |row| {
+            rows.push(row.get::<i32>(0).unwrap());
+            std::ops::ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert_eq!(rows, vec![1, 2, 3]);
+
+        let mut count = 0;
+        conn.exec_foreach("SELECT generate_series(1, 100000000)", |_| {
+            count += 1;
+
+            if count == 10 {
+                std::ops::ControlFlow::Break(())
+            } else {
+                std::ops::ControlFlow::Continue(())
+            }
+        })
+        .unwrap();
+        assert_eq!(count, 10);
+
+        assert!(conn
+            .exec_foreach("SELECT 1 / (2 - x) FROM generate_series(1, 3) x", |_| {
+                std::ops::ControlFlow::Continue(())
+            })
+            .is_err());
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
     #[test]
     #[cfg(feature = "v17")]
     fn set_chunked_rows_mode() -> crate::errors::Result {
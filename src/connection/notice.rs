@@ -0,0 +1,114 @@
+/**
+ * A notice or warning sent by the server, see `libpq::Connection::set_notice_handler`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Notice {
+    /** Non-localized severity: `WARNING`, `NOTICE`, `DEBUG`, `INFO` or `LOG`. */
+    pub severity: String,
+    pub sqlstate: Option<String>,
+    pub message: String,
+    /** Secondary message, carrying more detail about the problem. */
+    pub detail: Option<String>,
+    /** Suggestion of what to do about the problem. */
+    pub hint: Option<String>,
+}
+
+impl Notice {
+    pub(crate) fn from_result(result: &crate::PQResult) -> Self {
+        use crate::result::ErrorField;
+
+        let field = |field| result.error_field(field).ok().flatten().map(str::to_string);
+
+        Self {
+            severity: field(ErrorField::SeverityNonlocalized)
+                .or_else(|| field(ErrorField::Severity))
+                .unwrap_or_default(),
+            sqlstate: field(ErrorField::Sqlstate),
+            message: field(ErrorField::MessagePrimary)
+                .or_else(|| result.error_message().ok().flatten())
+                .unwrap_or_default(),
+            detail: field(ErrorField::MessageDetail),
+            hint: field(ErrorField::MessageHint),
+        }
+    }
+}
+
+type Handlers = std::collections::BTreeMap<
+    usize,
+    std::sync::Arc<std::sync::Mutex<Box<crate::connection::NoticeHandler>>>,
+>;
+
+/**
+ * Installed handlers, by id. libpq copies the receiver argument into each result, which can
+ * outlive the handler: the argument is an id looked up here rather than a pointer.
+ */
+static HANDLERS: std::sync::Mutex<Handlers> = std::sync::Mutex::new(Handlers::new());
+
+static NEXT_ID: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(1);
+
+/**
+ * A handler installed by `libpq::Connection::set_notice_handler` and the notice receiver it
+ * replaced.
+ */
+pub(crate) struct NoticeTrampoline {
+    pub previous: (crate::connection::NoticeReceiver, *mut std::os::raw::c_void),
+    pub id: usize,
+}
+
+impl NoticeTrampoline {
+    pub fn register(handler: Box<crate::connection::NoticeHandler>) -> usize {
+        let id = NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let handler = std::sync::Arc::new(std::sync::Mutex::new(handler));
+
+        HANDLERS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(id, handler);
+
+        id
+    }
+}
+
+impl Drop for NoticeTrampoline {
+    fn drop(&mut self) {
+        HANDLERS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&self.id);
+    }
+}
+
+pub(crate) unsafe extern "C" fn notice_trampoline(
+    arg: *mut std::os::raw::c_void,
+    result: *const pq_sys::PGresult,
+) {
+    // The result belongs to libpq.
+    let result = std::mem::ManuallyDrop::new(crate::PQResult::from(result as *mut _));
+
+    let handler = HANDLERS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(&(arg as usize))
+        .cloned();
+
+    let Some(handler) = handler else {
+        // Removed handler, behaves like the default notice processor.
+        if let Ok(Some(message)) = result.error_message() {
+            eprint!("{message}");
+        }
+        return;
+    };
+
+    let notice = Notice::from_result(&result);
+    let call = || {
+        let handler = handler
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        handler(notice);
+    };
+
+    // Unwinding into libpq would abort.
+    if std::panic::catch_unwind(std::panic::AssertUnwindSafe(call)).is_err() {
+        log::trace!("Notice handler panicked");
+    }
+}
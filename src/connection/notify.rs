@@ -24,6 +24,14 @@ impl Notify {
     pub fn extra(&self) -> crate::errors::Result<String> {
         crate::ffi::to_string(unsafe { (*self.notify).extra })
     }
+
+    /**
+     * Returns `true` if this notification was sent by the backend serving `conn`, that is by
+     * `conn` itself.
+     */
+    pub fn is_self(&self, conn: &crate::Connection) -> bool {
+        self.be_pid() == conn.backend_pid()
+    }
 }
 
 #[doc(hidden)]
@@ -58,3 +66,349 @@ impl std::fmt::Debug for Notify {
             .finish()
     }
 }
+
+/**
+ * Client-side filter on notification channel names.
+ */
+#[derive(Clone, Debug)]
+pub enum ChannelFilter {
+    /** Matches any channel of the set. */
+    Exact(std::collections::BTreeSet<String>),
+    /** Matches channels starting with the given prefix. */
+    Prefix(String),
+    /** Matches channels matching the regular expression. */
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl ChannelFilter {
+    /**
+     * Creates a filter matching exactly one channel.
+     */
+    pub fn exact(channel: &str) -> Self {
+        Self::Exact(std::collections::BTreeSet::from([channel.to_string()]))
+    }
+
+    /**
+     * Returns `true` if `channel` is accepted by this filter.
+     */
+    pub fn matches(&self, channel: &str) -> bool {
+        match self {
+            Self::Exact(channels) => channels.contains(channel),
+            Self::Prefix(prefix) => channel.starts_with(prefix),
+            #[cfg(feature = "regex")]
+            Self::Regex(regex) => regex.is_match(channel),
+        }
+    }
+}
+
+type NotifyCallback<'a> = Box<dyn FnMut(&Notify) + 'a>;
+
+/**
+ * Dispatches notifications received on a connection to callbacks, according to their channel.
+ *
+ * This allows one connection, and one `LISTEN` loop, to serve many logical subscribers.
+ */
+#[derive(Default)]
+pub struct NotifyDispatcher<'a> {
+    subscribers: Vec<(ChannelFilter, NotifyCallback<'a>)>,
+}
+
+impl<'a> NotifyDispatcher<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Registers `callback` for notifications whose channel matches `filter`.
+     *
+     * Callbacks are called in registration order; a notification matching several filters is
+     * delivered to each of them.
+     */
+    pub fn on<F>(&mut self, filter: ChannelFilter, callback: F) -> &mut Self
+    where
+        F: FnMut(&Notify) + 'a,
+    {
+        self.subscribers.push((filter, Box::new(callback)));
+        self
+    }
+
+    /**
+     * Delivers a notification to the matching callbacks.
+     *
+     * Returns `false` if no subscriber accepted it.
+     */
+    pub fn dispatch(&mut self, notify: &Notify) -> crate::errors::Result<bool> {
+        let channel = notify.relname()?;
+        let mut delivered = false;
+
+        for (filter, callback) in &mut self.subscribers {
+            if filter.matches(&channel) {
+                callback(notify);
+                delivered = true;
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    /**
+     * Delivers all notifications already received by `conn`.
+     *
+     * Returns the number of notifications read from the connection, and the errors of the
+     * ones that couldn't be dispatched, like a channel name not valid in UTF-8: a failure
+     * doesn't stop the delivery of the next notifications.
+     */
+    pub fn dispatch_pending(
+        &mut self,
+        conn: &crate::Connection,
+    ) -> (usize, Vec<crate::errors::Error>) {
+        let mut count = 0;
+        let mut errors = Vec::new();
+
+        while let Some(notify) = conn.notifies() {
+            if let Err(err) = self.dispatch(&notify) {
+                log::trace!("Unable to dispatch notification: {err}");
+                errors.push(err);
+            }
+            count += 1;
+        }
+
+        (count, errors)
+    }
+}
+
+impl std::fmt::Debug for NotifyDispatcher<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NotifyDispatcher")
+            .field(
+                "filters",
+                &self.subscribers.iter().map(|(x, _)| x).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/**
+ * Handle to listen to notification channels and wait for notifications.
+ *
+ * Created by `libpq::Connection::notifications`.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let notifications = conn.notifications();
+ * notifications.listen("jobs")?;
+ *
+ * while let Some(notify) = notifications.blocking_wait(None)? {
+ *     println!("{}: {}", notify.relname()?, notify.extra()?);
+ * }
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct Notifications<'c> {
+    conn: &'c crate::Connection,
+}
+
+impl<'c> Notifications<'c> {
+    pub(crate) fn new(conn: &'c crate::Connection) -> Self {
+        Self { conn }
+    }
+
+    /**
+     * Starts listening to `channel`, the name is escaped.
+     */
+    pub fn listen(&self, channel: &str) -> crate::errors::Result {
+        let channel = self.conn.escape_identifier(channel)?;
+        self.conn
+            .exec_checked(&format!("LISTEN {}", channel.to_string_lossy()))?;
+
+        Ok(())
+    }
+
+    /**
+     * Stops listening to `channel`.
+     */
+    pub fn unlisten(&self, channel: &str) -> crate::errors::Result {
+        let channel = self.conn.escape_identifier(channel)?;
+        self.conn
+            .exec_checked(&format!("UNLISTEN {}", channel.to_string_lossy()))?;
+
+        Ok(())
+    }
+
+    /**
+     * Stops listening to all channels.
+     */
+    pub fn unlisten_all(&self) -> crate::errors::Result {
+        self.conn.exec_checked("UNLISTEN *")?;
+
+        Ok(())
+    }
+
+    /**
+     * Iterates over the notifications already received, without blocking.
+     */
+    pub fn iter(&self) -> NotificationsIter<'c> {
+        NotificationsIter { conn: self.conn }
+    }
+
+    /**
+     * Returns the next notification, waiting for it at most `timeout`, or indefinitely if
+     * `None`.
+     *
+     * Returns `None` when the timeout expires.
+     */
+    #[cfg(unix)]
+    pub fn blocking_wait(
+        &self,
+        timeout: Option<std::time::Duration>,
+    ) -> crate::errors::Result<Option<Notify>> {
+        let deadline = timeout.map(|x| std::time::Instant::now() + x);
+
+        loop {
+            self.conn.consume_input()?;
+
+            if let Some(notify) = self.conn.notifies() {
+                return Ok(Some(notify));
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => {
+                    match deadline.checked_duration_since(std::time::Instant::now()) {
+                        Some(remaining) if !remaining.is_zero() => Some(remaining),
+                        _ => return Ok(None),
+                    }
+                }
+                None => None,
+            };
+
+            crate::connection::wait_socket(self.conn.socket()?, true, false, remaining)?;
+        }
+    }
+}
+
+/**
+ * Iterator over the notifications already received, see [`Notifications::iter`].
+ */
+#[derive(Debug)]
+pub struct NotificationsIter<'c> {
+    conn: &'c crate::Connection,
+}
+
+impl Iterator for NotificationsIter<'_> {
+    type Item = crate::errors::Result<Notify>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(err) = self.conn.consume_input() {
+            return Some(Err(err));
+        }
+
+        self.conn.notifies().map(Ok)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn filter() {
+        let filter = crate::connection::ChannelFilter::exact("foo");
+        assert!(filter.matches("foo"));
+        assert!(!filter.matches("foobar"));
+
+        let filter = crate::connection::ChannelFilter::Prefix("foo".to_string());
+        assert!(filter.matches("foobar"));
+        assert!(!filter.matches("barfoo"));
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn regex_filter() {
+        let filter =
+            crate::connection::ChannelFilter::Regex(regex::Regex::new("^job_[0-9]+$").unwrap());
+        assert!(filter.matches("job_42"));
+        assert!(!filter.matches("job_x"));
+    }
+
+    #[test]
+    fn dispatcher() {
+        let conn = crate::test::new_conn();
+        conn.exec("LISTEN dispatch_a");
+        conn.exec("LISTEN dispatch_b");
+        conn.exec("LISTEN other");
+
+        let mut a = Vec::new();
+        let mut all = 0;
+
+        {
+            let mut dispatcher = crate::connection::NotifyDispatcher::new();
+            dispatcher
+                .on(
+                    crate::connection::ChannelFilter::exact("dispatch_a"),
+                    |notify| a.push(notify.extra().unwrap()),
+                )
+                .on(
+                    crate::connection::ChannelFilter::Prefix("dispatch_".to_string()),
+                    |_| all += 1,
+                );
+
+            conn.exec("NOTIFY dispatch_a, 'foo'");
+            conn.exec("NOTIFY dispatch_b, 'bar'");
+            conn.exec("NOTIFY other, 'baz'");
+
+            assert_eq!(dispatcher.dispatch_pending(&conn), (3, Vec::new()));
+
+            // A channel name sent in LATIN1 isn't valid UTF-8.
+            conn.exec(r#"LISTEN "é""#);
+            conn.set_client_encoding(crate::Encoding::LATIN1);
+            conn.exec("NOTIFY dispatch_a, 'foo'");
+            conn.exec("SELECT pg_notify(chr(233), 'invalid')");
+            conn.exec("NOTIFY dispatch_b, 'bar'");
+
+            let (count, errors) = dispatcher.dispatch_pending(&conn);
+            assert_eq!(count, 3);
+            assert!(matches!(errors[..], [crate::errors::Error::Utf8(_)]));
+        }
+
+        assert_eq!(a, vec!["foo".to_string(), "foo".to_string()]);
+        assert_eq!(all, 4);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn notifications() {
+        let conn = crate::test::new_conn();
+        let notifications = conn.notifications();
+        notifications.listen("Notifications").unwrap();
+        notifications.listen("other").unwrap();
+
+        let timeout = Some(std::time::Duration::from_millis(10));
+        assert!(notifications.blocking_wait(timeout).unwrap().is_none());
+
+        let other = crate::test::new_conn();
+        other.exec(r#"NOTIFY "Notifications", 'foo'"#);
+
+        let notify = notifications
+            .blocking_wait(Some(std::time::Duration::from_secs(5)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(notify.relname(), Ok("Notifications".to_string()));
+        assert_eq!(notify.extra(), Ok("foo".to_string()));
+
+        conn.exec("NOTIFY other, 'bar'");
+        conn.exec("NOTIFY other, 'baz'");
+        let extra = notifications
+            .iter()
+            .map(|x| x.unwrap().extra().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(extra, vec!["bar".to_string(), "baz".to_string()]);
+
+        notifications.unlisten("other").unwrap();
+        conn.exec("NOTIFY other");
+        assert_eq!(notifications.iter().count(), 0);
+
+        notifications.unlisten_all().unwrap();
+        conn.exec(r#"NOTIFY "Notifications""#);
+        assert!(notifications.blocking_wait(timeout).unwrap().is_none());
+    }
+}
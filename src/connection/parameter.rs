@@ -0,0 +1,36 @@
+/**
+ * Parameters reported by the server whenever their value changes, see
+ * [PQparameterStatus](https://www.postgresql.org/docs/current/libpq-status.html#LIBPQ-PQPARAMETERSTATUS).
+ */
+pub const REPORTED_PARAMETERS: [&str; 14] = [
+    "application_name",
+    "client_encoding",
+    "DateStyle",
+    "default_transaction_read_only",
+    "in_hot_standby",
+    "integer_datetimes",
+    "IntervalStyle",
+    "is_superuser",
+    "scram_iterations",
+    "server_encoding",
+    "server_version",
+    "session_authorization",
+    "standard_conforming_strings",
+    "TimeZone",
+];
+
+/**
+ * A change of a server parameter, see `libpq::Connection::set_parameter_hook`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParameterChange {
+    pub name: &'static str,
+    /** Previous value, `None` if the server didn't report the parameter. */
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+pub(crate) struct ParameterWatch {
+    pub hook: Box<crate::connection::ParameterHook>,
+    pub values: Vec<Option<String>>,
+}
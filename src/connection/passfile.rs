@@ -0,0 +1,133 @@
+/**
+ * Location of the [password file](https://www.postgresql.org/docs/current/libpq-pgpass.html):
+ * `PGPASSFILE`, or `~/.pgpass` (`%APPDATA%\postgresql\pgpass.conf` on windows).
+ */
+pub(crate) fn path() -> Option<std::path::PathBuf> {
+    if let Some(path) = std::env::var_os("PGPASSFILE") {
+        return Some(path.into());
+    }
+
+    #[cfg(windows)]
+    let path = std::env::var_os("APPDATA").map(|x| {
+        std::path::Path::new(&x)
+            .join("postgresql")
+            .join("pgpass.conf")
+    });
+    #[cfg(not(windows))]
+    let path = std::env::var_os("HOME").map(|x| std::path::Path::new(&x).join(".pgpass"));
+
+    path
+}
+
+/**
+ * Returns the password of the first line of the password file matching the connection, like
+ * libpq: a field matches if it's `*` or equals the value, a socket directory is matched as
+ * `localhost`.
+ *
+ * On unix, a file readable by the group or the others is ignored.
+ */
+pub(crate) fn lookup(
+    path: &std::path::Path,
+    host: &str,
+    port: &str,
+    dbname: &str,
+    user: &str,
+) -> std::io::Result<Option<String>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        if std::fs::metadata(path)?.permissions().mode() & 0o077 != 0 {
+            log::trace!(
+                "Password file '{}' has group or world access, ignored",
+                path.display()
+            );
+            return Ok(None);
+        }
+    }
+
+    let host = if host.is_empty() || host.starts_with('/') {
+        "localhost"
+    } else {
+        host
+    };
+
+    for line in contents.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let fields = split(line);
+        let [h, p, d, u, password] = fields.as_slice() else {
+            continue;
+        };
+
+        let matches = |field: &str, value: &str| field == "*" || field == value;
+
+        if matches(h, host) && matches(p, port) && matches(d, dbname) && matches(u, user) {
+            return Ok(Some(password.clone()));
+        }
+    }
+
+    Ok(None)
+}
+
+/**
+ * Splits a line on `:`, `\` escaping the next character.
+ */
+fn split(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => field.extend(chars.next()),
+            ':' => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+
+    fields
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn lookup() {
+        let path = std::env::temp_dir().join(format!("libpq-pgpass-{}", std::process::id()));
+        std::fs::write(
+            &path,
+            "# comment\ndb.example:5432:app:alice:secret\\:1\n*:*:*:bob:any\nlocalhost:*:*:*:local\n",
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let lookup = |host, user| super::lookup(&path, host, "5432", "app", user).unwrap();
+
+        assert_eq!(lookup("db.example", "alice"), Some("secret:1".to_string()));
+        assert_eq!(lookup("other", "bob"), Some("any".to_string()));
+        assert_eq!(lookup("/tmp", "carol"), Some("local".to_string()));
+        assert_eq!(lookup("other", "carol"), None);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert_eq!(lookup("db.example", "alice"), None);
+        }
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
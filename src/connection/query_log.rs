@@ -0,0 +1,105 @@
+/**
+ * How the parameter values appear in the logs, see `libpq::Connection::set_log_redaction`.
+ *
+ * Values are hashed by default: logs often outlive and travel further than the data, the
+ * whole values have to be asked for.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Redaction {
+    /** The whole values. */
+    Full,
+    /**
+     * A hash of the values, to spot identical values without revealing them.
+     *
+     * The hash is keyed with a random key drawn once per process, so it can't be reversed by
+     * hashing candidate values, even low-entropy ones like a PIN, without that key. Hashes
+     * are only comparable within a process, and are 64 bits: not meant to be collision
+     * resistant.
+     */
+    #[default]
+    Hash,
+    /** The first characters of the values. */
+    Truncate(usize),
+    /** Only the number of parameters. */
+    Omit,
+}
+
+impl Redaction {
+    /**
+     * Redacts a formatted parameter value, `None` if it's omitted.
+     */
+    pub fn apply(&self, value: &str) -> Option<String> {
+        use std::hash::BuildHasher;
+
+        static KEY: std::sync::OnceLock<std::collections::hash_map::RandomState> =
+            std::sync::OnceLock::new();
+
+        match self {
+            Self::Full => Some(value.to_string()),
+            Self::Hash => {
+                let hash = KEY
+                    .get_or_init(std::collections::hash_map::RandomState::new)
+                    .hash_one(value);
+
+                Some(format!("#{hash:016x}"))
+            }
+            Self::Truncate(len) => {
+                let mut chars = value.chars();
+                let mut truncated = chars.by_ref().take(*len).collect::<String>();
+
+                if chars.next().is_some() {
+                    truncated.push('…');
+                }
+
+                Some(truncated)
+            }
+            Self::Omit => None,
+        }
+    }
+}
+
+/**
+ * A query executed by the connection, see `libpq::Connection::set_query_logger`.
+ */
+#[derive(Clone, Debug)]
+pub struct QueryLog<'a> {
+    /** The SQL command, empty for a prepared statement. */
+    pub command: &'a str,
+    /** The prepared statement name, for `libpq::Connection::exec_prepared`. */
+    pub statement: Option<&'a str>,
+    /** The parameters, formatted as `'value'::type` and redacted, or `NULL::type`. */
+    pub params: Vec<String>,
+    /** The number of parameters, even when their values are omitted. */
+    pub nparams: usize,
+    pub duration: std::time::Duration,
+    pub status: crate::Status,
+    /** Rows returned or affected, when the command reports them. */
+    pub rows: Option<usize>,
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn redaction() {
+        use super::Redaction;
+
+        assert_eq!(Redaction::Full.apply("secret"), Some("secret".to_string()));
+        assert_eq!(
+            Redaction::Truncate(3).apply("secret"),
+            Some("sec…".to_string())
+        );
+        assert_eq!(
+            Redaction::Truncate(6).apply("secret"),
+            Some("secret".to_string())
+        );
+        assert_eq!(Redaction::Omit.apply("secret"), None);
+
+        let hash = Redaction::Hash.apply("secret").unwrap();
+        assert!(!hash.contains("secret"));
+        assert_eq!(Redaction::Hash.apply("secret"), Some(hash));
+        assert_ne!(
+            Redaction::Hash.apply("secret"),
+            Redaction::Hash.apply("other")
+        );
+    }
+}
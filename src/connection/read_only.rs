@@ -0,0 +1,611 @@
+const WRITE_KEYWORDS: &[&str] = &["DELETE", "INSERT", "MERGE", "TRUNCATE", "UPDATE"];
+
+const WRITE_COMMANDS: &[&str] = &[
+    "ALTER", "CALL", "CLUSTER", "COMMENT", "COPY", "CREATE", "DO", "DROP", "GRANT", "IMPORT",
+    "LOCK", "REASSIGN", "REFRESH", "REINDEX", "REVOKE", "SECURITY", "VACUUM",
+];
+
+const READ_ONLY_SETTINGS: &[&str] = &["DEFAULT_TRANSACTION_READ_ONLY", "TRANSACTION_READ_ONLY"];
+
+/**
+ * A connection for read-only code paths, like replica-targeted queries.
+ *
+ * `default_transaction_read_only` is enabled for the session, and statements modifying data
+ * or the schema, or leaving the read-only mode, are rejected before being sent with an
+ * `Error::ReadOnly` error. The client side check is a guard rail based on keywords, the server
+ * setting remains the actual enforcement.
+ *
+ * Only the query methods are available, each checking the statement first: COPY and
+ * transaction guards aren't.
+ *
+ * ```compile_fail
+ * # let conn = libpq::connection::ReadOnlyConnection::new("")?;
+ * conn.transaction()?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ *
+ * ```compile_fail
+ * # let conn = libpq::connection::ReadOnlyConnection::new("")?;
+ * conn.copy_in("COPY t FROM STDIN")?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct ReadOnlyConnection {
+    conn: crate::Connection,
+    prepared: std::cell::RefCell<std::collections::HashSet<String>>,
+}
+
+impl ReadOnlyConnection {
+    /**
+     * Connects to the database, see `libpq::Connection::new`.
+     */
+    pub fn new(dsn: &str) -> crate::errors::Result<Self> {
+        crate::Connection::new(dsn)?.try_into()
+    }
+
+    /**
+     * Returns an error if `query` seems to modify data or the schema, or to leave the
+     * read-only mode.
+     */
+    pub fn check(query: &str) -> crate::errors::Result {
+        for statement in words(query).split(|x| *x == ";") {
+            let keywords = statement
+                .iter()
+                .map(|x| x.to_ascii_uppercase())
+                .collect::<Vec<_>>();
+
+            check_statement(&keywords)?;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * See `libpq::Connection::exec`.
+     */
+    pub fn exec(&self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        Self::check(query)?;
+
+        Ok(self.conn.exec(query))
+    }
+
+    /**
+     * See `libpq::Connection::exec_checked`.
+     */
+    pub fn exec_checked(&self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        Self::check(query)?;
+
+        self.conn.exec_checked(query)
+    }
+
+    /**
+     * See `libpq::Connection::exec_interruptible`.
+     */
+    #[cfg(unix)]
+    pub fn exec_interruptible(
+        &self,
+        query: &str,
+        token: &std::sync::atomic::AtomicBool,
+    ) -> crate::errors::Result<crate::PQResult> {
+        Self::check(query)?;
+
+        self.conn.exec_interruptible(query, token)
+    }
+
+    /**
+     * See `libpq::Connection::exec_params`.
+     */
+    pub fn exec_params(
+        &self,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        Self::check(command)?;
+
+        Ok(self.conn.exec_params(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        ))
+    }
+
+    /**
+     * See `libpq::Connection::exec_params_checked`.
+     */
+    pub fn exec_params_checked(
+        &self,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        Self::check(command)?;
+
+        self.conn.exec_params_checked(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        )
+    }
+
+    /**
+     * See `libpq::Connection::exec_typed`.
+     */
+    pub fn exec_typed(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result<crate::PQResult> {
+        Self::check(command)?;
+
+        self.conn.exec_typed(command, params)
+    }
+
+    /**
+     * See `libpq::Connection::prepare`.
+     */
+    pub fn prepare(
+        &self,
+        name: Option<&str>,
+        query: &str,
+        param_types: &[crate::Oid],
+    ) -> crate::errors::Result<crate::PQResult> {
+        Self::check(query)?;
+
+        let result = self.conn.prepare(name, query, param_types);
+
+        if result.status() == crate::Status::CommandOk {
+            self.prepared
+                .borrow_mut()
+                .insert(name.unwrap_or_default().to_string());
+        }
+
+        Ok(result)
+    }
+
+    /**
+     * See `libpq::Connection::exec_prepared`.
+     *
+     * Only the statements prepared by `ReadOnlyConnection::prepare` or
+     * `ReadOnlyConnection::send_prepare` can be executed, their query having been checked.
+     */
+    pub fn exec_prepared(
+        &self,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.check_prepared(name)?;
+
+        Ok(self
+            .conn
+            .exec_prepared(name, param_values, param_formats, result_format))
+    }
+
+    /**
+     * See `libpq::Connection::exec_prepared_checked`.
+     */
+    pub fn exec_prepared_checked(
+        &self,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        self.check_prepared(name)?;
+
+        self.conn
+            .exec_prepared_checked(name, param_values, param_formats, result_format)
+    }
+
+    /**
+     * See `libpq::Connection::send_query`.
+     */
+    pub fn send_query(&self, query: &str) -> crate::errors::Result {
+        Self::check(query)?;
+
+        self.conn.send_query(query)
+    }
+
+    /**
+     * See `libpq::Connection::send_query_params`.
+     */
+    pub fn send_query_params(
+        &self,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result {
+        Self::check(command)?;
+
+        self.conn.send_query_params(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        )
+    }
+
+    /**
+     * See `libpq::Connection::send_query_typed`.
+     */
+    pub fn send_query_typed(
+        &self,
+        command: &str,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result {
+        Self::check(command)?;
+
+        self.conn.send_query_typed(command, params)
+    }
+
+    /**
+     * See `libpq::Connection::send_prepare`.
+     *
+     * The statement can be executed once its result is successfully read.
+     */
+    pub fn send_prepare(
+        &self,
+        name: Option<&str>,
+        query: &str,
+        param_types: &[crate::Oid],
+    ) -> crate::errors::Result {
+        Self::check(query)?;
+
+        self.conn.send_prepare(name, query, param_types)?;
+        self.prepared
+            .borrow_mut()
+            .insert(name.unwrap_or_default().to_string());
+
+        Ok(())
+    }
+
+    /**
+     * See `libpq::Connection::send_query_prepared`.
+     */
+    pub fn send_query_prepared(
+        &self,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result {
+        self.check_prepared(name)?;
+
+        self.conn
+            .send_query_prepared(name, param_values, param_formats, result_format)
+    }
+
+    /**
+     * See `libpq::Connection::result`.
+     */
+    pub fn result(&self) -> Option<crate::PQResult> {
+        self.conn.result()
+    }
+
+    /**
+     * See `libpq::Connection::consume_input`.
+     */
+    pub fn consume_input(&self) -> crate::errors::Result {
+        self.conn.consume_input()
+    }
+
+    /**
+     * See `libpq::Connection::is_busy`.
+     */
+    pub fn is_busy(&self) -> bool {
+        self.conn.is_busy()
+    }
+
+    /**
+     * See `libpq::Connection::status`.
+     */
+    pub fn status(&self) -> crate::connection::Status {
+        self.conn.status()
+    }
+
+    /**
+     * See `libpq::Connection::transaction_status`.
+     */
+    pub fn transaction_status(&self) -> crate::transaction::Status {
+        self.conn.transaction_status()
+    }
+
+    /**
+     * See `libpq::Connection::parameter_status`.
+     */
+    pub fn parameter_status(&self, param: &str) -> crate::errors::Result<String> {
+        self.conn.parameter_status(param)
+    }
+
+    /**
+     * See `libpq::Connection::error_message`.
+     */
+    pub fn error_message(&self) -> Option<&str> {
+        self.conn.error_message()
+    }
+
+    /**
+     * See `libpq::Connection::escape_literal`.
+     */
+    pub fn escape_literal(&self, str: &str) -> crate::errors::Result<crate::connection::PqString> {
+        self.conn.escape_literal(str)
+    }
+
+    /**
+     * See `libpq::Connection::escape_identifier`.
+     */
+    pub fn escape_identifier(
+        &self,
+        str: &str,
+    ) -> crate::errors::Result<crate::connection::PqString> {
+        self.conn.escape_identifier(str)
+    }
+
+    /**
+     * Returns the underlying connection, still in read-only mode.
+     */
+    pub fn into_inner(self) -> crate::Connection {
+        self.conn
+    }
+
+    fn check_prepared(&self, name: Option<&str>) -> crate::errors::Result {
+        let name = name.unwrap_or_default();
+
+        if self.prepared.borrow().contains(name) {
+            Ok(())
+        } else {
+            Err(crate::errors::Error::ReadOnly(format!(
+                "EXECUTE of the unchecked statement '{name}'"
+            )))
+        }
+    }
+}
+
+impl TryFrom<crate::Connection> for ReadOnlyConnection {
+    type Error = crate::errors::Error;
+
+    fn try_from(conn: crate::Connection) -> crate::errors::Result<Self> {
+        conn.exec_checked("SET default_transaction_read_only = on")?;
+
+        Ok(Self {
+            conn,
+            prepared: Default::default(),
+        })
+    }
+}
+
+/**
+ * Checks the upper case words of a single statement.
+ */
+fn check_statement(keywords: &[String]) -> crate::errors::Result {
+    let Some(command) = keywords.first().map(String::as_str) else {
+        return Ok(());
+    };
+
+    let rejected = if WRITE_COMMANDS.contains(&command) {
+        Some(command.to_string())
+    } else if let Some(keyword) = keywords
+        .iter()
+        .find(|x| WRITE_KEYWORDS.contains(&x.as_str()))
+    {
+        Some(keyword.clone())
+    } else if keywords
+        .windows(2)
+        .any(|x| x[0] == "READ" && x[1] == "WRITE")
+    {
+        Some("READ WRITE".to_string())
+    } else if let (true, Some(setting)) = (
+        matches!(command, "SET" | "RESET"),
+        keywords
+            .iter()
+            .find(|x| READ_ONLY_SETTINGS.contains(&x.as_str())),
+    ) {
+        Some(format!("{command} {setting}"))
+    } else if matches!(command, "RESET" | "DISCARD")
+        && keywords.get(1).map(String::as_str) == Some("ALL")
+    {
+        Some(format!("{command} ALL"))
+    } else {
+        None
+    };
+
+    match rejected {
+        Some(keyword) => Err(crate::errors::Error::ReadOnly(keyword)),
+        None => Ok(()),
+    }
+}
+
+/**
+ * Splits `query` into words and semicolons, skipping literals, quoted identifiers and comments.
+ */
+fn words(query: &str) -> Vec<&str> {
+    let bytes = query.as_bytes();
+    let mut words = Vec::new();
+    let mut i = 0;
+
+    let skip_to = |from: usize, pattern: &str| {
+        query[from..]
+            .find(pattern)
+            .map(|x| from + x + pattern.len())
+            .unwrap_or(query.len())
+    };
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' | b'"' => {
+                let quote = bytes[i];
+                let escape = quote == b'\'' && i > 0 && matches!(bytes[i - 1], b'e' | b'E');
+                i += 1;
+
+                while i < bytes.len() {
+                    if bytes[i] == b'\\' && escape {
+                        i += 1;
+                    } else if bytes[i] == quote {
+                        break;
+                    }
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = skip_to(i, "\n"),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => i = skip_to(i + 2, "*/"),
+            b'$' => {
+                let tag_end = query[i + 1..]
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .map(|x| i + 1 + x);
+
+                match tag_end {
+                    Some(end) if bytes[end] == b'$' && !bytes[i + 1].is_ascii_digit() => {
+                        i = skip_to(end + 1, &query[i..=end]);
+                    }
+                    _ => i += 1,
+                }
+            }
+            b';' => {
+                words.push(";");
+                i += 1;
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' => {
+                let start = i;
+
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+
+                words.push(&query[start..i]);
+            }
+            _ => i += 1,
+        }
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn check() {
+        use crate::connection::ReadOnlyConnection;
+
+        assert!(ReadOnlyConnection::check("SELECT 'delete', \"update\" -- insert").is_ok());
+        assert!(ReadOnlyConnection::check("SELECT $$ DROP TABLE x $$ /* drop */").is_ok());
+        assert!(ReadOnlyConnection::check("SELECT created FROM t; SHOW all").is_ok());
+
+        assert_eq!(
+            ReadOnlyConnection::check("with x as (delete from t returning *) select * from x"),
+            Err(crate::errors::Error::ReadOnly("DELETE".to_string()))
+        );
+        assert_eq!(
+            ReadOnlyConnection::check("SELECT 1; CREATE TABLE t()"),
+            Err(crate::errors::Error::ReadOnly("CREATE".to_string()))
+        );
+        assert_eq!(
+            ReadOnlyConnection::check("SELECT E'\\''; drop table t"),
+            Err(crate::errors::Error::ReadOnly("DROP".to_string()))
+        );
+        assert_eq!(
+            ReadOnlyConnection::check("SELECT 'C:\\'; drop table t"),
+            Err(crate::errors::Error::ReadOnly("DROP".to_string()))
+        );
+
+        for (query, keyword) in [
+            (
+                "SET default_transaction_read_only = off",
+                "SET DEFAULT_TRANSACTION_READ_ONLY",
+            ),
+            (
+                "set session transaction_read_only to off",
+                "SET TRANSACTION_READ_ONLY",
+            ),
+            (
+                "RESET default_transaction_read_only",
+                "RESET DEFAULT_TRANSACTION_READ_ONLY",
+            ),
+            ("RESET ALL", "RESET ALL"),
+            ("DISCARD ALL", "DISCARD ALL"),
+            (
+                "SET SESSION CHARACTERISTICS AS TRANSACTION READ WRITE",
+                "READ WRITE",
+            ),
+            ("SET TRANSACTION READ WRITE", "READ WRITE"),
+            ("BEGIN READ WRITE", "READ WRITE"),
+            (
+                "START TRANSACTION ISOLATION LEVEL SERIALIZABLE, READ WRITE",
+                "READ WRITE",
+            ),
+        ] {
+            assert_eq!(
+                ReadOnlyConnection::check(query),
+                Err(crate::errors::Error::ReadOnly(keyword.to_string())),
+                "{query}"
+            );
+        }
+
+        assert!(ReadOnlyConnection::check("SET search_path = public").is_ok());
+        assert!(ReadOnlyConnection::check("BEGIN READ ONLY").is_ok());
+        assert!(ReadOnlyConnection::check("SHOW transaction_read_only").is_ok());
+    }
+
+    #[test]
+    fn read_only() {
+        let conn = crate::connection::ReadOnlyConnection::new(&crate::test::dsn()).unwrap();
+
+        assert_eq!(
+            conn.exec("SELECT 1").unwrap().status(),
+            crate::Status::TuplesOk
+        );
+        assert!(conn.exec("CREATE TEMPORARY TABLE t()").is_err());
+
+        let result = conn.exec("SHOW default_transaction_read_only").unwrap();
+        assert_eq!(result.value(0, 0), Some(&b"on"[..]));
+    }
+
+    #[test]
+    fn bypass() {
+        let conn = crate::connection::ReadOnlyConnection::new(&crate::test::dsn()).unwrap();
+        let create = "CREATE TEMPORARY TABLE zz()";
+
+        assert!(conn.exec_checked(create).is_err());
+        assert!(conn
+            .exec_params_checked(create, &[], &[], &[], crate::Format::Text)
+            .is_err());
+        assert!(conn.send_query_typed(create, &[]).is_err());
+        #[cfg(unix)]
+        assert!(conn
+            .exec_interruptible(create, &std::sync::atomic::AtomicBool::new(false))
+            .is_err());
+        assert!(conn
+            .exec_checked("SET default_transaction_read_only = off")
+            .is_err());
+        assert!(conn.exec_checked("BEGIN READ WRITE").is_err());
+
+        conn.exec_checked("PREPARE unchecked AS SELECT 1").unwrap();
+        assert!(conn
+            .exec_prepared(Some("unchecked"), &[], &[], crate::Format::Text)
+            .is_err());
+
+        conn.prepare(Some("checked"), "SELECT 1", &[]).unwrap();
+        let result = conn
+            .exec_prepared_checked(Some("checked"), &[], &[], crate::Format::Text)
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+
+        assert_eq!(conn.status(), crate::connection::Status::Ok);
+        let result = conn.exec("SHOW default_transaction_read_only").unwrap();
+        assert_eq!(result.value(0, 0), Some(&b"on"[..]));
+    }
+}
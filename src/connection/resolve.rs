@@ -0,0 +1,86 @@
+/**
+ * Resolves `host` to its addresses of `family`, in the resolver order.
+ *
+ * The resolution runs in a thread when `timeout` is set: the resolver can't be interrupted, it
+ * keeps running in the background after a timeout.
+ */
+pub(crate) fn lookup(
+    host: &str,
+    port: u16,
+    family: crate::connection::AddressFamily,
+    timeout: Option<std::time::Duration>,
+) -> crate::errors::Result<Vec<std::net::IpAddr>> {
+    use std::net::ToSocketAddrs;
+
+    log::trace!("Resolving '{host}'");
+
+    let addrs = match timeout {
+        Some(timeout) => {
+            let (tx, rx) = std::sync::mpsc::channel();
+            let target = (host.to_string(), port);
+
+            std::thread::spawn(move || {
+                tx.send(target.to_socket_addrs().map(Iterator::collect::<Vec<_>>))
+                    .ok();
+            });
+
+            match rx.recv_timeout(timeout) {
+                Ok(addrs) => addrs,
+                Err(_) => {
+                    log::trace!("Resolution of '{host}' timed out");
+                    return Err(crate::errors::Error::Timeout);
+                }
+            }
+        }
+        None => (host, port).to_socket_addrs().map(Iterator::collect),
+    }
+    .map_err(|err| {
+        crate::errors::Error::Backend(format!(
+            "could not translate host name \"{host}\" to address: {err}"
+        ))
+    })?;
+
+    let mut ips = Vec::new();
+
+    for addr in addrs {
+        let ip = addr.ip();
+
+        if family.accepts(&ip) && !ips.contains(&ip) {
+            ips.push(ip);
+        }
+    }
+
+    Ok(ips)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn lookup() {
+        let ips = super::lookup(
+            "127.0.0.1",
+            5432,
+            crate::connection::AddressFamily::Ipv4,
+            Some(std::time::Duration::from_secs(5)),
+        )
+        .unwrap();
+        assert_eq!(ips, vec![std::net::IpAddr::from([127, 0, 0, 1])]);
+
+        let ips = super::lookup(
+            "127.0.0.1",
+            5432,
+            crate::connection::AddressFamily::Ipv6,
+            None,
+        )
+        .unwrap();
+        assert!(ips.is_empty());
+
+        assert!(super::lookup(
+            "unknow.invalid",
+            5432,
+            crate::connection::AddressFamily::Any,
+            None
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,110 @@
+/**
+ * Service files searched for a service, in order: `PGSERVICEFILE` or `~/.pg_service.conf`,
+ * then `pg_service.conf` in `PGSYSCONFDIR`.
+ *
+ * The system directory compiled in libpq isn't known here, `PGSYSCONFDIR` has to be set for
+ * the system file to be read.
+ */
+pub(crate) fn paths() -> Vec<std::path::PathBuf> {
+    let mut paths = Vec::new();
+
+    if let Some(path) = std::env::var_os("PGSERVICEFILE") {
+        paths.push(path.into());
+    } else if let Some(home) = std::env::var_os("HOME") {
+        paths.push(std::path::Path::new(&home).join(".pg_service.conf"));
+    }
+
+    if let Some(dir) = std::env::var_os("PGSYSCONFDIR") {
+        paths.push(std::path::Path::new(&dir).join("pg_service.conf"));
+    }
+
+    paths
+}
+
+/**
+ * Returns the options of the section `[service]` of the
+ * [connection service file](https://www.postgresql.org/docs/current/libpq-pgservice.html) at
+ * `path`, `None` if the file or the section doesn't exist.
+ */
+pub(crate) fn lookup(
+    path: &std::path::Path,
+    service: &str,
+) -> crate::errors::Result<Option<Vec<(String, String)>>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(crate::errors::Error::Backend(format!(
+                "Unable to read the service file '{}': {err}",
+                path.display()
+            )))
+        }
+    };
+
+    let mut options = None;
+
+    for (n, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(name) = line.strip_prefix('[').and_then(|x| x.strip_suffix(']')) {
+            if options.is_some() {
+                break;
+            }
+            if name == service {
+                options = Some(Vec::new());
+            }
+        } else if let Some(options) = &mut options {
+            let Some((keyword, value)) = line.split_once('=') else {
+                return Err(crate::errors::Error::Backend(format!(
+                    "syntax error in service file \"{}\", line {}",
+                    path.display(),
+                    n + 1
+                )));
+            };
+
+            let keyword = keyword.trim();
+            if keyword == "service" {
+                return Err(crate::errors::Error::Backend(format!(
+                    "nested service specifications not supported in service file \"{}\", line {}",
+                    path.display(),
+                    n + 1
+                )));
+            }
+
+            options.push((keyword.to_string(), value.trim().to_string()));
+        }
+    }
+
+    Ok(options)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn lookup() {
+        let path = std::env::temp_dir().join(format!("libpq-service-{}", std::process::id()));
+        std::fs::write(
+            &path,
+            "# comment\n[other]\nhost=other\n\n[app]\nhost=db.example\nport = 5433\n[last]\nport=1\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            super::lookup(&path, "app").unwrap(),
+            Some(vec![
+                ("host".to_string(), "db.example".to_string()),
+                ("port".to_string(), "5433".to_string()),
+            ])
+        );
+        assert_eq!(super::lookup(&path, "missing").unwrap(), None);
+
+        std::fs::write(&path, "[app]\nservice=other\n").unwrap();
+        assert!(super::lookup(&path, "app").is_err());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
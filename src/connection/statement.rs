@@ -0,0 +1,236 @@
+/**
+ * A named prepared statement, deallocated when dropped.
+ *
+ * Created by `libpq::Connection::prepare_statement`.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let statement =
+ *     conn.prepare_statement("item_by_id", "SELECT * FROM item WHERE id = $1", &[libpq::types::INT4.oid])?;
+ *
+ * for id in [1_i32, 2, 3] {
+ *     let result = statement.execute(&[&id])?;
+ * }
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct Statement<'c> {
+    conn: &'c crate::Connection,
+    name: String,
+    closed: bool,
+    param_types: std::cell::OnceCell<Vec<crate::Oid>>,
+}
+
+impl<'c> Statement<'c> {
+    pub(crate) fn new(conn: &'c crate::Connection, name: &str) -> Self {
+        Self {
+            conn,
+            name: name.to_string(),
+            closed: false,
+            param_types: std::cell::OnceCell::new(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /**
+     * The parameter types of the statement, known once `Statement::describe` has been called.
+     */
+    pub fn param_types(&self) -> Option<&[crate::Oid]> {
+        self.param_types.get().map(Vec::as_slice)
+    }
+
+    /**
+     * Executes the statement.
+     *
+     * The parameter types are the ones given when preparing the statement, or inferred by the
+     * server; the values are sent in the format of their `libpq::types::ToParam`
+     * implementation.
+     *
+     * Once the statement is described, the parameters are checked against its types before
+     * being sent: an `Error::ParamMismatch` error is returned for a wrong number of
+     * parameters, or a binary value of another type. Text values are parsed by the server, so
+     * they are accepted for any type.
+     *
+     * See `libpq::Connection::exec_prepared`.
+     */
+    pub fn execute(
+        &self,
+        params: &[&dyn crate::types::ToParam],
+    ) -> crate::errors::Result<crate::PQResult> {
+        let params = crate::types::Params::new(params);
+        params.check()?;
+        self.check_params(&params)?;
+
+        self.conn.exec_prepared_checked(
+            Some(&self.name),
+            &params.values(),
+            &params.formats,
+            crate::Format::Text,
+        )
+    }
+
+    /**
+     * Returns the description of the parameters and the result columns of the statement.
+     *
+     * The parameter types are kept to check the parameters of `Statement::execute`.
+     *
+     * See `libpq::Connection::describe_prepared`.
+     */
+    pub fn describe(&self) -> crate::errors::Result<crate::PQResult> {
+        let description = self
+            .conn
+            .check_result(self.conn.describe_prepared(Some(&self.name)))?;
+
+        let param_types = (0..description.nparams())
+            .map(|x| description.param_type(x).unwrap_or_default())
+            .collect();
+        self.param_types.set(param_types).ok();
+
+        Ok(description)
+    }
+
+    fn check_params(&self, params: &crate::types::Params) -> crate::errors::Result {
+        let Some(expected) = self.param_types.get() else {
+            return Ok(());
+        };
+
+        if params.len() != expected.len() {
+            return Err(crate::errors::Error::ParamMismatch(format!(
+                "{} parameters given, the statement expects {}",
+                params.len(),
+                expected.len()
+            )));
+        }
+
+        for (x, expected) in expected.iter().enumerate() {
+            let oid = params.types[x];
+
+            if params.formats[x] == crate::Format::Binary
+                && params.values[x].is_some()
+                && oid != *expected
+            {
+                let name = |oid: crate::Oid| {
+                    crate::Type::try_from(oid)
+                        .map(|x| x.name.to_string())
+                        .unwrap_or_else(|_| oid.to_string())
+                };
+
+                return Err(crate::errors::Error::ParamMismatch(format!(
+                    "${} is {}, the statement expects {}",
+                    x + 1,
+                    name(oid),
+                    name(*expected)
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Deallocates the statement.
+     */
+    pub fn close(mut self) -> crate::errors::Result {
+        self.closed = true;
+        self.deallocate()
+    }
+
+    #[cfg(feature = "v17")]
+    fn deallocate(&self) -> crate::errors::Result {
+        self.conn
+            .check_result(self.conn.close_prepared(Some(&self.name)))?;
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "v17"))]
+    fn deallocate(&self) -> crate::errors::Result {
+        let name = self.conn.escape_identifier(&self.name)?;
+        self.conn
+            .exec_checked(&format!("DEALLOCATE {}", name.to_string_lossy()))?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        if self.closed {
+            return;
+        }
+
+        log::trace!("Deallocating prepared statement {}", self.name);
+
+        self.deallocate().ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn statement() {
+        let conn = crate::test::new_conn();
+
+        let statement = conn
+            .prepare_statement(
+                "statement",
+                "SELECT $1::int4 + $2",
+                &[crate::types::INT4.oid, crate::types::INT4.oid],
+            )
+            .unwrap();
+        assert_eq!(statement.name(), "statement");
+
+        let result = statement.execute(&[&1_i32, &2_i32]).unwrap();
+        assert_eq!(result.get::<i32>(0, 0).unwrap(), 3);
+
+        assert_eq!(statement.param_types(), None);
+        let description = statement.describe().unwrap();
+        assert_eq!(description.nparams(), 2);
+        assert_eq!(description.nfields(), 1);
+        assert_eq!(
+            statement.param_types(),
+            Some(&[crate::types::INT4.oid, crate::types::INT4.oid][..])
+        );
+
+        assert!(conn
+            .prepare_statement("statement", "SELECT 1", &[])
+            .is_err());
+
+        assert_eq!(
+            statement.execute(&[&1_i64, &2_i32]).unwrap_err(),
+            crate::errors::Error::ParamMismatch(
+                "$1 is int8, the statement expects int4".to_string()
+            )
+        );
+        assert_eq!(
+            statement.execute(&[&1_i32]).unwrap_err(),
+            crate::errors::Error::ParamMismatch(
+                "1 parameters given, the statement expects 2".to_string()
+            )
+        );
+        let result = statement.execute(&[&"1", &None::<i64>]).unwrap();
+        assert_eq!(result.get::<Option<i32>>(0, 0).unwrap(), None);
+
+        statement.close().unwrap();
+        assert_eq!(
+            conn.describe_prepared(Some("statement")).status(),
+            crate::Status::FatalError
+        );
+
+        {
+            let _statement = conn
+                .prepare_statement("dropped \"statement\"", "SELECT 1", &[])
+                .unwrap();
+        }
+        assert_eq!(
+            conn.exec("SELECT count(*) FROM pg_prepared_statements")
+                .get::<i64>(0, 0)
+                .unwrap(),
+            0
+        );
+    }
+}
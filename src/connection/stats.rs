@@ -0,0 +1,98 @@
+/**
+ * Traffic counters of a connection, see `libpq::Connection::stats`.
+ *
+ * libpq doesn't expose its socket, only the data going through this crate is counted: the
+ * queries sent and the COPY data exchanged, not the protocol overhead nor the results.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Stats {
+    /**
+     * Queries sent by `exec`, `exec_params`, `prepare`, `exec_prepared` and their `send_*`
+     * counterparts, including the ones made by the methods built on them.
+     */
+    pub queries_sent: u64,
+    pub copy_bytes_sent: u64,
+    /** Number of `PQputCopyData` calls. */
+    pub copy_messages_sent: u64,
+    pub copy_bytes_received: u64,
+    /** Number of rows received with `PQgetCopyData`. */
+    pub copy_messages_received: u64,
+}
+
+/**
+ * Limits the throughput of a COPY stream, see `libpq::connection::CopyInWriter::throttle`.
+ */
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Throttle {
+    bytes_per_second: u64,
+    start: Option<std::time::Instant>,
+    bytes: u64,
+}
+
+impl Throttle {
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self {
+            bytes_per_second: bytes_per_second.max(1),
+            start: None,
+            bytes: 0,
+        }
+    }
+
+    /**
+     * Accounts for `len` bytes, sleeping as long as needed to stay under the rate.
+     */
+    pub fn consume(&mut self, len: usize) {
+        let start = *self.start.get_or_insert_with(std::time::Instant::now);
+        self.bytes += len as u64;
+
+        let expected =
+            std::time::Duration::from_secs_f64(self.bytes as f64 / self.bytes_per_second as f64);
+        let elapsed = start.elapsed();
+
+        if expected > elapsed {
+            std::thread::sleep(expected - elapsed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn queries_sent() {
+        let conn = crate::test::new_conn();
+        let sent = || conn.stats().queries_sent;
+
+        conn.reset_stats();
+        conn.exec("SELECT 1");
+        assert_eq!(sent(), 1);
+        conn.exec_params("SELECT $1", &[], &[Some(b"1")], &[], crate::Format::Text);
+        assert_eq!(sent(), 2);
+        conn.exec_typed("SELECT $1", &[&1_i32]).unwrap();
+        assert_eq!(sent(), 3);
+        conn.prepare(Some("stats"), "SELECT 1", &[]);
+        assert_eq!(sent(), 4);
+        conn.exec_prepared(Some("stats"), &[], &[], crate::Format::Text);
+        assert_eq!(sent(), 5);
+
+        conn.send_query("SELECT 1").unwrap();
+        conn.results().for_each(drop);
+        assert_eq!(sent(), 6);
+        conn.send_query_params("SELECT 1", &[], &[], &[], crate::Format::Text)
+            .unwrap();
+        conn.results().for_each(drop);
+        assert_eq!(sent(), 7);
+        conn.send_prepare(Some("stats_async"), "SELECT 1", &[])
+            .unwrap();
+        conn.results().for_each(drop);
+        assert_eq!(sent(), 8);
+        conn.send_query_prepared(Some("stats_async"), &[], &[], crate::Format::Text)
+            .unwrap();
+        conn.results().for_each(drop);
+        assert_eq!(sent(), 9);
+
+        // Neither the comment hook nor the descriptions count as queries.
+        conn.commented("SELECT 1");
+        conn.describe_prepared(Some("stats"));
+        assert_eq!(sent(), 9);
+    }
+}
@@ -0,0 +1,231 @@
+/**
+ * Iterator over the rows of a query executed in single-row mode.
+ *
+ * Created by `libpq::Connection::stream_query`. Each item is a `libpq::Status::SingleTuple`
+ * result holding one row, or a `libpq::Status::TuplesChunk` result holding several rows with
+ * `libpq::Connection::stream_query_chunked`; a failing query yields an `Err` item and ends the
+ * iteration.
+ *
+ * Dropping the stream before its end reads and discards the remaining rows, so the connection
+ * is ready for the next command. See [`RowStream::cancel_on_drop`] to avoid transferring them.
+ */
+#[cfg(unix)]
+#[derive(Debug)]
+pub struct RowStream<'c> {
+    conn: &'c crate::Connection,
+    cancel_on_drop: bool,
+    done: bool,
+}
+
+#[cfg(unix)]
+impl<'c> RowStream<'c> {
+    pub(crate) fn new(conn: &'c crate::Connection) -> crate::errors::Result<Self> {
+        let stream = Self {
+            conn,
+            cancel_on_drop: false,
+            done: false,
+        };
+        stream.flush()?;
+
+        Ok(stream)
+    }
+
+    /**
+     * Cancels the query when the stream is dropped before its end, instead of receiving all
+     * the remaining rows.
+     *
+     * A canceled query aborts the current transaction block, if any.
+     */
+    pub fn cancel_on_drop(mut self) -> Self {
+        self.cancel_on_drop = true;
+        self
+    }
+
+    /**
+     * Sends the queued output, needed when the connection is in non-blocking mode.
+     */
+    fn flush(&self) -> crate::errors::Result {
+        let socket = self.conn.socket()?;
+
+        loop {
+            match unsafe { pq_sys::PQflush(self.conn.into()) } {
+                0 => return Ok(()),
+                1 => {
+                    crate::connection::wait_socket(socket, true, true, None)?;
+                    // The server may be blocked sending us data: read it to let it go on.
+                    self.conn.consume_input()?;
+                }
+                _ => return self.conn.error(),
+            }
+        }
+    }
+
+    fn next_result(&self) -> crate::errors::Result<Option<crate::PQResult>> {
+        let socket = self.conn.socket()?;
+
+        while self.conn.is_busy() {
+            crate::connection::wait_socket(socket, true, false, None)?;
+            self.conn.consume_input()?;
+        }
+
+        Ok(self.conn.result())
+    }
+}
+
+#[cfg(unix)]
+impl Iterator for RowStream<'_> {
+    type Item = crate::errors::Result<crate::PQResult>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let result = match self.next_result() {
+                Ok(Some(result)) => result,
+                Ok(None) => {
+                    self.done = true;
+                    break;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+
+            match result.status() {
+                crate::Status::SingleTuple => return Some(Ok(result)),
+                #[cfg(feature = "v17")]
+                crate::Status::TuplesChunk => return Some(Ok(result)),
+                // Zero-row result marking the end of the rows.
+                crate::Status::TuplesOk => (),
+                status @ (crate::Status::CopyIn
+                | crate::Status::CopyOut
+                | crate::Status::CopyBoth) => {
+                    // libpq returns the same status until the COPY ends.
+                    self.done = true;
+                    self.conn.discard_results().ok();
+
+                    return Some(Err(crate::errors::Error::Backend(format!(
+                        "Unexpected {status:?} result in a row stream"
+                    ))));
+                }
+                _ => {
+                    let result = self.conn.check_result(result);
+
+                    if result.is_err() {
+                        // The remaining results are drained on the next call or on drop.
+                        return Some(result);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RowStream<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        log::trace!("Draining row stream");
+
+        let result = if self.cancel_on_drop {
+            self.conn.cancel_and_discard()
+        } else {
+            self.conn.discard_results()
+        };
+
+        if let Err(err) = result {
+            log::trace!("Unable to drain the row stream: {err}");
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod test {
+    #[test]
+    fn stream_query() {
+        let conn = crate::test::new_conn();
+
+        let rows = conn
+            .stream_query("SELECT generate_series(1, $1::int4)", &[&"3"])
+            .unwrap()
+            .map(|row| row.unwrap().get::<i32>(0, 0).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![1, 2, 3]);
+
+        let mut stream = conn
+            .stream_query("SELECT 1 / (2 - x) FROM generate_series(1, 3) x", &[])
+            .unwrap();
+        assert!(stream.next().unwrap().is_ok());
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn drop_stream() {
+        let conn = crate::test::new_conn();
+        conn.set_non_blocking(true).unwrap();
+
+        let mut stream = conn
+            .stream_query("SELECT generate_series(1, 10000)", &[])
+            .unwrap();
+        assert!(stream.next().unwrap().is_ok());
+        drop(stream);
+
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+
+        let mut stream = conn
+            .stream_query("SELECT generate_series(1, 100000000)", &[])
+            .unwrap()
+            .cancel_on_drop();
+        assert!(stream.next().unwrap().is_ok());
+        let start = std::time::Instant::now();
+        drop(stream);
+        assert!(start.elapsed() < std::time::Duration::from_secs(10));
+
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+
+        let mut stream = conn.stream_query("COPY (SELECT 1) TO STDOUT", &[]).unwrap();
+        assert!(stream.next().unwrap().is_err());
+        assert!(stream.next().is_none());
+
+        assert_eq!(conn.exec("SELECT 1").status(), crate::Status::TuplesOk);
+    }
+
+    #[test]
+    #[cfg(feature = "v17")]
+    fn stream_query_chunked() {
+        let conn = crate::test::new_conn();
+
+        let chunks = conn
+            .stream_query_chunked("SELECT generate_series(1, 100000)", &[], 1_000)
+            .unwrap()
+            .map(|chunk| chunk.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 100);
+        assert!(chunks
+            .iter()
+            .all(|x| x.status() == crate::Status::TuplesChunk && x.ntuples() == 1_000));
+        assert_eq!(chunks[99].get::<i32>(999, 0), Ok(100_000));
+
+        conn.send_query("SELECT generate_series(1, 10)").unwrap();
+        conn.set_chunked_rows_mode(3).unwrap();
+        let mut rows = Vec::new();
+        while let Some(result) = conn.result() {
+            rows.push((result.status(), result.ntuples()));
+        }
+        assert_eq!(
+            rows,
+            vec![
+                (crate::Status::TuplesChunk, 3),
+                (crate::Status::TuplesChunk, 3),
+                (crate::Status::TuplesChunk, 3),
+                (crate::Status::TuplesChunk, 1),
+                (crate::Status::TuplesOk, 0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+/**
+ * A connection shareable between threads.
+ *
+ * A libpq connection can be moved between threads but must not be used concurrently: this
+ * wrapper serializes accesses with a mutex.
+ *
+ * If a thread panics while holding the connection, the lock is poisoned since the connection
+ * may be left in the middle of a command or transaction. Use
+ * [`SyncConnection::recover`] to restore it to an idle state.
+ */
+#[derive(Debug)]
+pub struct SyncConnection {
+    inner: std::sync::Mutex<crate::Connection>,
+    last_activity: std::sync::Mutex<std::time::Instant>,
+}
+
+impl SyncConnection {
+    pub fn new(conn: crate::Connection) -> Self {
+        Self {
+            inner: std::sync::Mutex::new(conn),
+            last_activity: std::sync::Mutex::new(std::time::Instant::now()),
+        }
+    }
+
+    /**
+     * Acquires the connection, blocking the current thread until it is available.
+     *
+     * Returns `Error::Poisoned` if another thread panicked while holding it.
+     */
+    pub fn lock(&self) -> crate::errors::Result<std::sync::MutexGuard<'_, crate::Connection>> {
+        let conn = self
+            .inner
+            .lock()
+            .map_err(|_| crate::errors::Error::Poisoned)?;
+        self.touch();
+
+        Ok(conn)
+    }
+
+    /**
+     * Acquires the connection if it's available, without blocking.
+     */
+    pub fn try_lock(
+        &self,
+    ) -> Option<crate::errors::Result<std::sync::MutexGuard<'_, crate::Connection>>> {
+        let conn = match self.inner.try_lock() {
+            Ok(conn) => Ok(conn),
+            Err(std::sync::TryLockError::Poisoned(_)) => Err(crate::errors::Error::Poisoned),
+            Err(std::sync::TryLockError::WouldBlock) => return None,
+        };
+        self.touch();
+
+        Some(conn)
+    }
+
+    fn touch(&self) {
+        if let Ok(mut last_activity) = self.last_activity.lock() {
+            *last_activity = std::time::Instant::now();
+        }
+    }
+
+    /**
+     * Last time the connection was acquired.
+     */
+    pub fn last_activity(&self) -> std::time::Instant {
+        self.last_activity
+            .lock()
+            .map(|x| *x)
+            .unwrap_or_else(|x| *x.into_inner())
+    }
+
+    /**
+     * Spawns a thread sending an empty query when the connection is idle for longer than
+     * `interval`, see [`crate::connection::Keepalive`].
+     *
+     * The thread stops when the returned handle is dropped, or at the first error.
+     */
+    pub fn spawn_keepalive(
+        self: &std::sync::Arc<Self>,
+        interval: std::time::Duration,
+    ) -> crate::connection::KeepaliveHandle {
+        crate::connection::KeepaliveHandle::spawn(self.clone(), interval)
+    }
+
+    /**
+     * Returns `true` if another thread panicked while holding the connection.
+     */
+    pub fn is_poisoned(&self) -> bool {
+        self.inner.is_poisoned()
+    }
+
+    /**
+     * Acquires the connection, restoring it to an idle state if the lock is poisoned.
+     *
+     * Pending results are discarded, an aborted or in progress transaction is rolled back and
+     * a broken connection is reset.
+     */
+    pub fn recover(&self) -> crate::errors::Result<std::sync::MutexGuard<'_, crate::Connection>> {
+        let conn = match self.inner.lock() {
+            Ok(conn) => return Ok(conn),
+            Err(err) => err.into_inner(),
+        };
+        self.touch();
+
+        log::debug!("Recovering poisoned connection");
+
+        if conn.status() == crate::connection::Status::Bad {
+            conn.reset();
+
+            if conn.status() == crate::connection::Status::Bad {
+                return conn.error();
+            }
+        }
+
+        conn.ensure_idle()?;
+
+        self.inner.clear_poison();
+
+        Ok(conn)
+    }
+
+    /**
+     * Consumes this wrapper, returning the underlying connection.
+     */
+    pub fn into_inner(self) -> crate::errors::Result<crate::Connection> {
+        self.inner
+            .into_inner()
+            .map_err(|_| crate::errors::Error::Poisoned)
+    }
+
+    /**
+     * Locks the connection and calls `libpq::Connection::exec`.
+     */
+    pub fn exec(&self, query: &str) -> crate::errors::Result<crate::PQResult> {
+        Ok(self.lock()?.exec(query))
+    }
+
+    /**
+     * Locks the connection and calls `libpq::Connection::exec_params`.
+     */
+    pub fn exec_params(
+        &self,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        Ok(self.lock()?.exec_params(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        ))
+    }
+
+    /**
+     * Locks the connection and calls `libpq::Connection::prepare`.
+     */
+    pub fn prepare(
+        &self,
+        name: Option<&str>,
+        query: &str,
+        param_types: &[crate::Oid],
+    ) -> crate::errors::Result<crate::PQResult> {
+        Ok(self.lock()?.prepare(name, query, param_types))
+    }
+
+    /**
+     * Locks the connection and calls `libpq::Connection::exec_prepared`.
+     */
+    pub fn exec_prepared(
+        &self,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result<crate::PQResult> {
+        Ok(self
+            .lock()?
+            .exec_prepared(name, param_values, param_formats, result_format))
+    }
+
+    /**
+     * Locks the connection and runs `f` with it.
+     */
+    pub fn with<F, T>(&self, f: F) -> crate::errors::Result<T>
+    where
+        F: FnOnce(&crate::Connection) -> T,
+    {
+        let conn = self.lock()?;
+
+        Ok(f(&conn))
+    }
+}
+
+impl From<crate::Connection> for SyncConnection {
+    fn from(conn: crate::Connection) -> Self {
+        Self::new(conn)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn threads() {
+        let conn = std::sync::Arc::new(crate::connection::SyncConnection::new(
+            crate::test::new_conn(),
+        ));
+
+        let threads = (0..4)
+            .map(|x| {
+                let conn = conn.clone();
+
+                std::thread::spawn(move || {
+                    let result = conn.exec(&format!("SELECT {x}")).unwrap();
+                    assert_eq!(result.value(0, 0), Some(x.to_string().as_bytes()));
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn recover() {
+        let conn = std::sync::Arc::new(crate::connection::SyncConnection::new(
+            crate::test::new_conn(),
+        ));
+
+        let thread = {
+            let conn = conn.clone();
+
+            std::thread::spawn(move || {
+                let conn = conn.lock().unwrap();
+                conn.exec("BEGIN");
+                conn.exec("SELECT 1/0");
+                panic!("worker failed");
+            })
+        };
+        assert!(thread.join().is_err());
+
+        assert!(conn.is_poisoned());
+        assert_eq!(
+            conn.exec("SELECT 1").unwrap_err(),
+            crate::errors::Error::Poisoned
+        );
+
+        let guard = conn.recover().unwrap();
+        assert_eq!(guard.transaction_status(), crate::transaction::Status::Idle);
+        drop(guard);
+
+        assert!(!conn.is_poisoned());
+        assert_eq!(
+            conn.exec("SELECT 1").unwrap().status(),
+            crate::Status::TuplesOk
+        );
+    }
+}
@@ -0,0 +1,346 @@
+/**
+ * Signature starting the binary COPY format.
+ *
+ * See [Binary Format](https://www.postgresql.org/docs/current/sql-copy.html).
+ */
+const SIGNATURE: &[u8] = b"PGCOPY\n\xFF\r\n\0";
+
+/**
+ * Header flag set when the data includes OIDs, only used by servers older than 12.
+ */
+const FLAG_OIDS: u32 = 1 << 16;
+
+/**
+ * Encodes rows in the binary COPY format, for `COPY … FROM STDIN (FORMAT binary)`.
+ *
+ * The header is written with the first row, the trailer by [`BinaryCopyWriter::finish`].
+ * Values must be encoded in the binary format of their column type.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let writer = conn.copy_in("COPY item (id, name) FROM STDIN (FORMAT binary)")?;
+ * let mut writer = libpq::copy::binary::BinaryCopyWriter::new(writer);
+ *
+ * writer.write_row(&[Some(&1_i32.to_be_bytes()), Some(b"foo")]).unwrap();
+ * writer.write_row(&[Some(&2_i32.to_be_bytes()), None]).unwrap();
+ *
+ * writer.finish().unwrap().finish()?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct BinaryCopyWriter<W: std::io::Write> {
+    inner: W,
+    header: bool,
+}
+
+impl<W: std::io::Write> BinaryCopyWriter<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            header: false,
+        }
+    }
+
+    /**
+     * Writes a row, `None` being a null value.
+     */
+    pub fn write_row(&mut self, row: &[Option<&[u8]>]) -> std::io::Result<()> {
+        self.write_header()?;
+
+        let count = i16::try_from(row.len()).map_err(|_| invalid_input("Too many fields"))?;
+        self.inner.write_all(&count.to_be_bytes())?;
+
+        for field in row {
+            match field {
+                Some(value) => {
+                    let len = i32::try_from(value.len())
+                        .map_err(|_| invalid_input("Field value too long"))?;
+                    self.inner.write_all(&len.to_be_bytes())?;
+                    self.inner.write_all(value)?;
+                }
+                None => self.inner.write_all(&(-1_i32).to_be_bytes())?,
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Writes a row of typed values, see `libpq::types::ToParam`.
+     *
+     * Text parameters are sent as is: it's the binary representation of the `text`, `varchar`
+     * and `name` types only.
+     */
+    pub fn write_typed(&mut self, row: &[&dyn crate::types::ToParam]) -> std::io::Result<()> {
+        let values = row.iter().map(|x| x.to_param()).collect::<Vec<_>>();
+        let row = values.iter().map(Option::as_deref).collect::<Vec<_>>();
+
+        self.write_row(&row)
+    }
+
+    /**
+     * Writes the trailer and returns the inner writer.
+     */
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_header()?;
+        self.inner.write_all(&(-1_i16).to_be_bytes())?;
+        self.inner.flush()?;
+
+        Ok(self.inner)
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        if self.header {
+            return Ok(());
+        }
+
+        self.header = true;
+
+        self.inner.write_all(SIGNATURE)?;
+        // Flags
+        self.inner.write_all(&0_u32.to_be_bytes())?;
+        // Header extension length
+        self.inner.write_all(&0_u32.to_be_bytes())
+    }
+}
+
+/**
+ * A row decoded by [`BinaryCopyReader`], `None` being a null value.
+ */
+pub type Row = Vec<Option<Vec<u8>>>;
+
+/**
+ * Decodes rows in the binary COPY format, from `COPY … TO STDOUT (FORMAT binary)`.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let reader = conn.copy_out("COPY item (id, name) TO STDOUT (FORMAT binary)")?;
+ *
+ * for row in libpq::copy::binary::BinaryCopyReader::new(reader) {
+ *     let row = row.unwrap();
+ *     let id = i32::from_be_bytes(row[0].as_deref().unwrap().try_into().unwrap());
+ * }
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct BinaryCopyReader<R: std::io::Read> {
+    inner: R,
+    header: bool,
+    done: bool,
+}
+
+impl<R: std::io::Read> BinaryCopyReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            header: false,
+            done: false,
+        }
+    }
+
+    /**
+     * Reads the next row, `None` after the trailer.
+     */
+    pub fn read_row(&mut self) -> std::io::Result<Option<Row>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        self.read_header()?;
+
+        let count = i16::from_be_bytes(self.read_array()?);
+
+        if count == -1 {
+            self.done = true;
+            return Ok(None);
+        }
+
+        let count = usize::try_from(count).map_err(|_| invalid_data("Invalid field count"))?;
+        let mut row = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let len = i32::from_be_bytes(self.read_array()?);
+
+            let field = if len == -1 {
+                None
+            } else {
+                let len = usize::try_from(len).map_err(|_| invalid_data("Invalid field length"))?;
+                // The length isn't trusted to allocate the buffer before reading the data.
+                let mut value = Vec::new();
+                std::io::Read::read_to_end(
+                    &mut std::io::Read::take(&mut self.inner, len as u64),
+                    &mut value,
+                )?;
+
+                if value.len() != len {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::UnexpectedEof,
+                        "Truncated field value",
+                    ));
+                }
+
+                Some(value)
+            };
+
+            row.push(field);
+        }
+
+        Ok(Some(row))
+    }
+
+    /**
+     * Returns the inner reader.
+     */
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn read_header(&mut self) -> std::io::Result<()> {
+        if self.header {
+            return Ok(());
+        }
+
+        self.header = true;
+
+        let mut signature = [0; SIGNATURE.len()];
+        self.inner.read_exact(&mut signature)?;
+
+        if signature != SIGNATURE {
+            return Err(invalid_data("Invalid binary COPY signature"));
+        }
+
+        let flags = u32::from_be_bytes(self.read_array()?);
+
+        if flags & FLAG_OIDS != 0 {
+            return Err(invalid_data("Binary COPY with OIDs is not supported"));
+        }
+
+        let extension_len = u32::from_be_bytes(self.read_array()?);
+        std::io::copy(
+            &mut std::io::Read::take(&mut self.inner, extension_len.into()),
+            &mut std::io::sink(),
+        )?;
+
+        Ok(())
+    }
+
+    fn read_array<const N: usize>(&mut self) -> std::io::Result<[u8; N]> {
+        let mut buf = [0; N];
+        self.inner.read_exact(&mut buf)?;
+
+        Ok(buf)
+    }
+}
+
+impl<R: std::io::Read> Iterator for BinaryCopyReader<R> {
+    type Item = std::io::Result<Row>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_row() {
+            Ok(row) => row.map(Ok),
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn encode() {
+        let mut writer = super::BinaryCopyWriter::new(Vec::new());
+        writer
+            .write_row(&[Some(&b"\xFF\x00\xFF\x00\xFF\x00\xFF"[..]), None])
+            .unwrap();
+        let data = writer.finish().unwrap();
+
+        assert_eq!(
+            data,
+            b"PGCOPY\n\xFF\r\n\0\0\0\0\0\0\0\0\0\
+              \x00\x02\x00\x00\x00\x07\xFF\x00\xFF\x00\xFF\x00\xFF\xFF\xFF\xFF\xFF\
+              \xFF\xFF"
+        );
+
+        let mut reader = super::BinaryCopyReader::new(data.as_slice());
+        assert_eq!(
+            reader.read_row().unwrap(),
+            Some(vec![Some(b"\xFF\x00\xFF\x00\xFF\x00\xFF".to_vec()), None])
+        );
+        assert_eq!(reader.read_row().unwrap(), None);
+
+        let data = super::BinaryCopyWriter::new(Vec::new()).finish().unwrap();
+        assert_eq!(super::BinaryCopyReader::new(data.as_slice()).count(), 0);
+    }
+
+    #[test]
+    fn decode_invalid() {
+        let mut reader = super::BinaryCopyReader::new(&b"COPY"[..]);
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+
+        let mut reader = super::BinaryCopyReader::new(
+            &b"PGCOPY\n\xFF\r\n\0\0\0\0\0\0\0\0\0\x00\x01\x00\x00\x00\x07\xFF"[..],
+        );
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+
+        let mut reader = super::BinaryCopyReader::new(
+            &b"PGCOPY\n\xFF\r\n\0\0\0\0\0\0\0\0\0\x00\x01\x7F\xFF\xFF\xFF\xFF"[..],
+        );
+        assert_eq!(
+            reader.next().unwrap().unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn copy() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE copy_binary (id int4, name text, data bytea)");
+
+        let writer = conn
+            .copy_in("COPY copy_binary FROM STDIN (FORMAT binary)")
+            .unwrap();
+        let mut writer = super::BinaryCopyWriter::new(writer);
+        writer
+            .write_typed(&[&1_i32, &"foo", &b"\0\xFF".to_vec()])
+            .unwrap();
+        writer
+            .write_row(&[Some(&2_i32.to_be_bytes()), None, None])
+            .unwrap();
+        writer.finish().unwrap().finish().unwrap();
+
+        let reader = conn
+            .copy_out("COPY copy_binary TO STDOUT (FORMAT binary)")
+            .unwrap();
+        let rows = super::BinaryCopyReader::new(reader)
+            .collect::<std::io::Result<Vec<_>>>()
+            .unwrap();
+
+        assert_eq!(
+            rows,
+            vec![
+                vec![
+                    Some(1_i32.to_be_bytes().to_vec()),
+                    Some(b"foo".to_vec()),
+                    Some(b"\0\xFF".to_vec())
+                ],
+                vec![Some(2_i32.to_be_bytes().to_vec()), None, None],
+            ]
+        );
+    }
+}
@@ -5,17 +5,396 @@ pub enum Error {
     #[error("{0}")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("{0}")]
+    ParseFloatError(#[from] std::num::ParseFloatError),
+    #[error("{0}")]
     NulError(#[from] std::ffi::NulError),
     #[error("{0}")]
     Backend(String),
+    /**
+     * Every connection attempt failed, one attempt per host candidate.
+     *
+     * Only returned when the hosts are tried one by one, with
+     * `libpq::connection::Config::handshake_timeout`. Otherwise, a failed connection is an
+     * `Error::Backend` with libpq's message: see `Error::connect_attempts`.
+     */
+    #[error("{}", display_attempts(.0))]
+    Connect(Vec<ConnectAttempt>),
+    #[error("{message}")]
+    Query {
+        status: crate::Status,
+        sqlstate: Option<String>,
+        message: String,
+        /** Secondary error message, carrying more detail about the problem. */
+        detail: Option<String>,
+        /** Suggestion of what to do about the problem. */
+        hint: Option<String>,
+        /** Error cursor position, as a 1-based character index in the query. */
+        position: Option<usize>,
+    },
+    #[error("Unknow column '{0}'")]
+    UnknowColumn(String),
+    #[error("Unexpected null value")]
+    UnexpectedNull,
+    #[error("Unable to convert SQL type {oid} into {rust_type}")]
+    WrongType {
+        oid: crate::Oid,
+        rust_type: &'static str,
+    },
+    #[error("Invalid bytea at offset {0}")]
+    InvalidBytea(usize),
     #[error("Large object error")]
     LargeObject,
     #[error("Invalid SSL attribute: '{0}'")]
     InvalidSslAttribute(String),
     #[error("Timeout")]
     Timeout,
+    #[error("Statement rejected on a read-only connection: {0}")]
+    ReadOnly(String),
+    #[error("Parameters not matching the statement: {0}")]
+    ParamMismatch(String),
+    #[error("Connection lock poisoned")]
+    Poisoned,
+    #[cfg(feature = "explain")]
+    #[error("Invalid plan: {0}")]
+    Explain(String),
     #[error("Unknow error")]
     Unknow,
     #[error("{0}")]
     Utf8(#[from] std::str::Utf8Error),
 }
+
+/**
+ * A failed connection attempt to a host candidate, see [`Error::Connect`].
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConnectAttempt {
+    /** Host name or socket path, as specified in the connection parameters. */
+    pub host: Option<String>,
+    /** Resolved IP address. */
+    pub address: Option<String>,
+    pub port: Option<u16>,
+    pub message: String,
+}
+
+impl ConnectAttempt {
+    /**
+     * Splits a libpq connection error message into the failure of each host candidate.
+     *
+     * This is best-effort: libpq's messages are translated, so only the English ones are
+     * understood. Returns an empty vector if the message doesn't come from a connection attempt
+     * or isn't in English.
+     */
+    pub(crate) fn parse_all(message: &str) -> Vec<Self> {
+        let mut attempts: Vec<Self> = Vec::new();
+
+        for line in message.lines() {
+            if let Some(continuation) = line.strip_prefix('\t') {
+                if let Some(attempt) = attempts.last_mut() {
+                    attempt.message.push('\n');
+                    attempt.message.push_str(continuation);
+                }
+            } else if !line.is_empty() {
+                match Self::parse(line) {
+                    Some(attempt) => attempts.push(attempt),
+                    None => return Vec::new(),
+                }
+            }
+        }
+
+        attempts
+    }
+
+    fn parse(line: &str) -> Option<Self> {
+        if let Some(rest) = line.strip_prefix("connection to server at \"") {
+            // connection to server at "host" (address), port 5432 failed: message
+            let (host, rest) = rest.split_once('"')?;
+            let (address, rest) = match rest.strip_prefix(" (") {
+                Some(rest) => {
+                    let (address, rest) = rest.split_once(')')?;
+                    (Some(address.to_string()), rest)
+                }
+                None => (None, rest),
+            };
+            let (port, message) = rest.strip_prefix(", port ")?.split_once(" failed: ")?;
+
+            Some(Self {
+                host: Some(host.to_string()),
+                address,
+                port: port.parse().ok(),
+                message: message.to_string(),
+            })
+        } else if let Some(rest) = line.strip_prefix("connection to server on socket \"") {
+            // connection to server on socket "/path/.s.PGSQL.5432" failed: message
+            let (socket, message) = rest.split_once("\" failed: ")?;
+            let port = socket
+                .rsplit_once(".s.PGSQL.")
+                .and_then(|(_, x)| x.parse().ok());
+
+            Some(Self {
+                host: Some(socket.to_string()),
+                address: None,
+                port,
+                message: message.to_string(),
+            })
+        } else if let Some(rest) = line.strip_prefix("could not translate host name \"") {
+            // could not translate host name "host" to address: message
+            let (host, _) = rest.split_once('"')?;
+
+            Some(Self {
+                host: Some(host.to_string()),
+                address: None,
+                port: None,
+                message: line.to_string(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+/**
+ * Why the verification of the server certificate failed, see [`ConnectAttempt::ssl_failure`].
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SslFailure {
+    /**
+     * The certificate chain is valid, but the certificate isn't issued for the requested host
+     * name (`sslmode=verify-full`).
+     */
+    HostnameMismatch,
+    /**
+     * The certificate chain couldn't be verified against the root certificates: unknown
+     * authority, expired or revoked certificate.
+     */
+    CertificateVerify,
+    /** The root certificate file (`sslrootcert`) doesn't exist. */
+    MissingRootCert,
+}
+
+impl ConnectAttempt {
+    /**
+     * Classifies a failure of the server certificate verification, `None` for any other
+     * failure.
+     */
+    pub fn ssl_failure(&self) -> Option<SslFailure> {
+        let message = &self.message;
+
+        let hostname_mismatch = (message.starts_with("server certificate for \"")
+            && message.contains("does not match host name"))
+            || message.contains("could not get server's host name from server certificate");
+
+        if hostname_mismatch {
+            Some(SslFailure::HostnameMismatch)
+        } else if message.contains("certificate verify failed") {
+            Some(SslFailure::CertificateVerify)
+        } else if message.starts_with("root certificate file \"")
+            && message.contains("does not exist")
+        {
+            Some(SslFailure::MissingRootCert)
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for ConnectAttempt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(host) = &self.host {
+            write!(f, "\"{host}\"")?;
+        }
+
+        if let Some(address) = &self.address {
+            write!(f, " ({address})")?;
+        }
+
+        if let Some(port) = self.port {
+            write!(f, ", port {port}")?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+fn display_attempts(attempts: &[ConnectAttempt]) -> String {
+    let mut message = format!(
+        "Unable to connect, {} host candidate(s) tried:",
+        attempts.len()
+    );
+
+    for attempt in attempts {
+        message.push_str(&format!("\n- {attempt}"));
+    }
+
+    message
+}
+
+/**
+ * An error position in a query text, see [`Error::position_in`].
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Position {
+    /** 1-based line number. */
+    pub line: usize,
+    /** 1-based column number, in characters. */
+    pub column: usize,
+    /** The line of the query containing the error, followed by a caret under the error. */
+    pub snippet: String,
+}
+
+impl Error {
+    /**
+     * The failed connection attempts, one per host candidate tried.
+     *
+     * For an `Error::Backend`, they are parsed from libpq's message. This is best-effort: only
+     * the English messages are understood, so the result is empty with another `lc_messages`.
+     */
+    pub fn connect_attempts(&self) -> Vec<ConnectAttempt> {
+        match self {
+            Self::Connect(attempts) => attempts.clone(),
+            Self::Backend(message) => ConnectAttempt::parse_all(message),
+            _ => Vec::new(),
+        }
+    }
+
+    /**
+     * Maps the error cursor position of a `Error::Query` error to a line and column in `query`,
+     * which must be the text of the failed query.
+     */
+    pub fn position_in(&self, query: &str) -> Option<Position> {
+        let Self::Query {
+            position: Some(position),
+            ..
+        } = self
+        else {
+            return None;
+        };
+
+        let mut remaining = position.checked_sub(1)?;
+
+        for (number, line) in query.split('\n').enumerate() {
+            let len = line.chars().count();
+
+            if remaining <= len {
+                let line = line.trim_end_matches('\r');
+                let indent = line
+                    .chars()
+                    .take(remaining)
+                    .map(|x| if x == '\t' { '\t' } else { ' ' })
+                    .collect::<String>();
+
+                return Some(Position {
+                    line: number + 1,
+                    column: remaining + 1,
+                    snippet: format!("{line}\n{indent}^"),
+                });
+            }
+
+            remaining -= len + 1;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn position_in() {
+        let conn = crate::test::new_conn();
+
+        let query = "SELECT 1\n  FROM unknow";
+        let error = conn.exec_checked(query).unwrap_err();
+        let position = error.position_in(query).unwrap();
+
+        assert_eq!(position.line, 2);
+        assert_eq!(position.column, 8);
+        assert_eq!(position.snippet, "  FROM unknow\n       ^");
+
+        let query = "SELECT 'é', unknow";
+        let error = conn.exec_checked(query).unwrap_err();
+        let position = error.position_in(query).unwrap();
+
+        assert_eq!(position.line, 1);
+        assert_eq!(position.column, 13);
+
+        let error = conn.exec_checked("SELECT 1/0").unwrap_err();
+        assert_eq!(error.position_in("SELECT 1/0"), None);
+    }
+
+    #[test]
+    fn connect_attempts() {
+        let message =
+            "connection to server at \"localhost\" (::1), port 1 failed: Connection refused
+\tIs the server running on that host and accepting TCP/IP connections?
+could not translate host name \"unknow.invalid\" to address: Name or service not known
+connection to server on socket \"/tmp/.s.PGSQL.5433\" failed: No such file or directory
+";
+        let attempts = super::ConnectAttempt::parse_all(message);
+
+        assert_eq!(
+            attempts,
+            [
+                super::ConnectAttempt {
+                    host: Some("localhost".to_string()),
+                    address: Some("::1".to_string()),
+                    port: Some(1),
+                    message: "Connection refused\nIs the server running on that host and accepting TCP/IP connections?".to_string(),
+                },
+                super::ConnectAttempt {
+                    host: Some("unknow.invalid".to_string()),
+                    address: None,
+                    port: None,
+                    message: "could not translate host name \"unknow.invalid\" to address: Name or service not known".to_string(),
+                },
+                super::ConnectAttempt {
+                    host: Some("/tmp/.s.PGSQL.5433".to_string()),
+                    address: None,
+                    port: Some(5433),
+                    message: "No such file or directory".to_string(),
+                },
+            ]
+        );
+
+        assert!(super::ConnectAttempt::parse_all("out of memory").is_empty());
+    }
+
+    #[test]
+    fn ssl_failure() {
+        let message = "connection to server at \"db.example\" (192.0.2.1), port 5432 failed: server certificate for \"other.example\" does not match host name \"db.example\"
+connection to server at \"db2.example\" (192.0.2.2), port 5432 failed: SSL error: certificate verify failed
+connection to server at \"db3.example\" (192.0.2.3), port 5432 failed: root certificate file \"/nonexistent\" does not exist
+\tEither provide the file, use the system's trusted roots with sslrootcert=system, or change sslmode to disable server certificate verification.
+connection to server at \"db4.example\" (192.0.2.4), port 5432 failed: Connection refused
+";
+        let failures = super::ConnectAttempt::parse_all(message)
+            .iter()
+            .map(super::ConnectAttempt::ssl_failure)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            failures,
+            [
+                Some(super::SslFailure::HostnameMismatch),
+                Some(super::SslFailure::CertificateVerify),
+                Some(super::SslFailure::MissingRootCert),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn connect() {
+        let error =
+            crate::Connection::new("host=localhost,unknow.invalid port=1,2 connect_timeout=5")
+                .unwrap_err();
+
+        assert!(matches!(error, crate::errors::Error::Backend(_)));
+
+        let attempts = error.connect_attempts();
+        assert!(attempts.len() >= 2);
+        assert!(attempts.iter().any(|x| x.port == Some(1)));
+        assert!(attempts
+            .iter()
+            .any(|x| x.host.as_deref() == Some("unknow.invalid")));
+    }
+}
@@ -101,6 +101,108 @@ pub fn bytea(from: &[u8]) -> crate::errors::Result<PqBytes> {
     }
 }
 
+/**
+ * Output format of bytea escaping.
+ *
+ * See [Binary Data Types](https://www.postgresql.org/docs/current/datatype-binary.html).
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ByteaFormat {
+    /** Each byte is represented by two hexadecimal digits, prefixed by `\x`. */
+    #[default]
+    Hex,
+    /** Non-printable bytes are represented as octal escapes. */
+    Escape,
+}
+
+pub(crate) fn bytea_as(
+    conn: &crate::Connection,
+    from: &[u8],
+    format: ByteaFormat,
+) -> crate::errors::Result<String> {
+    let standard_strings = conn.parameter_status("standard_conforming_strings")? != "off";
+
+    let escaped = match format {
+        ByteaFormat::Hex => bytea_hex(from),
+        ByteaFormat::Escape => bytea_escape(from),
+    };
+
+    if standard_strings {
+        Ok(escaped)
+    } else {
+        Ok(escaped.replace('\\', "\\\\"))
+    }
+}
+
+/**
+ * Encodes binary data in the bytea hex format, for use within a string literal when
+ * `standard_conforming_strings` is on.
+ *
+ * Unlike `libpq::Connection::escape_bytea`, this doesn't need a connection and the result
+ * isn't NUL terminated.
+ */
+pub fn bytea_hex(from: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut to = String::with_capacity(2 + from.len() * 2);
+    to.push_str("\\x");
+
+    for byte in from {
+        to.push(DIGITS[(byte >> 4) as usize] as char);
+        to.push(DIGITS[(byte & 0xf) as usize] as char);
+    }
+
+    to
+}
+
+/**
+ * Encodes binary data in the bytea escape format, for use within a string literal when
+ * `standard_conforming_strings` is on.
+ *
+ * Unlike `libpq::Connection::escape_bytea`, this doesn't need a connection and the result
+ * isn't NUL terminated.
+ */
+pub fn bytea_escape(from: &[u8]) -> String {
+    let mut to = String::with_capacity(from.len());
+
+    for byte in from {
+        match byte {
+            b'\'' => to.push_str("''"),
+            b'\\' => to.push_str("\\\\"),
+            0x20..=0x7e => to.push(*byte as char),
+            _ => to.push_str(&format!("\\{byte:03o}")),
+        }
+    }
+
+    to
+}
+
+/**
+ * Escapes the `%` and `_` wildcards and `escape_char` itself, so `value` matches literally in a
+ * `LIKE` or `ILIKE` pattern.
+ *
+ * The result is meant to be sent as a parameter, or escaped as a literal: it isn't quoted. Use
+ * the same `escape_char` in the `ESCAPE` clause, `\` being the default.
+ *
+ * ```
+ * let pattern = format!("{}%", libpq::escape::like_pattern("50%_off", '\\'));
+ * assert_eq!(pattern, "50\\%\\_off%");
+ * ```
+ */
+pub fn like_pattern(value: &str, escape_char: char) -> String {
+    let mut to = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        if c == '%' || c == '_' || c == escape_char {
+            to.push(escape_char);
+        }
+
+        to.push(c);
+    }
+
+    to
+}
+
 /**
  * Converts a string representation of binary data into binary data — the reverse of
  * `libpq::Connection::escape_bytea`.
@@ -122,6 +224,74 @@ pub fn unescape_bytea(from: &[u8]) -> crate::errors::Result<PqBytes> {
     }
 }
 
+/**
+ * Decodes a bytea value in hex or escape format, as returned in text format results.
+ *
+ * Unlike [`unescape_bytea`], this is done in Rust and the input is validated: invalid data
+ * returns an `Error::InvalidBytea` error with the offset of the offending byte.
+ */
+pub fn decode_bytea(from: &[u8]) -> crate::errors::Result<Vec<u8>> {
+    match from.strip_prefix(b"\\x") {
+        Some(hex) => decode_bytea_hex(hex, 2),
+        None => decode_bytea_escape(from),
+    }
+}
+
+fn decode_bytea_hex(from: &[u8], start: usize) -> crate::errors::Result<Vec<u8>> {
+    let digit = |offset: usize| -> crate::errors::Result<u8> {
+        match from.get(offset) {
+            Some(c @ b'0'..=b'9') => Ok(c - b'0'),
+            Some(c @ b'a'..=b'f') => Ok(c - b'a' + 10),
+            Some(c @ b'A'..=b'F') => Ok(c - b'A' + 10),
+            _ => Err(crate::errors::Error::InvalidBytea(start + offset)),
+        }
+    };
+
+    let mut to = Vec::with_capacity(from.len() / 2);
+    let mut offset = 0;
+
+    while offset < from.len() {
+        if from[offset].is_ascii_whitespace() {
+            offset += 1;
+            continue;
+        }
+
+        to.push(digit(offset)? << 4 | digit(offset + 1)?);
+        offset += 2;
+    }
+
+    Ok(to)
+}
+
+fn decode_bytea_escape(from: &[u8]) -> crate::errors::Result<Vec<u8>> {
+    let mut to = Vec::with_capacity(from.len());
+    let mut offset = 0;
+
+    while offset < from.len() {
+        if from[offset] != b'\\' {
+            to.push(from[offset]);
+            offset += 1;
+            continue;
+        }
+
+        if from.get(offset + 1) == Some(&b'\\') {
+            to.push(b'\\');
+            offset += 2;
+            continue;
+        }
+
+        match from.get(offset + 1..offset + 4) {
+            Some(&[a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7']) => {
+                to.push((a - b'0') << 6 | (b - b'0') << 3 | (c - b'0'));
+                offset += 4;
+            }
+            _ => return Err(crate::errors::Error::InvalidBytea(offset)),
+        }
+    }
+
+    Ok(to)
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -132,6 +302,11 @@ mod test {
             crate::escape::literal(&conn, "foo").unwrap().as_ref(),
             b"'foo'"
         );
+
+        assert_eq!(
+            crate::escape::literal(&conn, "foo").unwrap().into_string(),
+            Ok("'foo'".to_string())
+        );
     }
 
     #[test]
@@ -162,6 +337,27 @@ mod test {
         assert_eq!(crate::escape::string("'foo'"), Ok("''foo''".to_string()));
     }
 
+    #[test]
+    fn like_pattern() {
+        assert_eq!(crate::escape::like_pattern("foo", '\\'), "foo");
+        assert_eq!(
+            crate::escape::like_pattern("100%_a\\b", '\\'),
+            "100\\%\\_a\\\\b"
+        );
+        assert_eq!(crate::escape::like_pattern("a!b%", '!'), "a!!b!%");
+
+        let conn = crate::test::new_conn();
+        let result = conn
+            .exec_typed(
+                "SELECT 'a%b_c' LIKE $1, 'axbyc' LIKE $1, 'A%B_C' ILIKE $1",
+                &[&crate::escape::like_pattern("a%b_c", '\\')],
+            )
+            .unwrap();
+        assert_eq!(result.get::<bool>(0, 0), Ok(true));
+        assert_eq!(result.get::<bool>(0, 1), Ok(false));
+        assert_eq!(result.get::<bool>(0, 2), Ok(true));
+    }
+
     #[test]
     fn bytea_conn() {
         let conn = crate::test::new_conn();
@@ -170,6 +366,11 @@ mod test {
             crate::escape::bytea_conn(&conn, b"\0").unwrap().as_ref(),
             b"\\x00\0"
         );
+
+        assert_eq!(
+            crate::escape::bytea_conn(&conn, b"\0").unwrap().into_vec(),
+            b"\\x00\0"
+        );
     }
 
     #[test]
@@ -189,4 +390,87 @@ mod test {
             b"''foo''\0"
         );
     }
+
+    #[test]
+    fn bytea_hex() {
+        assert_eq!(crate::escape::bytea_hex(b""), "\\x");
+        assert_eq!(crate::escape::bytea_hex(b"\0'\xff"), "\\x0027ff");
+    }
+
+    #[test]
+    fn bytea_escape() {
+        assert_eq!(
+            crate::escape::bytea_escape(b"a'\\\0\n\xff"),
+            "a''\\\\\\000\\012\\377"
+        );
+    }
+
+    #[test]
+    fn bytea_as() {
+        let conn = crate::test::new_conn();
+
+        for format in [
+            crate::escape::ByteaFormat::Hex,
+            crate::escape::ByteaFormat::Escape,
+        ] {
+            let data = b"\0'\\foo\xff";
+            let escaped = conn.escape_bytea_as(data, format).unwrap();
+            let result = conn.exec(&format!("SELECT '{escaped}'::bytea = '\\x00275c666f6fff'"));
+            assert_eq!(result.value(0, 0), Some(&b"t"[..]));
+
+            conn.exec("SET standard_conforming_strings = off");
+            let escaped = conn.escape_bytea_as(data, format).unwrap();
+            let result = conn.exec(&format!(
+                "SELECT '{escaped}'::bytea = E'\\\\x00275c666f6fff'"
+            ));
+            assert_eq!(result.value(0, 0), Some(&b"t"[..]));
+            conn.exec("RESET standard_conforming_strings");
+        }
+    }
+
+    #[test]
+    fn decode_bytea() {
+        assert_eq!(
+            crate::escape::decode_bytea(b"\\x00275C ff").unwrap(),
+            b"\0'\\\xff"
+        );
+        assert_eq!(
+            crate::escape::decode_bytea(b"a\\\\\\000\\377").unwrap(),
+            b"a\\\0\xff"
+        );
+        assert_eq!(crate::escape::decode_bytea(b"\\x").unwrap(), b"");
+        assert_eq!(crate::escape::decode_bytea(b"\\\\").unwrap(), b"\\");
+
+        assert_eq!(
+            crate::escape::decode_bytea(b"\\x0g"),
+            Err(crate::errors::Error::InvalidBytea(3))
+        );
+        assert_eq!(
+            crate::escape::decode_bytea(b"\\x001"),
+            Err(crate::errors::Error::InvalidBytea(5))
+        );
+        assert_eq!(
+            crate::escape::decode_bytea(b"ab\\400"),
+            Err(crate::errors::Error::InvalidBytea(2))
+        );
+        assert_eq!(
+            crate::escape::decode_bytea(b"ab\\"),
+            Err(crate::errors::Error::InvalidBytea(2))
+        );
+    }
+
+    #[test]
+    fn decode_bytea_result() {
+        let conn = crate::test::new_conn();
+
+        for output in ["hex", "escape"] {
+            conn.exec(&format!("SET bytea_output = {output}"));
+            let result = conn.exec("SELECT '\\x00275c666f6fff'::bytea");
+
+            assert_eq!(
+                crate::escape::decode_bytea(result.value(0, 0).unwrap()).unwrap(),
+                b"\0'\\foo\xff"
+            );
+        }
+    }
 }
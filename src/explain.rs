@@ -0,0 +1,156 @@
+/**
+ * `EXPLAIN` options.
+ *
+ * The plan is always requested in JSON format.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    /** Carry out the command and show actual run times and other statistics. */
+    pub analyze: bool,
+    /** Display additional information regarding the plan. */
+    pub verbose: bool,
+    /** Include information on buffer usage. */
+    pub buffers: bool,
+    /** Include information on configuration parameters affecting the plan. */
+    pub settings: bool,
+    /** Include information on WAL record generation, requires `analyze`. */
+    pub wal: bool,
+}
+
+impl Options {
+    fn to_sql(self) -> String {
+        let mut options = vec!["FORMAT JSON".to_string()];
+
+        for (name, value) in [
+            ("ANALYZE", self.analyze),
+            ("VERBOSE", self.verbose),
+            ("BUFFERS", self.buffers),
+            ("SETTINGS", self.settings),
+            ("WAL", self.wal),
+        ] {
+            if value {
+                options.push(format!("{name} TRUE"));
+            }
+        }
+
+        options.join(", ")
+    }
+}
+
+/**
+ * The output of `EXPLAIN`.
+ */
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct Explain {
+    #[serde(rename = "Plan")]
+    pub plan: Plan,
+    /** Planning time in milliseconds, only with `analyze`. */
+    #[serde(rename = "Planning Time")]
+    pub planning_time: Option<f64>,
+    /** Execution time in milliseconds, only with `analyze`. */
+    #[serde(rename = "Execution Time")]
+    pub execution_time: Option<f64>,
+}
+
+/**
+ * A plan node.
+ */
+#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
+pub struct Plan {
+    #[serde(rename = "Node Type")]
+    pub node_type: String,
+    #[serde(rename = "Startup Cost")]
+    pub startup_cost: f64,
+    #[serde(rename = "Total Cost")]
+    pub total_cost: f64,
+    #[serde(rename = "Plan Rows")]
+    pub plan_rows: f64,
+    #[serde(rename = "Plan Width")]
+    pub plan_width: u64,
+    #[serde(rename = "Actual Startup Time")]
+    pub actual_startup_time: Option<f64>,
+    #[serde(rename = "Actual Total Time")]
+    pub actual_total_time: Option<f64>,
+    #[serde(rename = "Actual Rows")]
+    pub actual_rows: Option<f64>,
+    #[serde(rename = "Actual Loops")]
+    pub actual_loops: Option<f64>,
+    /** Children nodes. */
+    #[serde(rename = "Plans", default)]
+    pub plans: Vec<Plan>,
+    /** Node specific properties, like `Relation Name` or `Filter`. */
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+pub(crate) fn explain(
+    conn: &crate::Connection,
+    query: &str,
+    options: &Options,
+) -> crate::errors::Result<Explain> {
+    let result = conn.exec(&format!("EXPLAIN ({}) {query}", options.to_sql()));
+
+    if result.status() != crate::Status::TuplesOk {
+        return conn.error();
+    }
+
+    let Some(value) = result.value(0, 0) else {
+        return Err(crate::errors::Error::Explain("empty output".to_string()));
+    };
+
+    let mut explains: Vec<Explain> = serde_json::from_slice(value)
+        .map_err(|err| crate::errors::Error::Explain(err.to_string()))?;
+
+    if explains.is_empty() {
+        Err(crate::errors::Error::Explain("empty output".to_string()))
+    } else {
+        Ok(explains.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn explain() {
+        let conn = crate::test::new_conn();
+
+        let explain = conn
+            .explain(
+                "SELECT * FROM generate_series(1, 10)",
+                &crate::explain::Options::default(),
+            )
+            .unwrap();
+        assert_eq!(explain.plan.node_type, "Function Scan");
+        assert!(explain.plan.total_cost > 0.);
+        assert_eq!(explain.plan.actual_rows, None);
+        assert_eq!(explain.execution_time, None);
+    }
+
+    #[test]
+    fn analyze() {
+        let conn = crate::test::new_conn();
+
+        let options = crate::explain::Options {
+            analyze: true,
+            buffers: true,
+            ..Default::default()
+        };
+        let explain = conn
+            .explain("SELECT count(*) FROM generate_series(1, 10)", &options)
+            .unwrap();
+        assert_eq!(explain.plan.node_type, "Aggregate");
+        assert_eq!(explain.plan.actual_rows, Some(1.));
+        assert_eq!(explain.plan.plans.len(), 1);
+        assert_eq!(explain.plan.plans[0].actual_rows, Some(10.));
+        assert!(explain.execution_time.is_some());
+    }
+
+    #[test]
+    fn invalid() {
+        let conn = crate::test::new_conn();
+
+        assert!(conn
+            .explain("SELECT * FROM unknow", &Default::default())
+            .is_err());
+    }
+}
@@ -1,7 +1,90 @@
 use std::os::raw::c_char;
 
 pub(crate) fn to_cstr(s: &str) -> std::ffi::CString {
-    unsafe { std::ffi::CString::from_vec_unchecked(s.as_bytes().to_vec()) }
+    // Reserve the nul byte, to not leave a copy behind a reallocation, see `wipe`.
+    let mut bytes = Vec::with_capacity(s.len() + 1);
+    bytes.extend_from_slice(s.as_bytes());
+
+    unsafe { std::ffi::CString::from_vec_unchecked(bytes) }
+}
+
+/**
+ * Frees the buffers, overwriting them with zeros first with the `zeroize` feature.
+ */
+pub(crate) fn wipe(buffers: Vec<std::ffi::CString>) {
+    #[cfg(feature = "zeroize")]
+    for buffer in buffers {
+        zeroize::Zeroize::zeroize(&mut buffer.into_bytes_with_nul());
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    drop(buffers);
+}
+
+/**
+ * Opens a C stream in write mode on `file`, which is then owned by the stream: closing the stream
+ * with `fclose` closes the file.
+ */
+pub(crate) fn into_stream(file: std::fs::File) -> *mut libc::FILE {
+    #[cfg(unix)]
+    let fd = std::os::unix::io::IntoRawFd::into_raw_fd(file);
+
+    #[cfg(windows)]
+    let fd = {
+        let handle = std::os::windows::io::IntoRawHandle::into_raw_handle(file);
+        let fd = unsafe { libc::open_osfhandle(handle as libc::intptr_t, 0) };
+
+        if fd < 0 {
+            use std::os::windows::io::FromRawHandle;
+
+            drop(unsafe { std::os::windows::io::OwnedHandle::from_raw_handle(handle) });
+            return std::ptr::null_mut();
+        }
+
+        fd
+    };
+
+    let stream = unsafe { libc::fdopen(fd, c"w".as_ptr()) };
+
+    if stream.is_null() {
+        unsafe {
+            libc::close(fd);
+        }
+    }
+
+    stream
+}
+
+/**
+ * Opens a C stream in write mode on a duplicate of the borrowed file descriptor `fd`.
+ *
+ * The caller must close the stream with `fclose`, which leaves `fd` open.
+ */
+#[cfg(unix)]
+pub(crate) fn fdopen(fd: std::os::unix::io::RawFd) -> *mut libc::FILE {
+    let fd = unsafe { std::os::unix::io::BorrowedFd::borrow_raw(fd) };
+
+    match fd.try_clone_to_owned() {
+        Ok(fd) => into_stream(fd.into()),
+        Err(_) => std::ptr::null_mut(),
+    }
+}
+
+/**
+ * Opens a C stream in write mode on a duplicate of the borrowed file `handle`.
+ *
+ * The duplicate is associated to a C runtime file descriptor, so libpq must use the same C
+ * runtime as this crate. The caller must close the stream with `fclose`, which leaves `handle`
+ * open.
+ */
+#[cfg(windows)]
+pub(crate) fn fdopen(handle: std::os::windows::io::RawHandle) -> *mut libc::FILE {
+    let handle = unsafe { std::os::windows::io::BorrowedHandle::borrow_raw(handle) };
+
+    match handle.try_clone_to_owned() {
+        Ok(handle) => into_stream(handle.into()),
+        Err(_) => std::ptr::null_mut(),
+    }
 }
 
 pub(crate) fn to_str(s: *const c_char) -> crate::errors::Result<&'static str> {
@@ -1,10 +1,60 @@
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[repr(i32)]
 pub enum Format {
     Text = 0,
     Binary = 1,
 }
 
+impl Format {
+    /**
+     * Format to exchange values of `ty`: binary when the crate has a binary codec for it (see
+     * `libpq::types::ToParam` and `libpq::types::FromSql`), text otherwise.
+     */
+    pub fn for_type(ty: &crate::Type) -> Self {
+        Self::for_oid(ty.oid)
+    }
+
+    /**
+     * Like `libpq::Format::for_type`, text for an unknown or unspecified (`0`) type.
+     */
+    pub fn for_oid(oid: crate::Oid) -> Self {
+        let binary = [
+            crate::types::BOOL.oid,
+            crate::types::BYTEA.oid,
+            crate::types::INT2.oid,
+            crate::types::INT4.oid,
+            crate::types::INT8.oid,
+            crate::types::OID.oid,
+            crate::types::FLOAT4.oid,
+            crate::types::FLOAT8.oid,
+            crate::types::TEXT.oid,
+            crate::types::VARCHAR.oid,
+            crate::types::BPCHAR.oid,
+            crate::types::NAME.oid,
+        ];
+
+        if binary.contains(&oid) {
+            Self::Binary
+        } else {
+            Self::Text
+        }
+    }
+
+    /**
+     * Result format of a query returning columns of `types`.
+     *
+     * libpq requests a single format for all the columns: binary only if every column can be
+     * decoded from binary, text otherwise.
+     */
+    pub fn for_types(types: &[crate::Oid]) -> Self {
+        if !types.is_empty() && types.iter().all(|x| Self::for_oid(*x) == Self::Binary) {
+            Self::Binary
+        } else {
+            Self::Text
+        }
+    }
+}
+
 impl From<i32> for Format {
     fn from(format: i32) -> Self {
         unsafe { std::mem::transmute(format) }
@@ -16,3 +66,29 @@ impl From<&Format> for i32 {
         unsafe { std::mem::transmute(*format) }
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn for_type() {
+        assert_eq!(
+            crate::Format::for_type(&crate::types::INT4),
+            crate::Format::Binary
+        );
+        assert_eq!(
+            crate::Format::for_type(&crate::types::NUMERIC),
+            crate::Format::Text
+        );
+        assert_eq!(crate::Format::for_oid(0), crate::Format::Text);
+
+        assert_eq!(
+            crate::Format::for_types(&[crate::types::INT8.oid, crate::types::TEXT.oid]),
+            crate::Format::Binary
+        );
+        assert_eq!(
+            crate::Format::for_types(&[crate::types::INT8.oid, crate::types::DATE.oid]),
+            crate::Format::Text
+        );
+        assert_eq!(crate::Format::for_types(&[]), crate::Format::Text);
+    }
+}
@@ -4,18 +4,25 @@
 #[macro_use]
 mod ffi;
 
+#[cfg(feature = "cli")]
+pub mod cli;
 pub mod connection;
+pub mod copy;
 pub mod encrypt;
 pub mod errors;
 pub mod escape;
+#[cfg(feature = "explain")]
+pub mod explain;
 pub mod lo;
 pub mod ping;
 #[cfg(feature = "v14")]
 pub mod pipeline;
 pub mod poll;
-#[cfg(unix)]
 pub mod print;
+pub mod query;
 pub mod result;
+pub mod retry;
+pub mod settings;
 pub mod ssl;
 pub mod state;
 pub mod transaction;
@@ -128,15 +128,111 @@ pub fn open(
     Ok(lo)
 }
 
+/**
+ * Size of the chunks transferred with `lo_read` and `lo_write` by [`import_from_reader`] and
+ * [`export_to_writer`].
+ */
+const CHUNK_SIZE: usize = 256 * 1024;
+
+/**
+ * Creates a large object from the content of `reader`.
+ *
+ * Unlike [`import`], the data is streamed from the client by chunks and needs no file on the
+ * server. Like every large object operation, it must run inside a transaction. The large object
+ * is removed if reading or writing fails.
+ */
+pub fn import_from_reader(
+    conn: &crate::Connection,
+    mut reader: impl std::io::Read,
+) -> crate::errors::Result<crate::Oid> {
+    let lobj_id = create(conn, 0);
+
+    if lobj_id == crate::oid::INVALID {
+        return Err(crate::errors::Error::LargeObject);
+    }
+
+    let result = open(conn, lobj_id, Inv::WRITE).and_then(|lobj| {
+        let mut buf = vec![0; CHUNK_SIZE];
+
+        loop {
+            let len = match reader.read(&mut buf) {
+                Ok(0) => return Ok(()),
+                Ok(len) => len,
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(crate::errors::Error::Backend(err.to_string())),
+            };
+
+            let mut written = 0;
+
+            while written < len {
+                match lobj.write(&buf[written..len])? {
+                    0 => return Err(crate::errors::Error::LargeObject),
+                    n => written += n,
+                }
+            }
+        }
+    });
+
+    match result {
+        Ok(()) => Ok(lobj_id),
+        Err(err) => {
+            unlink(conn, lobj_id).ok();
+            Err(err)
+        }
+    }
+}
+
+/**
+ * Writes the content of the large object `lobj_id` into `writer`.
+ *
+ * Unlike [`export`], the data is streamed to the client by chunks and needs no file on the
+ * server. Like every large object operation, it must run inside a transaction. Returns the
+ * number of bytes written.
+ */
+pub fn export_to_writer(
+    conn: &crate::Connection,
+    lobj_id: crate::Oid,
+    mut writer: impl std::io::Write,
+) -> crate::errors::Result<u64> {
+    let lobj = open(conn, lobj_id, Inv::READ)?;
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut total = 0;
+
+    loop {
+        let len = lobj.read(&mut buf)?;
+
+        if len == 0 {
+            break;
+        }
+
+        writer
+            .write_all(&buf[..len])
+            .map_err(|err| crate::errors::Error::Backend(err.to_string()))?;
+        total += len as u64;
+    }
+
+    writer
+        .flush()
+        .map_err(|err| crate::errors::Error::Backend(err.to_string()))?;
+
+    Ok(total)
+}
+
 impl LargeObject<'_> {
     /**
      * Writing Data to a Large Object.
      *
      * See [lo_write](https://www.postgresql.org/docs/current/lo-interfaces.html#LO-WRITE)
      */
-    pub fn write(&self, buf: &str) -> crate::errors::Result<usize> {
-        let c_buf = crate::ffi::to_cstr(buf).into_raw();
-        let written = unsafe { pq_sys::lo_write(self.conn.into(), self.fd, c_buf, buf.len()) };
+    pub fn write(&self, buf: &[u8]) -> crate::errors::Result<usize> {
+        let written = unsafe {
+            pq_sys::lo_write(
+                self.conn.into(),
+                self.fd,
+                buf.as_ptr() as *const std::os::raw::c_char,
+                buf.len(),
+            )
+        };
 
         if written < 0 {
             Err(crate::errors::Error::LargeObject)
@@ -148,18 +244,24 @@ impl LargeObject<'_> {
     /**
      * Reading Data from a Large Object.
      *
+     * Returns the number of bytes read into `buf`, 0 at the end of the object.
+     *
      * See [lo_read](https://www.postgresql.org/docs/current/lo-interfaces.html#LO-READ)
      */
-    pub fn read(&self, len: usize) -> crate::errors::Result<String> {
-        let buf = String::with_capacity(len);
-        let c_buf = crate::ffi::to_cstr(&buf).into_raw();
-
-        let read = unsafe { pq_sys::lo_read(self.conn.into(), self.fd, c_buf, len) };
+    pub fn read(&self, buf: &mut [u8]) -> crate::errors::Result<usize> {
+        let read = unsafe {
+            pq_sys::lo_read(
+                self.conn.into(),
+                self.fd,
+                buf.as_mut_ptr() as *mut std::os::raw::c_char,
+                buf.len(),
+            )
+        };
 
         if read < 0 {
             Err(crate::errors::Error::LargeObject)
         } else {
-            Ok(buf)
+            Ok(read as usize)
         }
     }
 
@@ -184,20 +286,7 @@ impl LargeObject<'_> {
      * See [lo_lseek64](https://www.postgresql.org/docs/current/lo-interfaces.html#LO-SEEK)
      */
     pub fn lseek64(&self, offset: i64, whence: Seek) -> crate::errors::Result {
-        let success = unsafe {
-            pq_sys::lo_lseek64(
-                self.conn.into(),
-                self.fd,
-                offset as pq_sys::pg_int64,
-                whence.into(),
-            )
-        };
-
-        if success < 0 {
-            Err(crate::errors::Error::LargeObject)
-        } else {
-            Ok(())
-        }
+        self.seek64(offset, whence).map(|_| ())
     }
 
     /**
@@ -262,8 +351,130 @@ impl LargeObject<'_> {
     }
 }
 
+impl LargeObject<'_> {
+    fn seek64(&self, offset: i64, whence: Seek) -> crate::errors::Result<i64> {
+        let position = unsafe {
+            pq_sys::lo_lseek64(
+                self.conn.into(),
+                self.fd,
+                offset as pq_sys::pg_int64,
+                whence.into(),
+            )
+        };
+
+        if position < 0 {
+            Err(crate::errors::Error::LargeObject)
+        } else {
+            Ok(position as i64)
+        }
+    }
+
+    fn io_error(&self) -> std::io::Error {
+        let message = self
+            .conn
+            .error_message()
+            .map(str::trim_end)
+            .unwrap_or("large object error");
+
+        std::io::Error::other(message.to_string())
+    }
+}
+
+impl std::io::Read for LargeObject<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        LargeObject::read(self, buf).map_err(|_| self.io_error())
+    }
+}
+
+impl std::io::Write for LargeObject<'_> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        LargeObject::write(self, buf).map_err(|_| self.io_error())
+    }
+
+    /**
+     * Writes are sent to the server immediately, there is nothing to flush.
+     */
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl std::io::Seek for LargeObject<'_> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        let (offset, whence) = match pos {
+            std::io::SeekFrom::Start(offset) => (
+                i64::try_from(offset).map_err(|_| {
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "Offset too large")
+                })?,
+                Seek::Set,
+            ),
+            std::io::SeekFrom::Current(offset) => (offset, Seek::Cur),
+            std::io::SeekFrom::End(offset) => (offset, Seek::End),
+        };
+
+        self.seek64(offset, whence)
+            .map(|position| position as u64)
+            .map_err(|_| self.io_error())
+    }
+}
+
 impl Drop for LargeObject<'_> {
     fn drop(&mut self) {
         unsafe { pq_sys::lo_close(self.conn.into(), self.fd) };
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn io() {
+        use std::io::{Read, Seek, Write};
+
+        let conn = crate::test::new_conn();
+        conn.exec("begin");
+
+        let oid = super::create(&conn, 0);
+        let mut lobj = super::open(&conn, oid, super::Inv::READ | super::Inv::WRITE).unwrap();
+
+        lobj.write_all(b"foo\0bar\xFF").unwrap();
+        assert_eq!(lobj.stream_position().unwrap(), 8);
+
+        lobj.seek(std::io::SeekFrom::Start(3)).unwrap();
+        let mut buf = [0; 2];
+        lobj.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"\0b");
+
+        assert_eq!(lobj.seek(std::io::SeekFrom::End(-2)).unwrap(), 6);
+        let mut data = Vec::new();
+        lobj.read_to_end(&mut data).unwrap();
+        assert_eq!(data, b"r\xFF");
+
+        lobj.rewind().unwrap();
+        let mut buf = [0; 16];
+        assert_eq!(super::LargeObject::read(&lobj, &mut buf).unwrap(), 8);
+        assert_eq!(&buf[..8], b"foo\0bar\xFF");
+
+        assert!(lobj.seek(std::io::SeekFrom::Current(-100)).is_err());
+
+        drop(lobj);
+        conn.exec("rollback");
+    }
+
+    #[test]
+    fn stream() {
+        let conn = crate::test::new_conn();
+        conn.exec("begin");
+
+        let data = (0..600_000).map(|x| (x % 251) as u8).collect::<Vec<_>>();
+        let oid = super::import_from_reader(&conn, data.as_slice()).unwrap();
+
+        let mut exported = Vec::new();
+        assert_eq!(
+            super::export_to_writer(&conn, oid, &mut exported).unwrap(),
+            data.len() as u64
+        );
+        assert_eq!(exported, data);
+
+        conn.exec("rollback");
+    }
+}
@@ -32,3 +32,133 @@ impl From<pq_sys::PGPing> for Status {
         }
     }
 }
+
+/**
+ * A ping made by `libpq::ping::wait_until_ready`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Attempt {
+    pub status: Status,
+    /** Time elapsed since the first ping, when this one returned. */
+    pub elapsed: std::time::Duration,
+}
+
+/**
+ * The pings made by `libpq::ping::wait_until_ready`, in order.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Report {
+    pub attempts: Vec<Attempt>,
+}
+
+impl Report {
+    /**
+     * Status of the last ping, `None` if there was no ping.
+     */
+    pub fn status(&self) -> Option<Status> {
+        self.attempts.last().map(|x| x.status)
+    }
+
+    /**
+     * Whether the server accepts connections.
+     */
+    pub fn is_ready(&self) -> bool {
+        self.status() == Some(Status::Ok)
+    }
+
+    /**
+     * Total time spent waiting.
+     */
+    pub fn elapsed(&self) -> std::time::Duration {
+        self.attempts.last().map(|x| x.elapsed).unwrap_or_default()
+    }
+}
+
+/**
+ * Pings the server every `interval` until it accepts connections or `total_timeout` expires,
+ * like the wait loop of a test harness waiting for a freshly started server.
+ *
+ * A `Status::NoAttempt` ping stops immediately, the parameters won't get better by waiting.
+ * Each ping can last up to the `connect_timeout` of `dsn`, which can delay the end past the
+ * deadline.
+ *
+ * ```no_run
+ * let report = libpq::ping::wait_until_ready(
+ *     "host=localhost",
+ *     std::time::Duration::from_secs(30),
+ *     std::time::Duration::from_millis(500),
+ * );
+ *
+ * if !report.is_ready() {
+ *     panic!("server not ready: {:?}", report.attempts);
+ * }
+ * ```
+ *
+ * See `libpq::Connection::ping`.
+ */
+pub fn wait_until_ready(
+    dsn: &str,
+    total_timeout: std::time::Duration,
+    interval: std::time::Duration,
+) -> Report {
+    let start = std::time::Instant::now();
+    let mut report = Report::default();
+
+    loop {
+        let status = crate::Connection::ping(dsn);
+        let elapsed = start.elapsed();
+
+        report.attempts.push(Attempt { status, elapsed });
+
+        if matches!(status, Status::Ok | Status::NoAttempt) {
+            break;
+        }
+
+        let Some(remaining) = total_timeout.checked_sub(elapsed).filter(|x| !x.is_zero()) else {
+            break;
+        };
+
+        log::trace!("Server not ready ({status:?}), next ping in {interval:?}");
+        std::thread::sleep(interval.min(remaining));
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn wait_until_ready() {
+        let report = super::wait_until_ready(
+            &crate::test::dsn(),
+            std::time::Duration::from_secs(5),
+            std::time::Duration::from_millis(10),
+        );
+        assert!(report.is_ready());
+        assert_eq!(report.attempts.len(), 1);
+
+        // Nobody listens on this port once the listener is dropped.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let report = super::wait_until_ready(
+            &format!("host=127.0.0.1 port={port}"),
+            std::time::Duration::from_millis(100),
+            std::time::Duration::from_millis(20),
+        );
+        assert!(!report.is_ready());
+        assert_eq!(report.status(), Some(super::Status::NoResponse));
+        assert!(report.attempts.len() > 1);
+        assert!(report.elapsed() >= std::time::Duration::from_millis(100));
+
+        let report = super::wait_until_ready(
+            "invalid=option",
+            std::time::Duration::from_secs(5),
+            std::time::Duration::from_millis(10),
+        );
+        assert_eq!(report.status(), Some(super::Status::NoAttempt));
+        assert_eq!(report.attempts.len(), 1);
+    }
+}
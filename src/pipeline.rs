@@ -80,6 +80,8 @@ pub fn status(conn: &crate::Connection) -> Status {
  * [PQpipelineSync](https://www.postgresql.org/docs/current/libpq-pipeline-mode.html#LIBPQ-PQPIPELINESYNC)
  */
 pub fn sync(conn: &crate::Connection) -> crate::errors::Result {
+    log::debug!("Pipeline sync");
+
     let success = unsafe { pq_sys::PQpipelineSync(conn.into()) };
 
     if success == 1 {
@@ -96,6 +98,8 @@ pub fn sync(conn: &crate::Connection) -> crate::errors::Result {
  * [PQsendFlushRequest](https://www.postgresql.org/docs/current/libpq-pipeline-mode.html#LIBPQ-PQSENDFLUSHREQUEST)
  */
 pub fn flush_request(conn: &crate::Connection) -> crate::errors::Result {
+    log::debug!("Pipeline flush request");
+
     let success = unsafe { pq_sys::PQsendFlushRequest(conn.into()) };
 
     if success == 1 {
@@ -113,6 +117,8 @@ pub fn flush_request(conn: &crate::Connection) -> crate::errors::Result {
  */
 #[cfg(feature = "v17")]
 pub fn send_sync(conn: &crate::Connection) -> crate::errors::Result {
+    log::debug!("Pipeline sync without flush");
+
     let success = unsafe { pq_sys::PQsendPipelineSync(conn.into()) };
 
     if success == 1 {
@@ -122,6 +128,293 @@ pub fn send_sync(conn: &crate::Connection) -> crate::errors::Result {
     }
 }
 
+#[derive(Debug)]
+enum Entry<T> {
+    Command {
+        tag: T,
+        deadline: Option<std::time::Instant>,
+    },
+    Sync,
+}
+
+/**
+ * A pipeline pairing each queued command with a user-supplied tag.
+ *
+ * Results are returned by [`Pipeline::poll_completed`] along with the tag of their command, so
+ * several logical requests sharing the connection can be matched back with their results.
+ *
+ * Only the last result of each command is kept: single-row mode isn't supported.
+ */
+#[derive(Debug)]
+pub struct Pipeline<'c, T> {
+    conn: &'c crate::Connection,
+    queue: std::collections::VecDeque<Entry<T>>,
+    current: Option<crate::PQResult>,
+}
+
+impl<'c, T> Pipeline<'c, T> {
+    /**
+     * Enters pipeline mode on `conn`.
+     */
+    pub fn new(conn: &'c crate::Connection) -> crate::errors::Result<Self> {
+        enter(conn)?;
+
+        Ok(Self {
+            conn,
+            queue: std::collections::VecDeque::new(),
+            current: None,
+        })
+    }
+
+    /**
+     * Queues a command tagged with `tag`.
+     *
+     * See `libpq::Connection::send_query_params`.
+     */
+    pub fn send_query_params(
+        &mut self,
+        tag: T,
+        command: &str,
+        param_types: &[crate::Oid],
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result {
+        self.conn.send_query_params(
+            command,
+            param_types,
+            param_values,
+            param_formats,
+            result_format,
+        )?;
+        self.queue.push_back(Entry::Command {
+            tag,
+            deadline: None,
+        });
+
+        Ok(())
+    }
+
+    /**
+     * Queues a request to create a prepared statement tagged with `tag`.
+     *
+     * See `libpq::Connection::send_prepare`.
+     */
+    pub fn send_prepare(
+        &mut self,
+        tag: T,
+        name: Option<&str>,
+        query: &str,
+        param_types: &[crate::Oid],
+    ) -> crate::errors::Result {
+        self.conn.send_prepare(name, query, param_types)?;
+        self.queue.push_back(Entry::Command {
+            tag,
+            deadline: None,
+        });
+
+        Ok(())
+    }
+
+    /**
+     * Queues the execution of a prepared statement tagged with `tag`.
+     *
+     * See `libpq::Connection::send_query_prepared`.
+     */
+    pub fn send_query_prepared(
+        &mut self,
+        tag: T,
+        name: Option<&str>,
+        param_values: &[Option<&[u8]>],
+        param_formats: &[crate::Format],
+        result_format: crate::Format,
+    ) -> crate::errors::Result {
+        self.conn
+            .send_query_prepared(name, param_values, param_formats, result_format)?;
+        self.queue.push_back(Entry::Command {
+            tag,
+            deadline: None,
+        });
+
+        Ok(())
+    }
+
+    /**
+     * Marks a synchronization point, see [`sync`].
+     */
+    pub fn sync(&mut self) -> crate::errors::Result {
+        sync(self.conn)?;
+        self.queue.push_back(Entry::Sync);
+
+        Ok(())
+    }
+
+    /**
+     * Sets the time before which the last queued command must complete.
+     *
+     * Deadlines are enforced by [`Pipeline::abort_expired`].
+     */
+    pub fn set_deadline(&mut self, deadline: std::time::Instant) -> crate::errors::Result {
+        match self
+            .queue
+            .iter_mut()
+            .rev()
+            .find(|x| matches!(x, Entry::Command { .. }))
+        {
+            Some(Entry::Command { deadline: d, .. }) => {
+                *d = Some(deadline);
+                Ok(())
+            }
+            _ => Err(crate::errors::Error::Backend(
+                "No command queued in the pipeline".to_string(),
+            )),
+        }
+    }
+
+    /**
+     * The earliest deadline of the pending commands, to bound the wait for their results.
+     */
+    pub fn next_deadline(&self) -> Option<std::time::Instant> {
+        self.queue
+            .iter()
+            .filter_map(|x| match x {
+                Entry::Command { deadline, .. } => *deadline,
+                Entry::Sync => None,
+            })
+            .min()
+    }
+
+    /**
+     * Aborts the pipeline if a pending command missed its deadline.
+     *
+     * The running command is canceled, the pipeline synchronized and all the pending results
+     * discarded. Returns the tags of the commands that were outstanding, an empty vector if no
+     * deadline is exceeded. The pipeline can be used again afterwards.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * let mut pipeline = libpq::pipeline::Pipeline::new(&conn)?;
+     * pipeline.send_query_params("slow", "SELECT pg_sleep(10)", &[], &[], &[], libpq::Format::Text)?;
+     * pipeline.set_deadline(std::time::Instant::now() + std::time::Duration::from_secs(1))?;
+     * pipeline.sync()?;
+     *
+     * while pipeline.pending() > 0 {
+     *     for (tag, result) in pipeline.poll_completed()? {
+     *         // …
+     *     }
+     *
+     *     let outstanding = pipeline.abort_expired()?;
+     *     if !outstanding.is_empty() {
+     *         eprintln!("Timeout: {outstanding:?}");
+     *     }
+     *     # std::thread::sleep(std::time::Duration::from_millis(10));
+     * }
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn abort_expired(&mut self) -> crate::errors::Result<Vec<T>> {
+        match self.next_deadline() {
+            Some(deadline) if deadline <= std::time::Instant::now() => (),
+            _ => return Ok(Vec::new()),
+        }
+
+        log::debug!("Pipeline deadline exceeded, aborting");
+
+        if matches!(self.queue.back(), Some(Entry::Command { .. })) {
+            self.sync()?;
+        }
+
+        self.conn.flush()?;
+        self.conn.cancel().request()?;
+
+        let mut outstanding = Vec::new();
+
+        for entry in std::mem::take(&mut self.queue) {
+            if let Entry::Command { tag, .. } = entry {
+                outstanding.push(tag);
+            }
+
+            while let Some(result) = self.conn.result() {
+                if result.status() == crate::Status::PipelineSync {
+                    break;
+                }
+            }
+        }
+
+        self.current = None;
+
+        Ok(outstanding)
+    }
+
+    /**
+     * Number of commands whose results haven't been returned yet.
+     */
+    pub fn pending(&self) -> usize {
+        self.queue
+            .iter()
+            .filter(|x| matches!(x, Entry::Command { .. }))
+            .count()
+    }
+
+    /**
+     * Returns the commands completed so far, without blocking.
+     *
+     * Commands of an aborted pipeline are completed with a `libpq::Status::PipelineAborted`
+     * result.
+     */
+    pub fn poll_completed(&mut self) -> crate::errors::Result<Vec<(T, crate::PQResult)>> {
+        self.conn.consume_input()?;
+
+        Ok(self.completed(false))
+    }
+
+    /**
+     * Waits for all the queued commands to complete.
+     *
+     * The pipeline must have been synchronized (or flushed) after the last command, otherwise
+     * this method blocks forever.
+     */
+    pub fn wait_completed(&mut self) -> Vec<(T, crate::PQResult)> {
+        self.completed(true)
+    }
+
+    fn completed(&mut self, block: bool) -> Vec<(T, crate::PQResult)> {
+        let mut completed = Vec::new();
+
+        while !self.queue.is_empty() && (block || !self.conn.is_busy()) {
+            match self.conn.result() {
+                Some(result) if result.status() == crate::Status::PipelineSync => {
+                    if let Some(position) = self.queue.iter().position(|x| matches!(x, Entry::Sync))
+                    {
+                        self.queue.remove(position);
+                    }
+                }
+                Some(result) => self.current = Some(result),
+                None => {
+                    if !matches!(self.queue.front(), Some(Entry::Command { .. })) {
+                        break;
+                    }
+
+                    if let (Some(Entry::Command { tag, .. }), Some(result)) =
+                        (self.queue.pop_front(), self.current.take())
+                    {
+                        completed.push((tag, result));
+                    }
+                }
+            }
+        }
+
+        completed
+    }
+
+    /**
+     * Exits pipeline mode, see [`exit`].
+     */
+    pub fn exit(self) -> crate::errors::Result {
+        exit(self.conn)
+    }
+}
+
 #[cfg(test)]
 mod test {
     #[test]
@@ -175,4 +468,122 @@ mod test {
         crate::pipeline::enter(&conn).unwrap();
         assert_eq!(crate::pipeline::send_sync(&conn), Ok(()));
     }
+
+    #[test]
+    fn demultiplex() {
+        let conn = crate::test::new_conn();
+        let mut pipeline = crate::pipeline::Pipeline::new(&conn).unwrap();
+
+        for (tag, query) in [
+            ("one", "SELECT 1"),
+            ("error", "SELECT 1/0"),
+            ("two", "SELECT 2"),
+        ] {
+            pipeline
+                .send_query_params(tag, query, &[], &[], &[], crate::Format::Text)
+                .unwrap();
+        }
+        pipeline.sync().unwrap();
+        pipeline
+            .send_query_params("three", "SELECT 3", &[], &[], &[], crate::Format::Text)
+            .unwrap();
+        pipeline.sync().unwrap();
+        assert_eq!(pipeline.pending(), 4);
+
+        let completed = pipeline.wait_completed();
+        let completed = completed
+            .iter()
+            .map(|(tag, result)| (*tag, result.status()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            completed,
+            [
+                ("one", crate::Status::TuplesOk),
+                ("error", crate::Status::FatalError),
+                ("two", crate::Status::PipelineAborted),
+                ("three", crate::Status::TuplesOk),
+            ]
+        );
+        assert_eq!(pipeline.pending(), 0);
+        assert!(pipeline.exit().is_ok());
+    }
+
+    #[test]
+    fn poll_completed() {
+        let conn = crate::test::new_conn();
+        let mut pipeline = crate::pipeline::Pipeline::new(&conn).unwrap();
+
+        pipeline
+            .send_query_params(
+                42,
+                "SELECT pg_sleep(0.1)",
+                &[],
+                &[],
+                &[],
+                crate::Format::Text,
+            )
+            .unwrap();
+        pipeline.sync().unwrap();
+
+        let mut completed = Vec::new();
+        while pipeline.pending() > 0 {
+            completed.extend(pipeline.poll_completed().unwrap());
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].0, 42);
+    }
+
+    #[test]
+    fn deadline() {
+        let conn = crate::test::new_conn();
+        let mut pipeline = crate::pipeline::Pipeline::new(&conn).unwrap();
+
+        assert!(pipeline.set_deadline(std::time::Instant::now()).is_err());
+        assert_eq!(pipeline.abort_expired(), Ok(Vec::new()));
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(100);
+
+        for (tag, query) in [("fast", "SELECT 1"), ("slow", "SELECT pg_sleep(10)")] {
+            pipeline
+                .send_query_params(tag, query, &[], &[], &[], crate::Format::Text)
+                .unwrap();
+            // The server only sends the results when asked to.
+            crate::pipeline::flush_request(&conn).unwrap();
+        }
+        pipeline.set_deadline(deadline).unwrap();
+        pipeline
+            .send_query_params("next", "SELECT 2", &[], &[], &[], crate::Format::Text)
+            .unwrap();
+        conn.flush().unwrap();
+        assert_eq!(pipeline.next_deadline(), Some(deadline));
+
+        let mut completed = Vec::new();
+        let outstanding = loop {
+            completed.extend(pipeline.poll_completed().unwrap());
+
+            let outstanding = pipeline.abort_expired().unwrap();
+            if !outstanding.is_empty() {
+                break outstanding;
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        };
+
+        assert!(deadline.elapsed() < std::time::Duration::from_secs(5));
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].0, "fast");
+        assert_eq!(outstanding, ["slow", "next"]);
+        assert_eq!(pipeline.pending(), 0);
+
+        pipeline
+            .send_query_params("after", "SELECT 3", &[], &[], &[], crate::Format::Text)
+            .unwrap();
+        pipeline.sync().unwrap();
+        let completed = pipeline.wait_completed();
+        assert_eq!(completed[0].0, "after");
+        assert_eq!(completed[0].1.status(), crate::Status::TuplesOk);
+    }
 }
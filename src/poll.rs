@@ -7,6 +7,68 @@ pub enum Status {
     Active,
 }
 
+impl Status {
+    /**
+     * Socket readiness to wait for before polling again, `None` once the polling is over.
+     */
+    pub fn interest(&self) -> Option<Interest> {
+        match self {
+            Self::Reading => Some(Interest::Read),
+            Self::Writing => Some(Interest::Write),
+            Self::Failed | Self::Ok | Self::Active => None,
+        }
+    }
+}
+
+/**
+ * Direction to wait on the connection socket.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Interest {
+    Read,
+    Write,
+}
+
+/**
+ * Result of a polling step, with what an event loop needs to schedule the next one.
+ *
+ * See `libpq::Connection::poll_hint` and `libpq::Connection::reset_poll_hint`.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Hint {
+    pub status: Status,
+    /**
+     * The socket to wait on. It may change between polling steps, when libpq tries another
+     * host: the event loop must register it again.
+     */
+    pub socket: Option<i32>,
+}
+
+impl Hint {
+    pub fn interest(&self) -> Option<Interest> {
+        self.status.interest()
+    }
+
+    /**
+     * Waits until the socket is ready for the next polling step, or until `timeout` expires.
+     *
+     * Returns `false` on timeout, `true` if there is nothing to wait for.
+     */
+    #[cfg(unix)]
+    pub fn wait(&self, timeout: Option<std::time::Duration>) -> crate::errors::Result<bool> {
+        let (Some(socket), Some(interest)) = (self.socket, self.interest()) else {
+            return Ok(true);
+        };
+
+        crate::connection::wait_socket(
+            socket,
+            interest == Interest::Read,
+            interest == Interest::Write,
+            timeout,
+        )
+    }
+}
+
 #[doc(hidden)]
 impl From<pq_sys::PostgresPollingStatusType> for Status {
     fn from(status: pq_sys::PostgresPollingStatusType) -> Self {
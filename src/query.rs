@@ -0,0 +1,179 @@
+/**
+ * Builds a query from SQL fragments and parameters, numbering the `$n` placeholders.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let name = Some("foo");
+ * let ids = [1, 2, 3];
+ *
+ * let mut query = libpq::query::QueryBuilder::new("SELECT * FROM item WHERE true");
+ * if let Some(name) = name {
+ *     query.push(" AND name = ").push_bind(&name);
+ * }
+ * query.push(" AND id IN (").push_bind_list(&[&ids[0], &ids[1], &ids[2]]).push(")");
+ *
+ * let result = query.exec(&conn)?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Clone, Debug, Default)]
+pub struct QueryBuilder {
+    sql: String,
+    params: crate::types::Params,
+}
+
+impl QueryBuilder {
+    pub fn new(sql: &str) -> Self {
+        Self {
+            sql: sql.to_string(),
+            params: crate::types::Params::default(),
+        }
+    }
+
+    /**
+     * Appends a raw SQL fragment.
+     *
+     * The fragment is not escaped, never use it for user input: see
+     * [`QueryBuilder::push_bind`].
+     */
+    pub fn push(&mut self, sql: &str) -> &mut Self {
+        self.sql.push_str(sql);
+        self
+    }
+
+    /**
+     * Appends a placeholder for `value`.
+     */
+    pub fn push_bind(&mut self, value: &dyn crate::types::ToParam) -> &mut Self {
+        self.params.push(value);
+        self.sql.push_str(&format!("${}", self.params.len()));
+        self
+    }
+
+    /**
+     * Appends a comma-separated list of placeholders, for `IN (…)` or `VALUES (…)` clauses.
+     */
+    pub fn push_bind_list(&mut self, values: &[&dyn crate::types::ToParam]) -> &mut Self {
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.sql.push_str(", ");
+            }
+
+            self.push_bind(*value);
+        }
+
+        self
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn param_types(&self) -> &[crate::Oid] {
+        &self.params.types
+    }
+
+    /**
+     * The parameter values, the text ones without the NUL terminator sent to libpq.
+     */
+    pub fn param_values(&self) -> Vec<Option<&[u8]>> {
+        self.params
+            .values()
+            .into_iter()
+            .zip(&self.params.formats)
+            .map(|(value, format)| match format {
+                crate::Format::Text => value.map(|x| x.strip_suffix(b"\0").unwrap_or(x)),
+                crate::Format::Binary => value,
+            })
+            .collect()
+    }
+
+    pub fn param_formats(&self) -> &[crate::Format] {
+        &self.params.formats
+    }
+
+    /**
+     * See `libpq::Connection::exec_params`, the connection default parameter types apply.
+     *
+     * Returns an `Error::NulError` error, without sending the query, if a text value contains
+     * a NUL byte.
+     */
+    pub fn exec(&self, conn: &crate::Connection) -> crate::errors::Result<crate::PQResult> {
+        self.params.check()?;
+
+        Ok(conn.exec_params(
+            &self.sql,
+            &conn.param_types(&self.params),
+            &self.params.values(),
+            self.param_formats(),
+            crate::Format::Text,
+        ))
+    }
+
+    /**
+     * See `libpq::Connection::send_query_params`, the connection default parameter types apply.
+     */
+    pub fn send(&self, conn: &crate::Connection) -> crate::errors::Result {
+        self.params.check()?;
+
+        conn.send_query_params(
+            &self.sql,
+            &conn.param_types(&self.params),
+            &self.params.values(),
+            self.param_formats(),
+            crate::Format::Text,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn numbering() {
+        let mut query = super::QueryBuilder::new("SELECT *");
+        query.push(" WHERE a = ").push_bind(&1_i32);
+        query
+            .push(" AND b IN (")
+            .push_bind_list(&[&"x", &"y"])
+            .push(")");
+        query.push_bind_list(&[]);
+
+        assert_eq!(query.sql(), "SELECT * WHERE a = $1 AND b IN ($2, $3)");
+        assert_eq!(query.param_types(), &[crate::types::INT4.oid, 0, 0][..]);
+        assert_eq!(
+            query.param_values(),
+            vec![
+                Some(&1_i32.to_be_bytes()[..]),
+                Some(&b"x"[..]),
+                Some(&b"y"[..])
+            ]
+        );
+    }
+
+    #[test]
+    fn exec() {
+        let conn = crate::test::new_conn();
+
+        let mut query =
+            super::QueryBuilder::new("SELECT x FROM generate_series(1, 10) x WHERE true");
+        query.push(" AND x > ").push_bind(&3_i32);
+        query
+            .push(" AND x::text IN (")
+            .push_bind_list(&[&"2", &"4", &"5"])
+            .push(")");
+        query.push(" ORDER BY x");
+
+        let result = query.exec(&conn).unwrap();
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+        assert_eq!(result.ntuples(), 2);
+        assert_eq!(result.get::<i32>(0, 0).unwrap(), 4);
+        assert_eq!(result.get::<i32>(1, 0).unwrap(), 5);
+
+        let mut query = super::QueryBuilder::new("SELECT ");
+        query.push_bind(&"a\0b");
+        assert!(matches!(
+            query.exec(&conn),
+            Err(crate::errors::Error::NulError(_))
+        ));
+    }
+}
@@ -0,0 +1,100 @@
+/**
+ * A parsed command tag, like `INSERT 0 5` or `CREATE TABLE`.
+ *
+ * See [CommandComplete](https://www.postgresql.org/docs/current/protocol-message-formats.html#PROTOCOL-MESSAGE-FORMATS-COMMANDCOMPLETE).
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommandTag {
+    /** The command name, like `INSERT` or `CREATE TABLE`. */
+    pub verb: String,
+    /** The OID of the inserted row, only for `INSERT` (always 0 since PostgreSQL 12). */
+    pub oid: Option<crate::Oid>,
+    /** The number of rows processed, for commands reporting it. */
+    pub rows: Option<u64>,
+}
+
+impl std::str::FromStr for CommandTag {
+    type Err = crate::errors::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace().collect::<Vec<_>>();
+
+        let rows = match words.last() {
+            Some(x) if x.bytes().all(|x| x.is_ascii_digit()) => Some(x.parse()?),
+            _ => None,
+        };
+        if rows.is_some() {
+            words.pop();
+        }
+
+        let oid = if rows.is_some() && words.len() == 2 && words[0] == "INSERT" {
+            Some(words.pop().unwrap_or_default().parse()?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            verb: words.join(" "),
+            oid,
+            rows,
+        })
+    }
+}
+
+impl std::fmt::Display for CommandTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.verb)?;
+
+        if let Some(oid) = self.oid {
+            write!(f, " {oid}")?;
+        }
+
+        if let Some(rows) = self.rows {
+            write!(f, " {rows}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn parse() {
+        let tests = [
+            ("INSERT 0 5", "INSERT", Some(0), Some(5)),
+            ("UPDATE 3", "UPDATE", None, Some(3)),
+            ("SELECT 10", "SELECT", None, Some(10)),
+            ("COPY 42", "COPY", None, Some(42)),
+            ("CREATE TABLE", "CREATE TABLE", None, None),
+            ("BEGIN", "BEGIN", None, None),
+        ];
+
+        for (tag, verb, oid, rows) in tests {
+            let command_tag = tag.parse::<crate::result::CommandTag>().unwrap();
+
+            assert_eq!(command_tag.verb, verb);
+            assert_eq!(command_tag.oid, oid);
+            assert_eq!(command_tag.rows, rows);
+            assert_eq!(command_tag.to_string(), tag);
+        }
+    }
+
+    #[test]
+    fn command_tag() {
+        let conn = crate::test::new_conn();
+
+        let result = conn.exec("SELECT generate_series(1, 3)");
+        assert_eq!(
+            result.command_tag(),
+            Ok(Some(crate::result::CommandTag {
+                verb: "SELECT".to_string(),
+                oid: None,
+                rows: Some(3),
+            }))
+        );
+
+        let result = conn.exec("");
+        assert_eq!(result.command_tag(), Ok(None));
+    }
+}
@@ -55,3 +55,28 @@ impl From<ErrorField> for i32 {
         unsafe { std::mem::transmute(error_field) }
     }
 }
+
+/**
+ * All the fields of an error report, see `libpq::PQResult::error_fields`.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ErrorFields {
+    pub severity: Option<String>,
+    pub severity_nonlocalized: Option<String>,
+    pub sqlstate: Option<String>,
+    pub message_primary: Option<String>,
+    pub message_detail: Option<String>,
+    pub message_hint: Option<String>,
+    pub statement_position: Option<usize>,
+    pub internal_position: Option<usize>,
+    pub internal_query: Option<String>,
+    pub context: Option<String>,
+    pub schema_name: Option<String>,
+    pub table_name: Option<String>,
+    pub column_name: Option<String>,
+    pub datatype_name: Option<String>,
+    pub constraint_name: Option<String>,
+    pub source_file: Option<String>,
+    pub source_line: Option<usize>,
+    pub source_function: Option<String>,
+}
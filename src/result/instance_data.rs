@@ -0,0 +1,28 @@
+/**
+ * Data attached to a result with `libpq::PQResult::set_instance_data`.
+ */
+pub(crate) type InstanceData = Box<dyn std::any::Any + Send + Sync>;
+
+/**
+ * Event procedure owning the data attached to the results, registered by
+ * `libpq::Connection::register_result_data`.
+ *
+ * See [Event System](https://www.postgresql.org/docs/current/libpq-events.html).
+ */
+pub(crate) unsafe extern "C" fn event_proc(
+    id: pq_sys::PGEventId,
+    info: *mut std::ffi::c_void,
+    _: *mut std::ffi::c_void,
+) -> std::os::raw::c_int {
+    if id == pq_sys::PGEventId::PGEVT_RESULTDESTROY {
+        let result = (*(info as *mut pq_sys::PGEventResultDestroy)).result;
+        let data = pq_sys::PQresultInstanceData(result, Some(event_proc));
+
+        if !data.is_null() {
+            drop(Box::from_raw(data as *mut InstanceData));
+        }
+    }
+
+    // A copied result starts without data, nothing else to do.
+    1
+}
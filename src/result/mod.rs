@@ -1,8 +1,14 @@
 mod attribute;
+mod command_tag;
 mod error_field;
+mod instance_data;
+mod row;
 
 pub use attribute::*;
+pub use command_tag::*;
 pub use error_field::*;
+pub(crate) use instance_data::*;
+pub use row::*;
 
 use std::os::raw;
 
@@ -33,6 +39,24 @@ impl PQResult {
         unsafe { pq_sys::PQresultStatus(self.into()) }.into()
     }
 
+    /**
+     * Returns `true` if the command failed, i.e. its status is `libpq::Status::FatalError` or
+     * `libpq::Status::BadResponse`.
+     */
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self.status(),
+            crate::Status::FatalError | crate::Status::BadResponse
+        )
+    }
+
+    /**
+     * Returns `true` if the query string sent to the server was empty.
+     */
+    pub fn is_empty_query(&self) -> bool {
+        self.status() == crate::Status::EmptyQuery
+    }
+
     /**
      * Returns the error message associated with the command, or an empty string if there was no error.
      *
@@ -43,14 +67,16 @@ impl PQResult {
     }
 
     /**
-     * Returns a reformatted version of the error message associated with a `libpq::Result` object.
+     * Returns an individual field of an error report.
+     *
+     * The value is owned by the result, it lives as long as it.
      *
      * See [PQresultErrorField](https://www.postgresql.org/docs/current/libpq-exec.html#LIBPQ-PQRESULTERRORFIELD).
      */
     pub fn error_field(
         &self,
         field: crate::result::ErrorField,
-    ) -> crate::errors::Result<Option<&'static str>> {
+    ) -> crate::errors::Result<Option<&str>> {
         unsafe {
             let ptr = pq_sys::PQresultErrorField(self.into(), field.into());
 
@@ -62,6 +88,41 @@ impl PQResult {
         }
     }
 
+    /**
+     * Returns all the fields of the error report, as owned values.
+     */
+    pub fn error_fields(&self) -> crate::errors::Result<crate::result::ErrorFields> {
+        use crate::result::ErrorField;
+
+        let string = |field| -> crate::errors::Result<Option<String>> {
+            Ok(self.error_field(field)?.map(ToString::to_string))
+        };
+        let number = |field| -> crate::errors::Result<Option<usize>> {
+            Ok(self.error_field(field)?.map(str::parse).transpose()?)
+        };
+
+        Ok(crate::result::ErrorFields {
+            severity: string(ErrorField::Severity)?,
+            severity_nonlocalized: string(ErrorField::SeverityNonlocalized)?,
+            sqlstate: string(ErrorField::Sqlstate)?,
+            message_primary: string(ErrorField::MessagePrimary)?,
+            message_detail: string(ErrorField::MessageDetail)?,
+            message_hint: string(ErrorField::MessageHint)?,
+            statement_position: number(ErrorField::StatementPosition)?,
+            internal_position: number(ErrorField::InternalPosition)?,
+            internal_query: string(ErrorField::InternalQuery)?,
+            context: string(ErrorField::Context)?,
+            schema_name: string(ErrorField::SchemaName)?,
+            table_name: string(ErrorField::TableName)?,
+            column_name: string(ErrorField::ColumnName)?,
+            datatype_name: string(ErrorField::DatatypeName)?,
+            constraint_name: string(ErrorField::ConstraintName)?,
+            source_file: string(ErrorField::SourceFile)?,
+            source_line: number(ErrorField::SourceLine)?,
+            source_function: string(ErrorField::SourceFunction)?,
+        })
+    }
+
     /**
      * Returns the number of rows (tuples) in the query result.
      *
@@ -145,6 +206,15 @@ impl PQResult {
         unsafe { pq_sys::PQfformat(self.into(), column as i32) }.into()
     }
 
+    /**
+     * Returns the format of each column.
+     *
+     * See `libpq::Format::for_types` to choose the result format of a query.
+     */
+    pub fn field_formats(&self) -> Vec<crate::Format> {
+        (0..self.nfields()).map(|x| self.field_format(x)).collect()
+    }
+
     /**
      * Returns the data type associated with the given column number.
      *
@@ -216,6 +286,35 @@ impl PQResult {
         }
     }
 
+    /**
+     * Returns an iterator over the rows of the result.
+     */
+    pub fn rows(&self) -> crate::result::Rows<'_> {
+        crate::result::Rows::new(self)
+    }
+
+    /**
+     * Decodes a single field value into a Rust type, according to the field type and format.
+     *
+     * A binary value `T` can't decode is decoded from its text representation, when
+     * `libpq::types::codec::decode` supports its type: a binary `numeric` can be read as a
+     * `String` for example.
+     *
+     * Returns an `Error::UnexpectedNull` error for null values, unless `T` is an `Option`.
+     */
+    pub fn get<T: crate::types::FromSql>(
+        &self,
+        row: usize,
+        column: usize,
+    ) -> crate::errors::Result<T> {
+        let ty = self.field_type(column);
+
+        match self.value(row, column) {
+            Some(raw) => crate::types::decode(ty, self.field_format(column), raw),
+            None => T::from_null(ty),
+        }
+    }
+
     /**
      * Tests a field for a null value.
      *
@@ -265,7 +364,28 @@ impl PQResult {
      */
     #[cfg(unix)]
     pub fn print(&self, output: &dyn std::os::unix::io::AsRawFd, option: &crate::print::Options) {
-        let c_mode = crate::ffi::to_cstr("w");
+        self.print_stream(crate::ffi::fdopen(output.as_raw_fd()), option);
+    }
+
+    /**
+     * Prints out all the rows and, optionally, the column names to the specified output stream.
+     *
+     * See [PQprint](https://www.postgresql.org/docs/current/libpq-exec.html#LIBPQ-PQPRINT).
+     */
+    #[cfg(windows)]
+    pub fn print(
+        &self,
+        output: &dyn std::os::windows::io::AsRawHandle,
+        option: &crate::print::Options,
+    ) {
+        self.print_stream(crate::ffi::fdopen(output.as_raw_handle()), option);
+    }
+
+    fn print_stream(&self, stream: *mut libc::FILE, option: &crate::print::Options) {
+        if stream.is_null() {
+            log::error!("Unable to open the output stream");
+            return;
+        }
 
         let (_c_field_name, ptr_field_name) = crate::ffi::vec_to_nta(&option.field_name);
 
@@ -287,9 +407,8 @@ impl PQResult {
         };
 
         unsafe {
-            let stream = libc::fdopen(output.as_raw_fd(), c_mode.as_ptr());
-
             pq_sys::PQprint(stream as *mut _, self.into(), &c_option);
+            libc::fclose(stream);
         }
     }
 
@@ -313,6 +432,16 @@ impl PQResult {
         Ok(ntuples.parse()?)
     }
 
+    /**
+     * Returns the parsed command status tag.
+     */
+    pub fn command_tag(&self) -> crate::errors::Result<Option<crate::result::CommandTag>> {
+        match self.cmd_status()? {
+            Some(tag) if !tag.is_empty() => Ok(Some(tag.parse()?)),
+            _ => Ok(None),
+        }
+    }
+
     /**
      * Returns the OID of the inserted row.
      *
@@ -378,6 +507,60 @@ impl PQResult {
         }
     }
 
+    /**
+     * Returns the attributes of the columns, as expected by `libpq::PQResult::set_attrs`.
+     */
+    pub fn attributes(&self) -> crate::errors::Result<Vec<crate::result::Attribute>> {
+        let mut attributes = Vec::with_capacity(self.nfields());
+
+        for column in 0..self.nfields() {
+            attributes.push(crate::result::Attribute {
+                name: self.field_name(column)?.unwrap_or_default(),
+                tableid: self.field_table(column).unwrap_or(crate::oid::INVALID),
+                columnid: self.field_tablecol(column) as i32,
+                format: self.field_format(column) as i32,
+                typid: self.field_type(column),
+                typlen: unsafe { pq_sys::PQfsize(self.into(), column as i32) },
+                atttypmod: unsafe { pq_sys::PQfmod(self.into(), column as i32) },
+            });
+        }
+
+        Ok(attributes)
+    }
+
+    /**
+     * Sets the attributes of this result to the ones of `other`.
+     *
+     * To keep some columns only, filter `libpq::PQResult::attributes` and call
+     * `libpq::PQResult::set_attrs`.
+     */
+    pub fn copy_attrs_from(&mut self, other: &Self) -> crate::errors::Result {
+        let attributes = other.attributes()?;
+
+        self.set_attrs(&attributes.iter().collect::<Vec<_>>())
+    }
+
+    /**
+     * Appends a copy of `row`, which must have as many columns as this result.
+     */
+    pub fn append_row(&mut self, row: &crate::result::Row<'_>) -> crate::errors::Result {
+        if row.len() != self.nfields() {
+            return Err(crate::errors::Error::Backend(format!(
+                "The row has {} columns, the result {}",
+                row.len(),
+                self.nfields()
+            )));
+        }
+
+        let tuple = self.ntuples();
+
+        for column in 0..row.len() {
+            self.set_value_bytes(tuple, column, row.value(column))?;
+        }
+
+        Ok(())
+    }
+
     /**
      * Sets a tuple field value of a `Result` object.
      *
@@ -389,11 +572,26 @@ impl PQResult {
         field: usize,
         value: Option<&str>,
     ) -> crate::errors::Result {
-        let (v, len) = if let Some(v) = value {
-            let cstring = std::ffi::CString::new(v)?;
-            (cstring.into_raw(), v.len() as i32)
-        } else {
-            (std::ptr::null_mut(), -1)
+        self.set_value_bytes(tuple, field, value.map(str::as_bytes))
+    }
+
+    /**
+     * Sets a tuple field value of a `Result` object, for binary values.
+     *
+     * The value is copied with its length, so it may contain NUL bytes.
+     *
+     * See [PQsetvalue](https://www.postgresql.org/docs/current/libpq-misc.html#LIBPQ-PQSETVALUE).
+     */
+    pub fn set_value_bytes(
+        &mut self,
+        tuple: usize,
+        field: usize,
+        value: Option<&[u8]>,
+    ) -> crate::errors::Result {
+        let (v, len) = match value {
+            // libpq copies the value, without modifying it.
+            Some(v) => (v.as_ptr() as *mut raw::c_char, v.len() as i32),
+            None => (std::ptr::null_mut(), -1),
         };
 
         let success =
@@ -439,7 +637,6 @@ impl PQResult {
     /**
      * Really old printing routines.
      */
-    #[cfg(unix)]
     pub fn display_tuples(
         &self,
         file: std::fs::File,
@@ -448,13 +645,14 @@ impl PQResult {
         print_header: bool,
         quiet: bool,
     ) {
-        use std::os::unix::io::IntoRawFd;
+        let fp = crate::ffi::into_stream(file);
 
-        let c_mode = crate::ffi::to_cstr("w");
+        if fp.is_null() {
+            log::error!("Unable to open the output file");
+            return;
+        }
 
         unsafe {
-            let fp = libc::fdopen(file.into_raw_fd(), c_mode.as_ptr());
-
             let c_sep = field_sep.map(crate::ffi::to_cstr);
             let sep = if let Some(c_sep) = c_sep {
                 c_sep.as_ptr()
@@ -470,8 +668,87 @@ impl PQResult {
                 print_header as i32,
                 quiet as i32,
             );
+            libc::fclose(fp);
         }
     }
+
+    /**
+     * Attaches `data` to the result, replacing the previous one. It's dropped with the result.
+     *
+     * The connection must have called `libpq::Connection::register_result_data` before creating
+     * the result.
+     *
+     * The previous data is dropped, so the result is borrowed mutably: it can't be replaced while
+     * borrowed from `libpq::PQResult::instance_data`.
+     *
+     * ```compile_fail
+     * # let conn = libpq::Connection::new("")?;
+     * let mut result = conn.exec("SELECT 1");
+     * result.set_instance_data(1_i32)?;
+     *
+     * let data = result.instance_data::<i32>();
+     * result.set_instance_data(2_i32)?;
+     * println!("{data:?}");
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     *
+     * See
+     * [PQresultSetInstanceData](https://www.postgresql.org/docs/current/libpq-events.html#LIBPQ-PQRESULTSETINSTANCEDATA).
+     */
+    pub fn set_instance_data<T: std::any::Any + Send + Sync>(
+        &mut self,
+        data: T,
+    ) -> crate::errors::Result {
+        let previous = unsafe { pq_sys::PQresultInstanceData(self.result, Some(event_proc)) };
+        let data: InstanceData = Box::new(data);
+        let ptr = Box::into_raw(Box::new(data));
+
+        let success = unsafe {
+            pq_sys::PQresultSetInstanceData(self.result, Some(event_proc), ptr as *mut _)
+        };
+
+        if success != 1 {
+            drop(unsafe { Box::from_raw(ptr) });
+
+            return Err(crate::errors::Error::Backend(
+                "The result data event procedure is not registered".to_string(),
+            ));
+        }
+
+        if !previous.is_null() {
+            drop(unsafe { Box::from_raw(previous as *mut InstanceData) });
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Returns the data attached by `libpq::PQResult::set_instance_data`, `None` if there is none
+     * or if it isn't a `T`.
+     *
+     * See
+     * [PQresultInstanceData](https://www.postgresql.org/docs/current/libpq-events.html#LIBPQ-PQRESULTINSTANCEDATA).
+     */
+    pub fn instance_data<T: std::any::Any>(&self) -> Option<&T> {
+        let ptr = unsafe { pq_sys::PQresultInstanceData(self.into(), Some(event_proc)) };
+
+        if ptr.is_null() {
+            return None;
+        }
+
+        unsafe { &*(ptr as *const InstanceData) }.downcast_ref()
+    }
+
+    /**
+     * Calls `f` with the underlying `PGresult`, for native code post-processing the result.
+     *
+     * # Safety
+     *
+     * `f` must neither free the result, nor keep the pointer once it returns.
+     */
+    pub unsafe fn process_with<R>(&mut self, f: impl FnOnce(*mut pq_sys::PGresult) -> R) -> R {
+        f(self.result)
+    }
 }
 
 unsafe impl Send for PQResult {}
@@ -527,3 +804,192 @@ impl std::fmt::Debug for PQResult {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn field_formats() {
+        let conn = crate::test::new_conn();
+
+        let result = conn.exec_params("SELECT 1, now()", &[], &[], &[], crate::Format::Binary);
+        assert_eq!(
+            result.field_formats(),
+            [crate::Format::Binary, crate::Format::Binary]
+        );
+
+        let types = (0..result.nfields())
+            .map(|x| result.field_type(x))
+            .collect::<Vec<_>>();
+        assert_eq!(crate::Format::for_types(&types), crate::Format::Text);
+    }
+
+    #[test]
+    fn print() {
+        use std::io::{Read, Seek, Write};
+
+        let path = std::env::temp_dir().join(format!("libpq-print-{}", std::process::id()));
+        let mut file = std::fs::File::options()
+            .create(true)
+            .truncate(true)
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+
+        let conn = crate::test::new_conn();
+        let result = conn.exec("SELECT 'foo' AS name");
+        let options = crate::print::Options {
+            header: true,
+            align: false,
+            standard: false,
+            html3: false,
+            expanded: false,
+            pager: false,
+            field_sep: "|".to_string(),
+            table_opt: String::new(),
+            caption: String::new(),
+            field_name: Vec::new(),
+        };
+
+        result.print(&file, &options);
+        result.print(&file, &options);
+
+        // The borrowed file is still open after the prints.
+        file.write_all(b"end\n").unwrap();
+        file.rewind().unwrap();
+
+        let mut output = String::new();
+        file.read_to_string(&mut output).unwrap();
+        drop(file);
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(output.matches("foo").count(), 2);
+        assert!(output.ends_with("end\n"));
+    }
+
+    #[test]
+    fn set_value() {
+        let conn = crate::test::new_conn();
+        let mut result = crate::PQResult::new(&conn, crate::Status::TuplesOk);
+        let attribute = crate::result::Attribute {
+            name: "data".to_string(),
+            tableid: 0,
+            columnid: 0,
+            format: crate::Format::Binary as i32,
+            typid: crate::types::BYTEA.oid,
+            typlen: -1,
+            atttypmod: -1,
+        };
+        result.set_attrs(&[&attribute]).unwrap();
+
+        result.set_value_bytes(0, 0, Some(b"\0\xFF\0")).unwrap();
+        result.set_value_bytes(1, 0, None).unwrap();
+        result.set_value(2, 0, Some("text")).unwrap();
+        result.set_value_bytes(3, 0, Some(b"")).unwrap();
+
+        assert_eq!(result.ntuples(), 4);
+        assert_eq!(result.value(0, 0), Some(&b"\0\xFF\0"[..]));
+        assert_eq!(result.length(0, 0), 3);
+        assert_eq!(result.value(1, 0), None);
+        assert_eq!(result.value(2, 0), Some(&b"text"[..]));
+        assert_eq!(result.value(3, 0), Some(&b""[..]));
+        assert!(!result.is_null(3, 0));
+
+        assert!(result.set_value_bytes(0, 1, Some(b"")).is_err());
+    }
+
+    #[test]
+    fn error_fields() {
+        let conn = crate::test::new_conn();
+        conn.exec(
+            "CREATE TEMPORARY TABLE error_fields (id int CONSTRAINT positive CHECK (id > 0))",
+        );
+
+        let result = conn.exec("INSERT INTO error_fields VALUES (-1)");
+        let sqlstate = result
+            .error_field(crate::result::ErrorField::Sqlstate)
+            .unwrap()
+            .map(ToString::to_string);
+        drop(result);
+        assert_eq!(sqlstate, Some("23514".to_string()));
+
+        let fields = conn
+            .exec("INSERT INTO error_fields VALUES (-1)")
+            .error_fields()
+            .unwrap();
+        assert_eq!(fields.severity_nonlocalized, Some("ERROR".to_string()));
+        assert_eq!(fields.sqlstate, Some("23514".to_string()));
+        assert_eq!(fields.table_name, Some("error_fields".to_string()));
+        assert_eq!(fields.constraint_name, Some("positive".to_string()));
+        assert!(fields.message_detail.is_some());
+        assert!(fields.source_line.is_some());
+        assert_eq!(fields.statement_position, None);
+
+        let fields = conn.exec("SELECT unknow").error_fields().unwrap();
+        assert_eq!(fields.statement_position, Some(8));
+
+        assert_eq!(
+            conn.exec("SELECT 1").error_fields(),
+            Ok(crate::result::ErrorFields::default())
+        );
+    }
+
+    #[test]
+    fn append_row() {
+        let conn = crate::test::new_conn();
+        let source =
+            conn.exec("SELECT x AS id, NULLIF(x, 2)::text AS name FROM generate_series(1, 3) x");
+
+        let mut result = crate::PQResult::new(&conn, crate::Status::TuplesOk);
+        result.copy_attrs_from(&source).unwrap();
+        assert_eq!(result.nfields(), 2);
+        assert_eq!(result.field_name(1), Ok(Some("name".to_string())));
+        assert_eq!(result.field_type(0), crate::types::INT4.oid);
+
+        for row in source.rows().filter(|x| x.index() != 0) {
+            result.append_row(&row).unwrap();
+        }
+
+        assert_eq!(result.ntuples(), 2);
+        assert_eq!(result.get::<i32>(0, 0), Ok(2));
+        assert_eq!(result.value(0, 1), None);
+        assert_eq!(result.get::<String>(1, 1), Ok("3".to_string()));
+
+        let mut ids = crate::PQResult::new(&conn, crate::Status::TuplesOk);
+        let attributes = source.attributes().unwrap();
+        ids.set_attrs(&[&attributes[0]]).unwrap();
+        assert!(ids.append_row(&source.rows().next().unwrap()).is_err());
+    }
+
+    #[test]
+    fn instance_data() {
+        let conn = crate::test::new_conn();
+        let mut result = conn.exec("SELECT 1");
+        assert!(result.set_instance_data(1).is_err());
+
+        conn.register_result_data().unwrap();
+        assert!(conn.register_result_data().is_err());
+
+        let data = std::sync::Arc::new(vec![1, 2]);
+        let mut result = conn.exec("SELECT 1");
+        assert!(result.instance_data::<i32>().is_none());
+
+        result.set_instance_data(data.clone()).unwrap();
+        assert_eq!(
+            result.instance_data::<std::sync::Arc<Vec<i32>>>(),
+            Some(&data)
+        );
+        assert!(result.instance_data::<String>().is_none());
+        assert_eq!(std::sync::Arc::strong_count(&data), 2);
+
+        result.set_instance_data("foo".to_string()).unwrap();
+        assert_eq!(std::sync::Arc::strong_count(&data), 1);
+
+        result.set_instance_data(data.clone()).unwrap();
+        let ntuples = unsafe { result.process_with(|raw| pq_sys::PQntuples(raw)) };
+        assert_eq!(ntuples, 1);
+
+        drop(result);
+        assert_eq!(std::sync::Arc::strong_count(&data), 1);
+    }
+}
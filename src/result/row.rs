@@ -0,0 +1,147 @@
+/**
+ * A row of a result, see `libpq::PQResult::rows`.
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct Row<'r> {
+    result: &'r crate::PQResult,
+    index: usize,
+}
+
+impl<'r> Row<'r> {
+    /**
+     * The row number in the result.
+     */
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /**
+     * Decodes the value of `column`, see `libpq::PQResult::get`.
+     */
+    pub fn get<T: crate::types::FromSql>(&self, column: usize) -> crate::errors::Result<T> {
+        self.result.get(self.index, column)
+    }
+
+    /**
+     * Decodes the value of the column named `name`, see `libpq::PQResult::field_number`.
+     */
+    pub fn get_by_name<T: crate::types::FromSql>(&self, name: &str) -> crate::errors::Result<T> {
+        let column = self
+            .result
+            .field_number(name)
+            .ok_or_else(|| crate::errors::Error::UnknowColumn(name.to_string()))?;
+
+        self.get(column)
+    }
+
+    /**
+     * Returns the raw value of `column`, `None` for null values.
+     */
+    pub fn value(&self, column: usize) -> Option<&'r [u8]> {
+        self.result.value(self.index, column)
+    }
+
+    pub fn is_null(&self, column: usize) -> bool {
+        self.result.is_null(self.index, column)
+    }
+
+    /**
+     * The number of columns.
+     */
+    pub fn len(&self) -> usize {
+        self.result.nfields()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/**
+ * Iterator over the rows of a result, see `libpq::PQResult::rows`.
+ */
+#[derive(Clone, Debug)]
+pub struct Rows<'r> {
+    result: &'r crate::PQResult,
+    range: std::ops::Range<usize>,
+}
+
+impl<'r> Rows<'r> {
+    pub(crate) fn new(result: &'r crate::PQResult) -> Self {
+        Self {
+            result,
+            range: 0..result.ntuples(),
+        }
+    }
+}
+
+impl<'r> Iterator for Rows<'r> {
+    type Item = Row<'r>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.range.next().map(|index| Row {
+            result: self.result,
+            index,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Rows<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.range.next_back().map(|index| Row {
+            result: self.result,
+            index,
+        })
+    }
+}
+
+impl ExactSizeIterator for Rows<'_> {}
+
+impl<'r> IntoIterator for &'r crate::PQResult {
+    type Item = Row<'r>;
+    type IntoIter = Rows<'r>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn rows() {
+        let conn = crate::test::new_conn();
+        let result =
+            conn.exec("SELECT x AS id, NULLIF(x, 2)::text AS name FROM generate_series(1, 3) x");
+
+        assert_eq!(result.rows().len(), 3);
+
+        let mut ids = Vec::new();
+
+        for row in &result {
+            assert_eq!(row.len(), 2);
+            ids.push(row.get::<i32>(0).unwrap());
+
+            if row.index() == 1 {
+                assert!(row.is_null(1));
+                assert_eq!(row.value(1), None);
+                assert_eq!(row.get_by_name::<Option<String>>("name"), Ok(None));
+            } else {
+                assert_eq!(row.value(1), Some(row.value(0).unwrap()));
+            }
+        }
+
+        assert_eq!(ids, [1, 2, 3]);
+
+        let last = result.rows().next_back().unwrap();
+        assert_eq!(last.get_by_name::<String>("name"), Ok("3".to_string()));
+        assert_eq!(
+            last.get_by_name::<String>("unknow"),
+            Err(crate::errors::Error::UnknowColumn("unknow".to_string()))
+        );
+    }
+}
@@ -0,0 +1,292 @@
+/**
+ * Decides whether a failed operation is worth another attempt.
+ */
+pub type Classifier = dyn Fn(&crate::errors::Error) -> bool + Send + Sync;
+
+/**
+ * Retry policy with exponential backoff, shared by the helpers retrying an operation like
+ * `libpq::Connection::connect_with_retry`.
+ *
+ * The delay before the attempt `n` (starting at 1 for the first retry) is
+ * `base_delay * multiplier^(n - 1)`, capped to `max_delay`. With jitter, the actual delay is
+ * picked at random between half and all of it, so clients failing together don't retry
+ * together.
+ *
+ * ```no_run
+ * let policy = libpq::retry::Policy::default()
+ *     .max_attempts(5)
+ *     .base_delay(std::time::Duration::from_millis(200));
+ *
+ * let conn = policy.run(|_| libpq::Connection::new("host=localhost"))?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Clone)]
+pub struct Policy {
+    max_attempts: u32,
+    base_delay: std::time::Duration,
+    max_delay: std::time::Duration,
+    multiplier: f64,
+    jitter: bool,
+    retry_on: std::sync::Arc<Classifier>,
+}
+
+impl Default for Policy {
+    /**
+     * 3 attempts, 100 ms doubled on each retry up to 10 s, with jitter, retrying on
+     * `libpq::retry::is_transient` errors.
+     */
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: std::time::Duration::from_millis(100),
+            max_delay: std::time::Duration::from_secs(10),
+            multiplier: 2.,
+            jitter: true,
+            retry_on: std::sync::Arc::new(is_transient),
+        }
+    }
+}
+
+impl std::fmt::Debug for Policy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Policy")
+            .field("max_attempts", &self.max_attempts)
+            .field("base_delay", &self.base_delay)
+            .field("max_delay", &self.max_delay)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Policy {
+    /**
+     * A policy making a single attempt.
+     */
+    pub fn never() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /**
+     * Total number of attempts, including the first one. `0` is handled as `1`.
+     */
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn base_delay(mut self, delay: std::time::Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    pub fn max_delay(mut self, delay: std::time::Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /**
+     * Factor applied to the delay after each retry, `1.` for a constant delay.
+     */
+    pub fn multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.);
+        self
+    }
+
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /**
+     * Replaces the classification of the errors worth a retry.
+     */
+    pub fn retry_on(
+        mut self,
+        classifier: impl Fn(&crate::errors::Error) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.retry_on = std::sync::Arc::new(classifier);
+        self
+    }
+
+    /**
+     * Whether `error`, returned by the attempt number `attempt` (starting at 1), should be
+     * retried.
+     */
+    pub fn should_retry(&self, attempt: u32, error: &crate::errors::Error) -> bool {
+        attempt < self.max_attempts && (self.retry_on)(error)
+    }
+
+    /**
+     * Delay to wait before the retry number `retry` (starting at 1).
+     */
+    pub fn delay(&self, retry: u32) -> std::time::Duration {
+        let factor = self.multiplier.powi(retry.saturating_sub(1) as i32);
+        let delay = self
+            .base_delay
+            .mul_f64(factor.min(u32::MAX as f64))
+            .min(self.max_delay);
+
+        if self.jitter {
+            delay.mul_f64(0.5 + random() * 0.5)
+        } else {
+            delay
+        }
+    }
+
+    /**
+     * Calls `f` with the attempt number (starting at 1) until it succeeds, returns an error
+     * not worth a retry or the attempts are exhausted. The last error is returned.
+     */
+    pub fn run<T>(
+        &self,
+        mut f: impl FnMut(u32) -> crate::errors::Result<T>,
+    ) -> crate::errors::Result<T> {
+        let mut attempt = 1;
+
+        loop {
+            match f(attempt) {
+                Ok(value) => return Ok(value),
+                Err(err) if self.should_retry(attempt, &err) => {
+                    let delay = self.delay(attempt);
+                    log::trace!("Attempt {attempt} failed, retrying in {delay:?}: {err}");
+
+                    std::thread::sleep(delay);
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/**
+ * Default classification: connection failures, timeouts, and server errors of the classes
+ * `08` (connection exception), `40` (transaction rollback, like a serialization failure or a
+ * deadlock), `53` (insufficient resources) and `57P0x` (server shutting down or starting up).
+ *
+ * A connection failure reported by libpq is recognized with
+ * `libpq::errors::Error::connect_attempts`, which only understands the English messages.
+ */
+pub fn is_transient(error: &crate::errors::Error) -> bool {
+    match error {
+        crate::errors::Error::Connect(_) | crate::errors::Error::Timeout => true,
+        crate::errors::Error::Backend(_) => !error.connect_attempts().is_empty(),
+        crate::errors::Error::Query {
+            sqlstate: Some(sqlstate),
+            ..
+        } => {
+            sqlstate.starts_with("08")
+                || sqlstate.starts_with("40")
+                || sqlstate.starts_with("53")
+                || sqlstate.starts_with("57P0")
+        }
+        _ => false,
+    }
+}
+
+/**
+ * A random number in `[0, 1)`, good enough to spread the retries.
+ */
+fn random() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    hasher.write_u128(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos(),
+    );
+
+    (hasher.finish() >> 11) as f64 / (1_u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn delay() {
+        let policy = super::Policy::default()
+            .base_delay(std::time::Duration::from_millis(100))
+            .max_delay(std::time::Duration::from_millis(300))
+            .jitter(false);
+
+        assert_eq!(policy.delay(1), std::time::Duration::from_millis(100));
+        assert_eq!(policy.delay(2), std::time::Duration::from_millis(200));
+        assert_eq!(policy.delay(3), std::time::Duration::from_millis(300));
+        assert_eq!(policy.delay(100), std::time::Duration::from_millis(300));
+
+        let policy = policy.jitter(true);
+        for _ in 0..100 {
+            let delay = policy.delay(2);
+            assert!(delay >= std::time::Duration::from_millis(100));
+            assert!(delay <= std::time::Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn run() {
+        let policy = super::Policy::default()
+            .max_attempts(3)
+            .base_delay(std::time::Duration::from_millis(1));
+
+        let mut calls = 0;
+        let result = policy.run(|attempt| {
+            calls += 1;
+
+            if attempt < 3 {
+                Err(crate::errors::Error::Timeout)
+            } else {
+                Ok(attempt)
+            }
+        });
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result: crate::errors::Result = policy.run(|_| {
+            calls += 1;
+            Err(crate::errors::Error::Timeout)
+        });
+        assert_eq!(result, Err(crate::errors::Error::Timeout));
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let result: crate::errors::Result = policy.run(|_| {
+            calls += 1;
+            Err(crate::errors::Error::Unknow)
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let result: crate::errors::Result = policy.retry_on(|_| true).run(|_| {
+            calls += 1;
+            Err(crate::errors::Error::Unknow)
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn connect_with_retry() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let config = crate::connection::Config::builder()
+            .host("127.0.0.1")
+            .port(port)
+            .build();
+        let policy = super::Policy::default()
+            .max_attempts(2)
+            .base_delay(std::time::Duration::from_millis(1));
+
+        assert!(matches!(
+            crate::Connection::connect_with_retry(&config, &policy),
+            Err(crate::errors::Error::Backend(_))
+        ));
+    }
+}
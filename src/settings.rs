@@ -0,0 +1,175 @@
+/**
+ * A server run-time parameter, as reported by `pg_settings`.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Setting {
+    pub name: String,
+    pub value: String,
+    /** Implicit unit of the parameter. */
+    pub unit: Option<String>,
+    /** Source of the current value, like `default`, `configuration file` or `session`. */
+    pub source: String,
+    /** The value has been changed in the configuration file but needs a restart. */
+    pub pending_restart: bool,
+}
+
+/**
+ * A difference between two settings snapshots.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Diff<'a> {
+    /** The parameter only exists in the other snapshot. */
+    Added(&'a Setting),
+    /** The parameter doesn't exist in the other snapshot. */
+    Removed(&'a Setting),
+    /** The parameter has a different value or unit. */
+    Changed {
+        left: &'a Setting,
+        right: &'a Setting,
+    },
+}
+
+/**
+ * A snapshot of the server settings, see `libpq::Connection::settings`.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Settings {
+    inner: std::collections::BTreeMap<String, Setting>,
+}
+
+impl Settings {
+    pub(crate) fn load(conn: &crate::Connection) -> crate::errors::Result<Self> {
+        let result = conn.exec(
+            "SELECT name, setting, unit, source, pending_restart FROM pg_catalog.pg_settings",
+        );
+
+        if result.status() != crate::Status::TuplesOk {
+            return conn.error();
+        }
+
+        let text = |row, column| -> crate::errors::Result<Option<String>> {
+            result
+                .value(row, column)
+                .map(|x| std::str::from_utf8(x).map(ToString::to_string))
+                .transpose()
+                .map_err(Into::into)
+        };
+
+        let mut inner = std::collections::BTreeMap::new();
+
+        for row in 0..result.ntuples() {
+            let setting = Setting {
+                name: text(row, 0)?.unwrap_or_default(),
+                value: text(row, 1)?.unwrap_or_default(),
+                unit: text(row, 2)?,
+                source: text(row, 3)?.unwrap_or_default(),
+                pending_restart: result.value(row, 4) == Some(b"t"),
+            };
+
+            inner.insert(setting.name.clone(), setting);
+        }
+
+        Ok(Self { inner })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Setting> {
+        self.inner.get(name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Setting> {
+        self.inner.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /**
+     * Compares the values of this snapshot with `other`, ordered by parameter name.
+     *
+     * The source of the values is ignored.
+     */
+    pub fn diff<'a>(&'a self, other: &'a Self) -> Vec<Diff<'a>> {
+        let mut diff = Vec::new();
+
+        for left in self.inner.values() {
+            match other.inner.get(&left.name) {
+                Some(right) if left.value != right.value || left.unit != right.unit => {
+                    diff.push(Diff::Changed { left, right })
+                }
+                Some(_) => (),
+                None => diff.push(Diff::Removed(left)),
+            }
+        }
+
+        for right in other.inner.values() {
+            if !self.inner.contains_key(&right.name) {
+                diff.push(Diff::Added(right));
+            }
+        }
+
+        diff.sort_by(|a, b| a.name().cmp(b.name()));
+
+        diff
+    }
+}
+
+impl Diff<'_> {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Added(setting) | Self::Removed(setting) => &setting.name,
+            Self::Changed { left, .. } => &left.name,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a Settings {
+    type Item = &'a Setting;
+    type IntoIter = std::collections::btree_map::Values<'a, String, Setting>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.inner.values()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn settings() {
+        let conn = crate::test::new_conn();
+        let settings = conn.settings().unwrap();
+
+        let encoding = settings.get("client_encoding").unwrap();
+        assert_eq!(encoding.value, "UTF8");
+        assert!(!encoding.pending_restart);
+
+        let work_mem = settings.get("work_mem").unwrap();
+        assert_eq!(work_mem.unit.as_deref(), Some("kB"));
+    }
+
+    #[test]
+    fn diff() {
+        let conn = crate::test::new_conn();
+        let other = crate::test::new_conn();
+
+        let left = conn.settings().unwrap();
+        assert!(left.diff(&other.settings().unwrap()).is_empty());
+
+        other.exec("SET work_mem = '42MB'");
+        let right = other.settings().unwrap();
+
+        let diff = left.diff(&right);
+        assert_eq!(diff.len(), 1);
+        assert_eq!(diff[0].name(), "work_mem");
+
+        let crate::settings::Diff::Changed { right, .. } = diff[0] else {
+            panic!("work_mem should have changed");
+        };
+        assert_eq!(right.value, "43008");
+        assert_eq!(right.source, "session");
+    }
+}
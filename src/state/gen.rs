@@ -1830,8 +1830,16 @@ pub const INDEX_CORRUPTED: State = State {
 
 impl State {
     /// Creates a `State` from its error code.
+    ///
+    /// Panics on an unknown code, like one raised by an extension or a newer server.
+    #[deprecated(since = "4.3.0", note = "Use State::try_from_code instead")]
     pub fn from_code(s: &str) -> State {
-        match s {
+        Self::try_from_code(s).unwrap_or_else(|| panic!("Unknown SQLSTATE code: {s}"))
+    }
+
+    /// Creates a `State` from its error code, `None` if the code is unknown.
+    pub fn try_from_code(s: &str) -> Option<State> {
+        let state = match s {
             "00000" => SUCCESSFUL_COMPLETION,
             "01000" => WARNING,
             "01003" => WARNING_NULL_VALUE_ELIMINATED_IN_SET_FUNCTION,
@@ -2094,7 +2102,9 @@ impl State {
             "XX000" => INTERNAL_ERROR,
             "XX001" => DATA_CORRUPTED,
             "XX002" => INDEX_CORRUPTED,
-            _ => unreachable!(),
-        }
+            _ => return None,
+        };
+
+        Some(state)
     }
 }
@@ -15,3 +15,15 @@ pub struct State {
 }
 
 include!("gen.rs");
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn try_from_code() {
+        assert_eq!(
+            crate::state::State::try_from_code("57014"),
+            Some(crate::state::QUERY_CANCELED)
+        );
+        assert_eq!(crate::state::State::try_from_code("ZZ999"), None);
+    }
+}
@@ -36,6 +36,14 @@ pub enum Status {
     /** Command didn't run because of an abort earlier in a pipeline. */
     #[cfg(feature = "v14")]
     PipelineAborted,
+
+    /**
+     * The `libpq::PQResult` contains several result tuples from the current command. This status
+     * occurs only when chunked mode has been selected for the query, see
+     * `libpq::Connection::set_chunked_rows_mode`.
+     */
+    #[cfg(feature = "v17")]
+    TuplesChunk,
 }
 
 #[doc(hidden)]
@@ -56,6 +64,8 @@ impl From<pq_sys::ExecStatusType> for Status {
             pq_sys::ExecStatusType::PGRES_PIPELINE_SYNC => Self::PipelineSync,
             #[cfg(feature = "v14")]
             pq_sys::ExecStatusType::PGRES_PIPELINE_ABORTED => Self::PipelineAborted,
+            #[cfg(feature = "v17")]
+            pq_sys::ExecStatusType::PGRES_TUPLES_CHUNK => Self::TuplesChunk,
             #[allow(unreachable_patterns)]
             _ => unreachable!(),
         }
@@ -89,6 +99,8 @@ impl From<&Status> for pq_sys::ExecStatusType {
             Status::PipelineSync => pq_sys::ExecStatusType::PGRES_PIPELINE_SYNC,
             #[cfg(feature = "v14")]
             Status::PipelineAborted => pq_sys::ExecStatusType::PGRES_PIPELINE_ABORTED,
+            #[cfg(feature = "v17")]
+            Status::TuplesChunk => pq_sys::ExecStatusType::PGRES_TUPLES_CHUNK,
             #[allow(unreachable_patterns)]
             _ => unreachable!(),
         }
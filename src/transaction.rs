@@ -24,3 +24,246 @@ impl From<pq_sys::PGTransactionStatusType> for Status {
         }
     }
 }
+
+/**
+ * A transaction block, rolled back unless committed.
+ *
+ * Created by `libpq::Connection::transaction`. Queries are executed through the connection,
+ * which the guard dereferences to. Dropping the guard without calling
+ * [`Transaction::commit`] rolls the transaction back, so an early return or a panic never
+ * leaves it open.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let transaction = conn.transaction()?;
+ * transaction.exec_checked("INSERT INTO item (name) VALUES ('foo')")?;
+ * transaction.commit()?;
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Debug)]
+pub struct Transaction<'c> {
+    conn: &'c crate::Connection,
+    done: bool,
+}
+
+impl<'c> Transaction<'c> {
+    pub(crate) fn begin(conn: &'c crate::Connection) -> crate::errors::Result<Self> {
+        if conn.transaction_status() != Status::Idle {
+            return Err(crate::errors::Error::Backend(
+                "A transaction is already in progress, use a savepoint instead".to_string(),
+            ));
+        }
+
+        conn.exec_checked("BEGIN")?;
+
+        Ok(Self { conn, done: false })
+    }
+
+    /**
+     * Commits the transaction.
+     *
+     * A transaction aborted by a failed command can't be committed: it is rolled back and an
+     * error is returned.
+     */
+    pub fn commit(mut self) -> crate::errors::Result {
+        self.done = true;
+
+        if self.conn.transaction_status() == Status::InError {
+            self.conn.exec_checked("ROLLBACK")?;
+
+            return Err(crate::errors::Error::Backend(
+                "The transaction was aborted by a failed command and has been rolled back"
+                    .to_string(),
+            ));
+        }
+
+        self.conn.exec_checked("COMMIT")?;
+
+        Ok(())
+    }
+
+    /**
+     * Rolls the transaction back.
+     */
+    pub fn rollback(mut self) -> crate::errors::Result {
+        self.done = true;
+        self.conn.exec_checked("ROLLBACK")?;
+
+        Ok(())
+    }
+
+    /**
+     * Defines a savepoint, the name is escaped.
+     *
+     * Like the transaction, the savepoint is rolled back when dropped unless released.
+     */
+    pub fn savepoint(&self, name: &str) -> crate::errors::Result<Savepoint<'_>> {
+        Savepoint::new(self.conn, name)
+    }
+}
+
+impl std::ops::Deref for Transaction<'_> {
+    type Target = crate::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if self.done || self.conn.transaction_status() == Status::Idle {
+            return;
+        }
+
+        log::trace!("Rolling back unfinished transaction");
+
+        self.conn.exec("ROLLBACK");
+    }
+}
+
+/**
+ * A savepoint inside a [`Transaction`], rolled back unless released.
+ */
+#[derive(Debug)]
+pub struct Savepoint<'t> {
+    conn: &'t crate::Connection,
+    name: String,
+    done: bool,
+}
+
+impl<'t> Savepoint<'t> {
+    fn new(conn: &'t crate::Connection, name: &str) -> crate::errors::Result<Self> {
+        let name = conn.escape_identifier(name)?.to_string_lossy().to_string();
+        conn.exec_checked(&format!("SAVEPOINT {name}"))?;
+
+        Ok(Self {
+            conn,
+            name,
+            done: false,
+        })
+    }
+
+    /**
+     * Releases the savepoint, keeping the changes made since it was defined.
+     */
+    pub fn release(mut self) -> crate::errors::Result {
+        self.done = true;
+        self.conn
+            .exec_checked(&format!("RELEASE SAVEPOINT {}", self.name))?;
+
+        Ok(())
+    }
+
+    /**
+     * Rolls back the changes made since the savepoint was defined, the transaction goes on.
+     */
+    pub fn rollback(mut self) -> crate::errors::Result {
+        self.done = true;
+        self.rollback_to()
+    }
+
+    /**
+     * Defines a nested savepoint.
+     */
+    pub fn savepoint(&self, name: &str) -> crate::errors::Result<Savepoint<'_>> {
+        Savepoint::new(self.conn, name)
+    }
+
+    fn rollback_to(&self) -> crate::errors::Result {
+        self.conn
+            .exec_checked(&format!("ROLLBACK TO SAVEPOINT {}", self.name))?;
+        self.conn
+            .exec_checked(&format!("RELEASE SAVEPOINT {}", self.name))?;
+
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for Savepoint<'_> {
+    type Target = crate::Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.conn
+    }
+}
+
+impl Drop for Savepoint<'_> {
+    fn drop(&mut self) {
+        if self.done || self.conn.transaction_status() == Status::Idle {
+            return;
+        }
+
+        log::trace!("Rolling back unreleased savepoint {}", self.name);
+
+        self.rollback_to().ok();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn count(conn: &crate::Connection) -> i64 {
+        conn.exec("SELECT count(*) FROM transaction")
+            .get::<i64>(0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn transaction() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE transaction (id int)");
+
+        let transaction = conn.transaction().unwrap();
+        assert_eq!(conn.transaction_status(), super::Status::InTrans);
+        assert!(conn.transaction().is_err());
+        transaction.exec("INSERT INTO transaction VALUES (1)");
+        transaction.commit().unwrap();
+        assert_eq!(conn.transaction_status(), super::Status::Idle);
+        assert_eq!(count(&conn), 1);
+
+        let transaction = conn.transaction().unwrap();
+        transaction.exec("INSERT INTO transaction VALUES (2)");
+        transaction.rollback().unwrap();
+        assert_eq!(count(&conn), 1);
+
+        {
+            let transaction = conn.transaction().unwrap();
+            transaction.exec("INSERT INTO transaction VALUES (2)");
+        }
+        assert_eq!(conn.transaction_status(), super::Status::Idle);
+        assert_eq!(count(&conn), 1);
+
+        let transaction = conn.transaction().unwrap();
+        transaction.exec("SELECT 1 / 0");
+        assert!(transaction.commit().is_err());
+        assert_eq!(conn.transaction_status(), super::Status::Idle);
+    }
+
+    #[test]
+    fn savepoint() {
+        let conn = crate::test::new_conn();
+        conn.exec("CREATE TEMPORARY TABLE transaction (id int)");
+
+        let transaction = conn.transaction().unwrap();
+        transaction.exec("INSERT INTO transaction VALUES (1)");
+
+        let savepoint = transaction.savepoint("first").unwrap();
+        savepoint.exec("INSERT INTO transaction VALUES (2)");
+        savepoint.release().unwrap();
+
+        let savepoint = transaction.savepoint("second").unwrap();
+        savepoint.exec("INSERT INTO transaction VALUES (3)");
+        savepoint.rollback().unwrap();
+
+        {
+            let savepoint = transaction.savepoint("third \"quoted\"").unwrap();
+            let nested = savepoint.savepoint("nested").unwrap();
+            nested.exec("SELECT 1 / 0");
+        }
+        assert_eq!(conn.transaction_status(), super::Status::InTrans);
+
+        transaction.commit().unwrap();
+        assert_eq!(count(&conn), 2);
+    }
+}
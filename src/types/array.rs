@@ -0,0 +1,714 @@
+/**
+ * A PostgreSQL [array](https://www.postgresql.org/docs/current/arrays.html) of `T`.
+ *
+ * The elements are stored in row-major order, with the size and lower bound of each dimension.
+ * Arrays are decoded from the text (`{1,2,NULL}`) and binary representations by
+ * `libpq::PQResult::get`, displayed as their text representation and encoded in binary by
+ * `libpq::types::codec::encode`:
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let result = conn.exec("SELECT '{{1,2},{3,NULL}}'::int4[]");
+ * let array = result.get::<libpq::types::Array<i32>>(0, 0)?;
+ *
+ * assert_eq!(array.get(&[2, 1]), Some(&3));
+ * assert_eq!(array.to_string(), "{{1,2},{3,NULL}}");
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Array<T> {
+    dimensions: Vec<crate::types::codec::Dimension>,
+    elements: Vec<Option<T>>,
+}
+
+impl<T> Array<T> {
+    /**
+     * A one-dimensional array, with a lower bound of 1.
+     */
+    pub fn new(elements: Vec<Option<T>>) -> Self {
+        let dimensions = if elements.is_empty() {
+            Vec::new()
+        } else {
+            vec![crate::types::codec::Dimension {
+                len: elements.len() as i32,
+                lower_bound: 1,
+            }]
+        };
+
+        Self {
+            dimensions,
+            elements,
+        }
+    }
+
+    /**
+     * A multidimensional array, `elements` being in row-major order.
+     *
+     * Returns an error if the number of elements doesn't match the dimensions.
+     */
+    pub fn with_dimensions(
+        dimensions: Vec<crate::types::codec::Dimension>,
+        elements: Vec<Option<T>>,
+    ) -> crate::errors::Result<Self> {
+        if dimensions.iter().any(|x| x.len < 0) || count(&dimensions) != Some(elements.len()) {
+            return Err(crate::errors::Error::Backend(format!(
+                "{} elements don't match the array dimensions {dimensions:?}",
+                elements.len()
+            )));
+        }
+
+        Ok(Self {
+            dimensions,
+            elements,
+        })
+    }
+
+    pub fn dimensions(&self) -> &[crate::types::codec::Dimension] {
+        &self.dimensions
+    }
+
+    /**
+     * The elements, in row-major order.
+     */
+    pub fn elements(&self) -> &[Option<T>] {
+        &self.elements
+    }
+
+    pub fn into_elements(self) -> Vec<Option<T>> {
+        self.elements
+    }
+
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /**
+     * The element at `indexes`, one per dimension and relative to its lower bound, like
+     * `array[i][j]` in SQL. `None` for a null element or indexes out of bounds.
+     */
+    pub fn get(&self, indexes: &[i32]) -> Option<&T> {
+        if indexes.len() != self.dimensions.len() {
+            return None;
+        }
+
+        let mut offset = 0;
+
+        for (index, dimension) in indexes.iter().zip(&self.dimensions) {
+            let n = index.checked_sub(dimension.lower_bound)?;
+
+            if n < 0 || n >= dimension.len {
+                return None;
+            }
+
+            offset = offset * dimension.len as usize + n as usize;
+        }
+
+        self.elements.get(offset)?.as_ref()
+    }
+}
+
+impl<T: crate::types::FromSql> Array<T> {
+    /**
+     * Parses the text representation of an array of `element`.
+     */
+    pub fn parse(element: &crate::Type, s: &str) -> crate::errors::Result<Self> {
+        let (dimensions, elements) = Parser::new(s).parse()?;
+
+        let elements = elements
+            .into_iter()
+            .map(|x| {
+                x.map(|x| T::from_sql(element.oid, crate::Format::Text, x.as_bytes()))
+                    .transpose()
+            })
+            .collect::<crate::errors::Result<_>>()?;
+
+        Ok(Self {
+            dimensions,
+            elements,
+        })
+    }
+
+    /**
+     * Decodes the binary representation of an array of type `ty`.
+     */
+    pub fn from_binary(ty: &crate::Type, raw: &[u8]) -> crate::errors::Result<Self> {
+        let element = element_type(ty.oid)?;
+        let array = crate::types::codec::split_array(ty, &element, raw)?;
+
+        let elements = array
+            .elements
+            .into_iter()
+            .map(|x| {
+                x.map(|x| crate::types::decode(element.oid, crate::Format::Binary, x))
+                    .transpose()
+            })
+            .collect::<crate::errors::Result<_>>()?;
+
+        Ok(Self {
+            dimensions: array.dimensions,
+            elements,
+        })
+    }
+}
+
+impl<T: crate::types::codec::Element> Array<T> {
+    /**
+     * Encodes the array in binary format.
+     */
+    pub fn to_binary(&self) -> crate::errors::Result<Vec<u8>> {
+        let elements = self
+            .elements
+            .iter()
+            .map(crate::types::codec::Encode::encode)
+            .collect::<crate::errors::Result<Vec<_>>>()?;
+
+        crate::types::codec::join_array(&T::ty(), &self.dimensions, &elements)
+    }
+}
+
+impl<T: crate::types::codec::Element> crate::types::codec::Encode for Array<T> {
+    fn ty() -> crate::Type {
+        T::array_ty()
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        self.to_binary().map(Some)
+    }
+}
+
+impl<T: crate::types::FromSql> crate::types::FromSql for Array<T> {
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self> {
+        match format {
+            crate::Format::Text => Self::parse(&element_type(ty)?, std::str::from_utf8(raw)?),
+            crate::Format::Binary => {
+                let ty = crate::Type::try_from(ty).map_err(crate::errors::Error::Backend)?;
+
+                Self::from_binary(&ty, raw)
+            }
+        }
+    }
+}
+
+/**
+ * The text representation, with the bounds when they aren't the default ones.
+ */
+impl<T: std::fmt::Display> std::fmt::Display for Array<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.dimensions.iter().any(|x| x.lower_bound != 1) {
+            for dimension in &self.dimensions {
+                write!(
+                    f,
+                    "[{}:{}]",
+                    dimension.lower_bound,
+                    i64::from(dimension.lower_bound) + i64::from(dimension.len) - 1
+                )?;
+            }
+            f.write_str("=")?;
+        }
+
+        if self.dimensions.is_empty() {
+            return f.write_str("{}");
+        }
+
+        let mut elements = self.elements.iter();
+        self.fmt_dimension(f, 0, &mut elements)
+    }
+}
+
+impl<T: std::fmt::Display> Array<T> {
+    fn fmt_dimension(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        dimension: usize,
+        elements: &mut std::slice::Iter<'_, Option<T>>,
+    ) -> std::fmt::Result {
+        f.write_str("{")?;
+
+        for n in 0..self.dimensions[dimension].len {
+            if n > 0 {
+                f.write_str(",")?;
+            }
+
+            if dimension + 1 < self.dimensions.len() {
+                self.fmt_dimension(f, dimension + 1, elements)?;
+            } else {
+                match elements.next() {
+                    Some(Some(element)) => fmt_element(f, &element.to_string())?,
+                    _ => f.write_str("NULL")?,
+                }
+            }
+        }
+
+        f.write_str("}")
+    }
+}
+
+fn fmt_element(f: &mut std::fmt::Formatter<'_>, element: &str) -> std::fmt::Result {
+    let quote = element.is_empty()
+        || element.eq_ignore_ascii_case("NULL")
+        || element
+            .chars()
+            .any(|c| matches!(c, '{' | '}' | ',' | '"' | '\\') || c.is_whitespace());
+
+    if !quote {
+        return f.write_str(element);
+    }
+
+    f.write_str("\"")?;
+    for c in element.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+        write!(f, "{c}")?;
+    }
+    f.write_str("\"")
+}
+
+/**
+ * Maximum number of array dimensions, `MAXDIM` on the server side.
+ */
+const MAXDIM: usize = 6;
+
+/**
+ * Number of elements of an array of `dimensions`, `None` on overflow.
+ */
+fn count(dimensions: &[crate::types::codec::Dimension]) -> Option<usize> {
+    if dimensions.is_empty() {
+        Some(0)
+    } else {
+        dimensions
+            .iter()
+            .try_fold(1_usize, |count, x| count.checked_mul(x.len.max(0) as usize))
+    }
+}
+
+/**
+ * The element type of the array type `ty`.
+ */
+fn element_type(ty: crate::Oid) -> crate::errors::Result<crate::Type> {
+    let wrong_type = || crate::errors::Error::WrongType {
+        oid: ty,
+        rust_type: std::any::type_name::<Array<()>>(),
+    };
+
+    match crate::Type::try_from(ty).map_err(|_| wrong_type())?.kind {
+        crate::types::Kind::Array(element) => {
+            crate::Type::try_from(element).map_err(crate::errors::Error::Backend)
+        }
+        _ => Err(wrong_type()),
+    }
+}
+
+/**
+ * Parser of the text representation of arrays, following `array_in`: optional bounds
+ * (`[0:1]=`), nested braces, elements separated by commas, double quotes and backslashes
+ * escaping the special characters, and unquoted `NULL` for null elements.
+ */
+struct Parser<'a> {
+    s: &'a str,
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    /** Length of each dimension, set by its first sub-array. */
+    lens: Vec<Option<i32>>,
+    elements: Vec<Option<String>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(s: &'a str) -> Self {
+        Self {
+            s,
+            chars: s.chars().peekable(),
+            lens: Vec::new(),
+            elements: Vec::new(),
+        }
+    }
+
+    fn error(&self) -> crate::errors::Error {
+        crate::errors::Error::Backend(format!("Malformed array literal: \"{}\"", self.s))
+    }
+
+    fn skip_whitespaces(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> crate::errors::Result {
+        self.skip_whitespaces();
+
+        match self.chars.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn parse(
+        mut self,
+    ) -> crate::errors::Result<(Vec<crate::types::codec::Dimension>, Vec<Option<String>>)> {
+        let bounds = self.parse_bounds()?;
+
+        self.expect('{')?;
+        self.parse_dimension(0)?;
+        self.skip_whitespaces();
+
+        if self.chars.next().is_some() {
+            return Err(self.error());
+        }
+
+        let lens = self.lens.iter().map(|x| x.unwrap_or(0)).collect::<Vec<_>>();
+
+        let dimensions = if self.elements.is_empty() {
+            if bounds.is_some() {
+                return Err(self.error());
+            }
+
+            Vec::new()
+        } else {
+            match bounds {
+                Some(bounds) => {
+                    let matches = bounds.len() == lens.len()
+                        && bounds
+                            .iter()
+                            .zip(&lens)
+                            .all(|(bound, len)| bound.len == *len);
+
+                    if !matches {
+                        return Err(self.error());
+                    }
+
+                    bounds
+                }
+                None => lens
+                    .into_iter()
+                    .map(|len| crate::types::codec::Dimension {
+                        len,
+                        lower_bound: 1,
+                    })
+                    .collect(),
+            }
+        };
+
+        Ok((dimensions, self.elements))
+    }
+
+    /**
+     * Parses the `[lower:upper]...=` prefix, if any.
+     */
+    fn parse_bounds(
+        &mut self,
+    ) -> crate::errors::Result<Option<Vec<crate::types::codec::Dimension>>> {
+        self.skip_whitespaces();
+
+        if self.chars.peek() != Some(&'[') {
+            return Ok(None);
+        }
+
+        let mut bounds = Vec::new();
+
+        while self.chars.next_if_eq(&'[').is_some() {
+            if bounds.len() >= MAXDIM {
+                return Err(self.error());
+            }
+
+            let mut bound = String::new();
+            for c in self.chars.by_ref() {
+                if c == ']' {
+                    break;
+                }
+                bound.push(c);
+            }
+
+            let (lower, upper) = match bound.split_once(':') {
+                Some((lower, upper)) => (lower.trim().parse::<i32>(), upper.trim().parse::<i32>()),
+                None => (Ok(1), bound.trim().parse::<i32>()),
+            };
+            let (Ok(lower), Ok(upper)) = (lower, upper) else {
+                return Err(self.error());
+            };
+
+            let Some(len) = upper
+                .checked_sub(lower)
+                .and_then(|x| x.checked_add(1))
+                .filter(|x| *x > 0)
+            else {
+                return Err(self.error());
+            };
+
+            bounds.push(crate::types::codec::Dimension {
+                len,
+                lower_bound: lower,
+            });
+        }
+
+        self.expect('=')?;
+
+        Ok(Some(bounds))
+    }
+
+    /**
+     * Parses the content of a `{...}` at nesting level `depth`, the opening brace being
+     * consumed.
+     */
+    fn parse_dimension(&mut self, depth: usize) -> crate::errors::Result {
+        if depth >= MAXDIM {
+            return Err(self.error());
+        }
+
+        if self.lens.len() <= depth {
+            self.lens.push(None);
+        }
+
+        self.skip_whitespaces();
+        if self.chars.next_if_eq(&'}').is_some() {
+            // Only a whole empty array is allowed.
+            return if depth == 0 {
+                Ok(())
+            } else {
+                Err(self.error())
+            };
+        }
+
+        let mut len = 0;
+        let mut sub_arrays = None;
+
+        loop {
+            self.skip_whitespaces();
+
+            let is_sub_array = self.chars.next_if_eq(&'{').is_some();
+            if *sub_arrays.get_or_insert(is_sub_array) != is_sub_array {
+                return Err(self.error());
+            }
+
+            if is_sub_array {
+                self.parse_dimension(depth + 1)?;
+            } else {
+                if self.lens.len() > depth + 1 {
+                    return Err(self.error());
+                }
+                let element = self.parse_element()?;
+                self.elements.push(element);
+            }
+
+            len += 1;
+            self.skip_whitespaces();
+
+            match self.chars.next() {
+                Some(',') => continue,
+                Some('}') => break,
+                _ => return Err(self.error()),
+            }
+        }
+
+        if sub_arrays == Some(false) && self.lens.len() > depth + 1 {
+            return Err(self.error());
+        }
+
+        match self.lens[depth] {
+            Some(expected) if expected != len => Err(self.error()),
+            _ => {
+                self.lens[depth] = Some(len);
+                Ok(())
+            }
+        }
+    }
+
+    fn parse_element(&mut self) -> crate::errors::Result<Option<String>> {
+        let mut element = String::new();
+
+        if self.chars.next_if_eq(&'"').is_some() {
+            loop {
+                match self.chars.next() {
+                    Some('"') => return Ok(Some(element)),
+                    Some('\\') => element.push(self.chars.next().ok_or_else(|| self.error())?),
+                    Some(c) => element.push(c),
+                    None => return Err(self.error()),
+                }
+            }
+        }
+
+        let mut escaped = false;
+        // Length without the trailing unescaped whitespaces.
+        let mut len = 0;
+
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                ',' | '}' => break,
+                '{' | '"' => return Err(self.error()),
+                '\\' => {
+                    self.chars.next();
+                    element.push(self.chars.next().ok_or_else(|| self.error())?);
+                    escaped = true;
+                    len = element.len();
+                    continue;
+                }
+                _ => (),
+            }
+
+            self.chars.next();
+            element.push(c);
+            if !c.is_whitespace() {
+                len = element.len();
+            }
+        }
+
+        element.truncate(len);
+
+        if element.is_empty() {
+            Err(self.error())
+        } else if !escaped && element.eq_ignore_ascii_case("NULL") {
+            Ok(None)
+        } else {
+            Ok(Some(element))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Array;
+
+    fn dimension(len: i32, lower_bound: i32) -> crate::types::codec::Dimension {
+        crate::types::codec::Dimension { len, lower_bound }
+    }
+
+    #[test]
+    fn parse() {
+        let array = Array::<i32>::parse(&crate::types::INT4, "{1, 2 ,NULL}").unwrap();
+        assert_eq!(array.dimensions(), [dimension(3, 1)]);
+        assert_eq!(array.elements(), [Some(1), Some(2), None]);
+
+        let array = Array::<String>::parse(
+            &crate::types::TEXT,
+            r#"{"a b","c\"d",NULL,"NULL",e\,f, g h }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            array.elements(),
+            [
+                Some("a b".to_string()),
+                Some("c\"d".to_string()),
+                None,
+                Some("NULL".to_string()),
+                Some("e,f".to_string()),
+                Some("g h".to_string()),
+            ]
+        );
+
+        let array =
+            Array::<i64>::parse(&crate::types::INT8, "[0:1][-1:1]={{1,2,3},{4,5,6}}").unwrap();
+        assert_eq!(array.dimensions(), [dimension(2, 0), dimension(3, -1)]);
+        assert_eq!(array.get(&[1, 0]), Some(&5));
+        assert_eq!(array.get(&[2, 0]), None);
+
+        let array = Array::<i32>::parse(&crate::types::INT4, "{}").unwrap();
+        assert!(array.dimensions().is_empty());
+        assert!(array.is_empty());
+
+        for invalid in [
+            "",
+            "{",
+            "{1,}",
+            "{{1},2}",
+            "{{1,2},{3}}",
+            "{1}x",
+            "[1:2]={1}",
+            "{{}}",
+            "{{{{{{{1}}}}}}}",
+            "[1:1][1:1][1:1][1:1][1:1][1:1][1:1]={{{{{{{1}}}}}}}",
+            &"{".repeat(1_000_000),
+        ] {
+            assert!(
+                Array::<i32>::parse(&crate::types::INT4, invalid).is_err(),
+                "{invalid}"
+            );
+        }
+
+        let array = Array::<i32>::parse(&crate::types::INT4, "{{{{{{1}}}}}}").unwrap();
+        assert_eq!(array.dimensions().len(), 6);
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(Array::<i32>::with_dimensions(
+            vec![
+                dimension(i32::MAX, 1),
+                dimension(i32::MAX, 1),
+                dimension(i32::MAX, 1)
+            ],
+            Vec::new(),
+        )
+        .is_err());
+
+        let raw = [1, 0, crate::types::INT4.oid as i32, i32::MAX, 1]
+            .iter()
+            .flat_map(|x| x.to_be_bytes())
+            .collect::<Vec<_>>();
+        assert!(Array::<i32>::from_binary(&crate::types::INT4_ARRAY, &raw).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let array = Array::new(vec![
+            Some("a b".to_string()),
+            None,
+            Some("NULL".to_string()),
+        ]);
+        assert_eq!(array.to_string(), r#"{"a b",NULL,"NULL"}"#);
+
+        let array = Array::with_dimensions(
+            vec![dimension(2, 0), dimension(2, 1)],
+            vec![Some(1), Some(2), Some(3), None],
+        )
+        .unwrap();
+        assert_eq!(array.to_string(), "[0:1][1:2]={{1,2},{3,NULL}}");
+
+        assert_eq!(Array::<i32>::new(Vec::new()).to_string(), "{}");
+        assert!(Array::with_dimensions(vec![dimension(2, 1)], vec![Some(1)]).is_err());
+    }
+
+    #[test]
+    fn from_sql() {
+        let conn = crate::test::new_conn();
+        let query =
+            r#"SELECT '[0:1][1:2]={{1,2},{3,NULL}}'::int4[], '{"a,b",NULL}'::text[], '{}'::int8[]"#;
+
+        for format in [crate::Format::Text, crate::Format::Binary] {
+            let result = conn.exec_params(query, &[], &[], &[], format);
+
+            let array = result.get::<Array<i32>>(0, 0).unwrap();
+            assert_eq!(array.dimensions(), [dimension(2, 0), dimension(2, 1)]);
+            assert_eq!(array.elements(), [Some(1), Some(2), Some(3), None]);
+
+            let array = result.get::<Array<String>>(0, 1).unwrap();
+            assert_eq!(array.elements(), [Some("a,b".to_string()), None]);
+
+            assert!(result.get::<Array<i64>>(0, 2).unwrap().is_empty());
+            assert!(result.get::<Array<i32>>(0, 1).is_err());
+        }
+    }
+
+    #[test]
+    fn encode() {
+        let conn = crate::test::new_conn();
+        let array = Array::with_dimensions(
+            vec![dimension(2, 0), dimension(2, 1)],
+            vec![Some(1_i32), Some(2), Some(3), None],
+        )
+        .unwrap();
+        let (oid, value) = crate::types::codec::encode(&array).unwrap();
+
+        let result = conn.exec_params(
+            "SELECT $1::text",
+            &[oid],
+            &[value.as_deref()],
+            &[crate::Format::Binary],
+            crate::Format::Text,
+        );
+        assert_eq!(
+            result.get::<String>(0, 0),
+            Ok("[0:1][1:2]={{1,2},{3,NULL}}".to_string())
+        );
+    }
+}
@@ -0,0 +1,1339 @@
+/*!
+ * Decoding and encoding of the [binary format](https://www.postgresql.org/docs/current/protocol-overview.html#PROTOCOL-FORMAT-CODES)
+ * of the builtin types, as exchanged with `libpq::Format::Binary`. Enums and composite types
+ * are decoded once looked up with `libpq::Type::from_database`.
+ *
+ * The `chrono`, `time` and `uuid` features add encoders for the types of these crates.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let result = conn.exec_params("SELECT now()", &[], &[], &[], libpq::Format::Binary);
+ * let ty = libpq::Type::try_from(result.field_type(0)).unwrap();
+ *
+ * if let Some(raw) = result.value(0, 0) {
+ *     let value = libpq::types::codec::decode(&ty, raw)?;
+ *     println!("{value}");
+ * }
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+
+/**
+ * Microseconds per day.
+ */
+const USECS_PER_DAY: i64 = 86_400_000_000;
+
+/**
+ * Days between the Unix epoch and the PostgreSQL one, 2000-01-01.
+ */
+const EPOCH_DAYS: i64 = 10_957;
+
+/**
+ * A decoded value.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Int2(i16),
+    Int4(i32),
+    Int8(i64),
+    Oid(crate::Oid),
+    Float4(f32),
+    Float8(f64),
+    /** Textual types: `text`, `varchar`, `bpchar`, `name`, `json`, `jsonb` and `xml`. */
+    Text(String),
+    Bytea(Vec<u8>),
+    Uuid([u8; 16]),
+    Date(Date),
+    Time(Time),
+    /** A time of day and its offset in seconds east of UTC. */
+    TimeTz(Time, i32),
+    Timestamp(Timestamp),
+    /** A timestamp in UTC. */
+    TimestampTz(Timestamp),
+    /** The decimal representation, `NaN`, `Infinity` or `-Infinity`. */
+    Numeric(String),
+    Array {
+        dimensions: Vec<Dimension>,
+        /** The elements, in row-major order. */
+        elements: Vec<Value>,
+    },
+    /** The attributes of a composite value and their names. */
+    Composite(Vec<(String, Value)>),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Null => f.write_str("NULL"),
+            Self::Bool(x) => f.write_str(if *x { "t" } else { "f" }),
+            Self::Int2(x) => write!(f, "{x}"),
+            Self::Int4(x) => write!(f, "{x}"),
+            Self::Int8(x) => write!(f, "{x}"),
+            Self::Oid(x) => write!(f, "{x}"),
+            Self::Float4(x) => write!(f, "{x}"),
+            Self::Float8(x) => write!(f, "{x}"),
+            Self::Text(x) | Self::Numeric(x) => f.write_str(x),
+            Self::Bytea(x) => {
+                f.write_str("\\x")?;
+                x.iter().try_for_each(|x| write!(f, "{x:02x}"))
+            }
+            Self::Uuid(x) => {
+                for (n, byte) in x.iter().enumerate() {
+                    if [4, 6, 8, 10].contains(&n) {
+                        f.write_str("-")?;
+                    }
+                    write!(f, "{byte:02x}")?;
+                }
+
+                Ok(())
+            }
+            Self::Date(x) => write!(f, "{x}"),
+            Self::Time(x) => write!(f, "{x}"),
+            Self::TimeTz(time, offset) => {
+                let sign = if *offset < 0 { '-' } else { '+' };
+                let offset = offset.unsigned_abs();
+
+                write!(f, "{time}{sign}{:02}", offset / 3600)?;
+                if offset % 3600 != 0 {
+                    write!(f, ":{:02}", offset % 3600 / 60)?;
+                }
+
+                Ok(())
+            }
+            Self::Timestamp(x) => write!(f, "{x}"),
+            Self::TimestampTz(x) => write!(f, "{x}+00"),
+            Self::Array { elements, .. } => {
+                f.write_str("{")?;
+                for (n, element) in elements.iter().enumerate() {
+                    if n > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                f.write_str("}")
+            }
+            Self::Composite(fields) => {
+                f.write_str("(")?;
+                for (n, (_, value)) in fields.iter().enumerate() {
+                    if n > 0 {
+                        f.write_str(",")?;
+                    }
+                    match value {
+                        Self::Null => (),
+                        value => {
+                            let value = value.to_string();
+
+                            if value.is_empty()
+                                || value.contains(|c: char| {
+                                    matches!(c, '(' | ')' | ',' | '"' | '\\') || c.is_whitespace()
+                                })
+                            {
+                                f.write_str("\"")?;
+                                for c in value.chars() {
+                                    if matches!(c, '"' | '\\') {
+                                        write!(f, "{c}")?;
+                                    }
+                                    write!(f, "{c}")?;
+                                }
+                                f.write_str("\"")?;
+                            } else {
+                                f.write_str(&value)?;
+                            }
+                        }
+                    }
+                }
+                f.write_str(")")
+            }
+        }
+    }
+}
+
+/**
+ * Size and lower bound of an array dimension.
+ */
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Dimension {
+    pub len: i32,
+    pub lower_bound: i32,
+}
+
+/**
+ * A date, in days since 2000-01-01.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Date(pub i32);
+
+impl Date {
+    pub fn is_infinite(&self) -> bool {
+        self.0 == i32::MAX || self.0 == i32::MIN
+    }
+
+    /**
+     * The proleptic Gregorian year, month and day, `None` for an infinite date. The year `0` is
+     * 1 BC.
+     */
+    pub fn ymd(&self) -> Option<(i64, u32, u32)> {
+        if self.is_infinite() {
+            return None;
+        }
+
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = self.0 as i64 + EPOCH_DAYS + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + i64::from(month <= 2);
+
+        Some((year, month, day))
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.ymd() {
+            Some((year, month, day)) if year <= 0 => {
+                write!(f, "{:04}-{month:02}-{day:02} BC", 1 - year)
+            }
+            Some((year, month, day)) => write!(f, "{year:04}-{month:02}-{day:02}"),
+            None if self.0 > 0 => f.write_str("infinity"),
+            None => f.write_str("-infinity"),
+        }
+    }
+}
+
+/**
+ * A time of day, in microseconds since midnight.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Time(pub i64);
+
+impl Time {
+    pub fn hms_micro(&self) -> (u32, u32, u32, u32) {
+        let secs = self.0 / 1_000_000;
+
+        (
+            (secs / 3600) as u32,
+            (secs / 60 % 60) as u32,
+            (secs % 60) as u32,
+            (self.0 % 1_000_000) as u32,
+        )
+    }
+}
+
+impl std::fmt::Display for Time {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (hour, minute, second, micro) = self.hms_micro();
+
+        write!(f, "{hour:02}:{minute:02}:{second:02}")?;
+        if micro != 0 {
+            let fraction = format!("{micro:06}");
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        Ok(())
+    }
+}
+
+/**
+ * A timestamp, in microseconds since 2000-01-01 00:00:00.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    pub fn is_infinite(&self) -> bool {
+        self.0 == i64::MAX || self.0 == i64::MIN
+    }
+
+    pub fn date(&self) -> Date {
+        Date(self.0.div_euclid(USECS_PER_DAY) as i32)
+    }
+
+    pub fn time(&self) -> Time {
+        Time(self.0.rem_euclid(USECS_PER_DAY))
+    }
+
+    /**
+     * The timestamp as a system time, `None` for an infinite timestamp.
+     */
+    pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+        if self.is_infinite() {
+            return None;
+        }
+
+        let micros = self.0.checked_add(EPOCH_DAYS * USECS_PER_DAY)?;
+        let duration = std::time::Duration::from_micros(micros.unsigned_abs());
+
+        if micros >= 0 {
+            std::time::UNIX_EPOCH.checked_add(duration)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(duration)
+        }
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_infinite() {
+            return f.write_str(if self.0 > 0 { "infinity" } else { "-infinity" });
+        }
+
+        match self.date().ymd() {
+            Some((year, month, day)) if year <= 0 => {
+                write!(f, "{:04}-{month:02}-{day:02} {} BC", 1 - year, self.time())
+            }
+            _ => write!(f, "{} {}", self.date(), self.time()),
+        }
+    }
+}
+
+/**
+ * Decodes `raw`, a non-null value of type `ty` in binary format.
+ *
+ * Returns an `Error::WrongType` error for a type without binary decoder.
+ */
+pub fn decode(ty: &crate::Type, raw: &[u8]) -> crate::errors::Result<Value> {
+    use crate::types::*;
+
+    let value = match ty.oid {
+        oid if oid == BOOL.oid => Value::Bool(fixed::<1>(raw)? != [0]),
+        oid if oid == INT2.oid => Value::Int2(i16::from_be_bytes(fixed(raw)?)),
+        oid if oid == INT4.oid => Value::Int4(i32::from_be_bytes(fixed(raw)?)),
+        oid if oid == INT8.oid => Value::Int8(i64::from_be_bytes(fixed(raw)?)),
+        oid if oid == OID.oid => Value::Oid(u32::from_be_bytes(fixed(raw)?)),
+        oid if oid == FLOAT4.oid => Value::Float4(f32::from_be_bytes(fixed(raw)?)),
+        oid if oid == FLOAT8.oid => Value::Float8(f64::from_be_bytes(fixed(raw)?)),
+        oid if [
+            TEXT.oid,
+            VARCHAR.oid,
+            BPCHAR.oid,
+            NAME.oid,
+            UNKNOWN.oid,
+            JSON.oid,
+            XML.oid,
+        ]
+        .contains(&oid) =>
+        {
+            Value::Text(std::str::from_utf8(raw)?.to_string())
+        }
+        oid if oid == JSONB.oid => match raw.split_first() {
+            Some((1, json)) => Value::Text(std::str::from_utf8(json)?.to_string()),
+            _ => return Err(invalid(ty, raw)),
+        },
+        oid if oid == BYTEA.oid => Value::Bytea(raw.to_vec()),
+        oid if oid == UUID.oid => Value::Uuid(fixed(raw)?),
+        oid if oid == DATE.oid => Value::Date(Date(i32::from_be_bytes(fixed(raw)?))),
+        oid if oid == TIME.oid => Value::Time(Time(i64::from_be_bytes(fixed(raw)?))),
+        oid if oid == TIMETZ.oid => {
+            let raw = fixed::<12>(raw)?;
+            let time = i64::from_be_bytes(raw[..8].try_into().unwrap_or_default());
+            // The wire offset is in seconds west of UTC.
+            let offset = i32::from_be_bytes(raw[8..].try_into().unwrap_or_default());
+
+            Value::TimeTz(Time(time), -offset)
+        }
+        oid if oid == TIMESTAMP.oid => Value::Timestamp(Timestamp(i64::from_be_bytes(fixed(raw)?))),
+        oid if oid == TIMESTAMPTZ.oid => {
+            Value::TimestampTz(Timestamp(i64::from_be_bytes(fixed(raw)?)))
+        }
+        oid if oid == NUMERIC.oid => Value::Numeric(numeric(raw).ok_or_else(|| invalid(ty, raw))?),
+        _ => match &ty.kind {
+            Kind::Array(element) => array(ty, *element, raw)?,
+            // An enum is sent as its label.
+            Kind::Enum(_) => Value::Text(std::str::from_utf8(raw)?.to_string()),
+            Kind::Composite(fields) if !fields.is_empty() => composite(ty, fields, raw)?,
+            _ => {
+                return Err(crate::errors::Error::WrongType {
+                    oid: ty.oid,
+                    rust_type: std::any::type_name::<Value>(),
+                })
+            }
+        },
+    };
+
+    Ok(value)
+}
+
+fn fixed<const N: usize>(raw: &[u8]) -> crate::errors::Result<[u8; N]> {
+    raw.try_into()
+        .map_err(|_| crate::errors::Error::Backend(format!("Invalid value length: {}", raw.len())))
+}
+
+fn invalid(ty: &crate::Type, raw: &[u8]) -> crate::errors::Error {
+    crate::errors::Error::Backend(format!("Invalid binary {} value: {raw:?}", ty.name))
+}
+
+/**
+ * Reads big-endian integers from a binary value.
+ */
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Some(bytes)
+    }
+
+    fn i16(&mut self) -> Option<i16> {
+        self.bytes(2)?.try_into().ok().map(i16::from_be_bytes)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.bytes(4)?.try_into().ok().map(i32::from_be_bytes)
+    }
+}
+
+/**
+ * Numeric: digit count, weight of the first digit, sign, display scale, then the base 10000
+ * digits.
+ */
+fn numeric(raw: &[u8]) -> Option<String> {
+    let mut reader = Reader(raw);
+    let ndigits = reader.i16()?;
+    let weight = reader.i16()? as i32;
+    let sign = reader.i16()? as u16;
+    // The server masks the display scale with `NUMERIC_DSCALE_MASK`.
+    let dscale = usize::try_from(reader.i16()?)
+        .ok()
+        .filter(|x| *x <= 0x3FFF)?;
+    let digits = (0..ndigits)
+        .map(|_| reader.i16().filter(|x| (0..10_000).contains(x)))
+        .collect::<Option<Vec<_>>>()?;
+
+    match sign {
+        0x0000 | 0x4000 => (),
+        0xC000 => return Some("NaN".to_string()),
+        0xD000 => return Some("Infinity".to_string()),
+        0xF000 => return Some("-Infinity".to_string()),
+        _ => return None,
+    }
+
+    let digit = |n: i32| {
+        usize::try_from(n)
+            .ok()
+            .and_then(|n| digits.get(n))
+            .copied()
+            .unwrap_or(0)
+    };
+
+    let mut s = String::new();
+
+    if sign == 0x4000 {
+        s.push('-');
+    }
+
+    if weight < 0 {
+        s.push('0');
+    } else {
+        s.push_str(&digit(0).to_string());
+        for n in 1..=weight {
+            s.push_str(&format!("{:04}", digit(n)));
+        }
+    }
+
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let mut n = weight + 1;
+
+        while fraction.len() < dscale {
+            fraction.push_str(&format!("{:04}", digit(n)));
+            n += 1;
+        }
+
+        s.push('.');
+        s.push_str(&fraction[..dscale]);
+    }
+
+    Some(s)
+}
+
+fn array(ty: &crate::Type, element: crate::Oid, raw: &[u8]) -> crate::errors::Result<Value> {
+    let element = crate::Type::try_from(element).map_err(crate::errors::Error::Backend)?;
+    let array = split_array(ty, &element, raw)?;
+
+    let elements = array
+        .elements
+        .into_iter()
+        .map(|x| match x {
+            Some(raw) => decode(&element, raw),
+            None => Ok(Value::Null),
+        })
+        .collect::<crate::errors::Result<_>>()?;
+
+    Ok(Value::Array {
+        dimensions: array.dimensions,
+        elements,
+    })
+}
+
+/**
+ * Composite: attribute count, then each attribute type and value prefixed by its length (`-1`
+ * for null).
+ */
+fn composite(
+    ty: &crate::Type,
+    fields: &[crate::types::Field],
+    raw: &[u8],
+) -> crate::errors::Result<Value> {
+    let mut reader = Reader(raw);
+
+    if reader.i32() != Some(fields.len() as i32) {
+        return Err(invalid(ty, raw));
+    }
+
+    let mut values = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let oid = reader.i32().ok_or_else(|| invalid(ty, raw))? as crate::Oid;
+        let len = reader.i32().ok_or_else(|| invalid(ty, raw))?;
+
+        if oid != field.ty.oid {
+            return Err(invalid(ty, raw));
+        }
+
+        let value = match usize::try_from(len) {
+            Ok(len) => decode(
+                &field.ty,
+                reader.bytes(len).ok_or_else(|| invalid(ty, raw))?,
+            )?,
+            Err(_) => Value::Null,
+        };
+
+        values.push((field.name.clone(), value));
+    }
+
+    Ok(Value::Composite(values))
+}
+
+/**
+ * A binary array split into its dimensions and raw elements.
+ */
+pub(crate) struct RawArray<'a> {
+    pub dimensions: Vec<Dimension>,
+    pub elements: Vec<Option<&'a [u8]>>,
+}
+
+/**
+ * Array: dimension count, null flag, element type, the dimensions, then the elements prefixed
+ * by their length (`-1` for null).
+ */
+pub(crate) fn split_array<'a>(
+    ty: &crate::Type,
+    element: &crate::Type,
+    raw: &'a [u8],
+) -> crate::errors::Result<RawArray<'a>> {
+    let mut reader = Reader(raw);
+    let header = (|| Some((reader.i32()?, reader.i32()?, reader.i32()? as u32)))();
+    let Some((ndim, _, oid)) = header else {
+        return Err(invalid(ty, raw));
+    };
+
+    if oid != element.oid {
+        return Err(crate::errors::Error::WrongType {
+            oid,
+            rust_type: element.name,
+        });
+    }
+
+    let mut dimensions = Vec::new();
+    for _ in 0..ndim {
+        let dimension = (|| {
+            Some(Dimension {
+                len: reader.i32()?,
+                lower_bound: reader.i32()?,
+            })
+        })();
+
+        dimensions.push(dimension.ok_or_else(|| invalid(ty, raw))?);
+    }
+
+    let count = if dimensions.is_empty() {
+        Some(0)
+    } else {
+        dimensions
+            .iter()
+            .try_fold(1_usize, |count, x| count.checked_mul(x.len.max(0) as usize))
+    };
+
+    // Each element takes at least its length, don't trust a count the value can't hold.
+    let count = count
+        .filter(|count| count.checked_mul(4).is_some_and(|x| x <= reader.0.len()))
+        .ok_or_else(|| invalid(ty, raw))?;
+
+    let mut elements = Vec::new();
+    for _ in 0..count {
+        let len = reader.i32().ok_or_else(|| invalid(ty, raw))?;
+
+        let value = if len < 0 {
+            None
+        } else {
+            Some(reader.bytes(len as usize).ok_or_else(|| invalid(ty, raw))?)
+        };
+
+        elements.push(value);
+    }
+
+    Ok(RawArray {
+        dimensions,
+        elements,
+    })
+}
+
+/**
+ * Builds a binary array of `element` values, the reverse of `split_array`.
+ */
+pub(crate) fn join_array(
+    element: &crate::Type,
+    dimensions: &[Dimension],
+    elements: &[Option<Vec<u8>>],
+) -> crate::errors::Result<Vec<u8>> {
+    let ndim =
+        i32::try_from(dimensions.len()).map_err(|_| out_of_range(element, dimensions.len()))?;
+    let has_null = elements.iter().any(Option::is_none);
+
+    let mut buf = Vec::with_capacity(12 + 8 * dimensions.len());
+    buf.extend_from_slice(&ndim.to_be_bytes());
+    buf.extend_from_slice(&i32::from(has_null).to_be_bytes());
+    buf.extend_from_slice(&element.oid.to_be_bytes());
+
+    for dimension in dimensions {
+        buf.extend_from_slice(&dimension.len.to_be_bytes());
+        buf.extend_from_slice(&dimension.lower_bound.to_be_bytes());
+    }
+
+    for value in elements {
+        match value {
+            Some(value) => {
+                let len =
+                    i32::try_from(value.len()).map_err(|_| out_of_range(element, value.len()))?;
+                buf.extend_from_slice(&len.to_be_bytes());
+                buf.extend_from_slice(value);
+            }
+            None => buf.extend_from_slice(&(-1_i32).to_be_bytes()),
+        }
+    }
+
+    Ok(buf)
+}
+
+/**
+ * A Rust value encodable in binary format.
+ *
+ * See `libpq::types::codec::encode`.
+ */
+pub trait Encode {
+    /**
+     * SQL type of the encoded values.
+     */
+    fn ty() -> crate::Type;
+
+    /**
+     * Encodes the value, `None` for a null value.
+     */
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>>;
+}
+
+/**
+ * A type usable as array element, see the `libpq::types::codec::Encode` implementation for
+ * slices.
+ */
+pub trait Element: Encode + Sized {
+    /**
+     * SQL type of the arrays of this type.
+     */
+    fn array_ty() -> crate::Type;
+}
+
+/**
+ * Encodes `value` in binary format, with its type.
+ *
+ * ```no_run
+ * # let conn = libpq::Connection::new("")?;
+ * let (oid, value) = libpq::types::codec::encode(&[1_i64, 2, 3][..])?;
+ *
+ * let result = conn.exec_params(
+ *     "SELECT array_length($1, 1)",
+ *     &[oid],
+ *     &[value.as_deref()],
+ *     &[libpq::Format::Binary],
+ *     libpq::Format::Text,
+ * );
+ * # Ok::<(), libpq::errors::Error>(())
+ * ```
+ */
+pub fn encode<T: Encode + ?Sized>(
+    value: &T,
+) -> crate::errors::Result<(crate::Oid, Option<Vec<u8>>)> {
+    Ok((T::ty().oid, value.encode()?))
+}
+
+fn out_of_range(ty: &crate::Type, value: impl std::fmt::Debug) -> crate::errors::Error {
+    crate::errors::Error::Backend(format!("{value:?} is out of range for type {}", ty.name))
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    fn ty() -> crate::Type {
+        T::ty()
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        (**self).encode()
+    }
+}
+
+impl<T: Element> Element for &T {
+    fn array_ty() -> crate::Type {
+        T::array_ty()
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn ty() -> crate::Type {
+        T::ty()
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        match self {
+            Some(value) => value.encode(),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<T: Element> Element for Option<T> {
+    fn array_ty() -> crate::Type {
+        T::array_ty()
+    }
+}
+
+macro_rules! encode {
+    ($rust:ty, $sql:ident, $array:ident, |$value:ident| $encode:expr) => {
+        impl Encode for $rust {
+            fn ty() -> crate::Type {
+                crate::types::$sql
+            }
+
+            fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+                let $value = self;
+
+                Ok(Some($encode))
+            }
+        }
+
+        impl Element for $rust {
+            fn array_ty() -> crate::Type {
+                crate::types::$array
+            }
+        }
+    };
+}
+
+encode!(bool, BOOL, BOOL_ARRAY, |x| vec![*x as u8]);
+encode!(i16, INT2, INT2_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(i32, INT4, INT4_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(i64, INT8, INT8_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(u32, OID, OID_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(f32, FLOAT4, FLOAT4_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(f64, FLOAT8, FLOAT8_ARRAY, |x| x.to_be_bytes().to_vec());
+encode!(String, TEXT, TEXT_ARRAY, |x| x.as_bytes().to_vec());
+encode!(Vec<u8>, BYTEA, BYTEA_ARRAY, |x| x.clone());
+encode!(Date, DATE, DATE_ARRAY, |x| x.0.to_be_bytes().to_vec());
+encode!(Time, TIME, TIME_ARRAY, |x| x.0.to_be_bytes().to_vec());
+encode!(Timestamp, TIMESTAMP, TIMESTAMP_ARRAY, |x| x
+    .0
+    .to_be_bytes()
+    .to_vec());
+
+impl Encode for str {
+    fn ty() -> crate::Type {
+        crate::types::TEXT
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        Ok(Some(self.as_bytes().to_vec()))
+    }
+}
+
+impl Encode for [u8] {
+    fn ty() -> crate::Type {
+        crate::types::BYTEA
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        Ok(Some(self.to_vec()))
+    }
+}
+
+impl Encode for std::time::SystemTime {
+    /**
+     * A system time is an absolute time, sent as `timestamptz`.
+     */
+    fn ty() -> crate::Type {
+        crate::types::TIMESTAMPTZ
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        let micros = match self.duration_since(std::time::UNIX_EPOCH) {
+            Ok(duration) => i64::try_from(duration.as_micros()).ok(),
+            Err(err) => i64::try_from(err.duration().as_micros()).ok().map(|x| -x),
+        }
+        .and_then(|x| x.checked_sub(EPOCH_DAYS * USECS_PER_DAY))
+        .ok_or_else(|| out_of_range(&Self::ty(), self))?;
+
+        Ok(Some(micros.to_be_bytes().to_vec()))
+    }
+}
+
+impl Element for std::time::SystemTime {
+    fn array_ty() -> crate::Type {
+        crate::types::TIMESTAMPTZ_ARRAY
+    }
+}
+
+/**
+ * One-dimensional arrays, with a lower bound of 1.
+ */
+impl<T: Element> Encode for [T] {
+    fn ty() -> crate::Type {
+        T::array_ty()
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        let elements = self
+            .iter()
+            .map(Encode::encode)
+            .collect::<crate::errors::Result<Vec<_>>>()?;
+
+        let dimensions = if self.is_empty() {
+            Vec::new()
+        } else {
+            let len =
+                i32::try_from(self.len()).map_err(|_| out_of_range(&Self::ty(), self.len()))?;
+
+            vec![Dimension {
+                len,
+                lower_bound: 1,
+            }]
+        };
+
+        join_array(&T::ty(), &dimensions, &elements).map(Some)
+    }
+}
+
+impl<T: Element> Encode for Vec<T> {
+    fn ty() -> crate::Type {
+        <[T]>::ty()
+    }
+
+    fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+        self.as_slice().encode()
+    }
+}
+
+#[cfg(feature = "uuid")]
+encode!(uuid::Uuid, UUID, UUID_ARRAY, |x| x.as_bytes().to_vec());
+
+#[cfg(feature = "chrono")]
+mod chrono_codec {
+    use super::{Element, Encode};
+
+    fn epoch() -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap_or_default()
+            .and_hms_opt(0, 0, 0)
+            .unwrap_or_default()
+    }
+
+    fn micros(ty: &crate::Type, value: chrono::NaiveDateTime) -> crate::errors::Result<Vec<u8>> {
+        let micros = (value - epoch())
+            .num_microseconds()
+            .ok_or_else(|| super::out_of_range(ty, value))?;
+
+        Ok(micros.to_be_bytes().to_vec())
+    }
+
+    impl Encode for chrono::NaiveDate {
+        fn ty() -> crate::Type {
+            crate::types::DATE
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            let days = i32::try_from((*self - epoch().date()).num_days())
+                .map_err(|_| super::out_of_range(&Self::ty(), self))?;
+
+            Ok(Some(days.to_be_bytes().to_vec()))
+        }
+    }
+
+    impl Element for chrono::NaiveDate {
+        fn array_ty() -> crate::Type {
+            crate::types::DATE_ARRAY
+        }
+    }
+
+    impl Encode for chrono::NaiveTime {
+        fn ty() -> crate::Type {
+            crate::types::TIME
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            use chrono::Timelike;
+
+            // A leap second is sent as the next second.
+            let micros = i64::from(self.num_seconds_from_midnight()) * 1_000_000
+                + i64::from(self.nanosecond()) / 1_000;
+
+            Ok(Some(micros.to_be_bytes().to_vec()))
+        }
+    }
+
+    impl Element for chrono::NaiveTime {
+        fn array_ty() -> crate::Type {
+            crate::types::TIME_ARRAY
+        }
+    }
+
+    impl Encode for chrono::NaiveDateTime {
+        fn ty() -> crate::Type {
+            crate::types::TIMESTAMP
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            micros(&Self::ty(), *self).map(Some)
+        }
+    }
+
+    impl Element for chrono::NaiveDateTime {
+        fn array_ty() -> crate::Type {
+            crate::types::TIMESTAMP_ARRAY
+        }
+    }
+
+    impl<Tz: chrono::TimeZone> Encode for chrono::DateTime<Tz> {
+        fn ty() -> crate::Type {
+            crate::types::TIMESTAMPTZ
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            micros(&Self::ty(), self.naive_utc()).map(Some)
+        }
+    }
+
+    impl<Tz: chrono::TimeZone> Element for chrono::DateTime<Tz> {
+        fn array_ty() -> crate::Type {
+            crate::types::TIMESTAMPTZ_ARRAY
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_codec {
+    use super::{Element, Encode};
+
+    fn epoch() -> time::Date {
+        time::Date::from_calendar_date(2000, time::Month::January, 1).unwrap_or(time::Date::MIN)
+    }
+
+    fn micros(ty: &crate::Type, value: time::PrimitiveDateTime) -> crate::errors::Result<Vec<u8>> {
+        let epoch = time::PrimitiveDateTime::new(epoch(), time::Time::MIDNIGHT);
+        let micros = i64::try_from((value - epoch).whole_microseconds())
+            .map_err(|_| super::out_of_range(ty, value))?;
+
+        Ok(micros.to_be_bytes().to_vec())
+    }
+
+    impl Encode for time::Date {
+        fn ty() -> crate::Type {
+            crate::types::DATE
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            let days = i32::try_from((*self - epoch()).whole_days())
+                .map_err(|_| super::out_of_range(&Self::ty(), self))?;
+
+            Ok(Some(days.to_be_bytes().to_vec()))
+        }
+    }
+
+    impl Element for time::Date {
+        fn array_ty() -> crate::Type {
+            crate::types::DATE_ARRAY
+        }
+    }
+
+    impl Encode for time::Time {
+        fn ty() -> crate::Type {
+            crate::types::TIME
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            let micros = (*self - time::Time::MIDNIGHT).whole_microseconds() as i64;
+
+            Ok(Some(micros.to_be_bytes().to_vec()))
+        }
+    }
+
+    impl Element for time::Time {
+        fn array_ty() -> crate::Type {
+            crate::types::TIME_ARRAY
+        }
+    }
+
+    impl Encode for time::PrimitiveDateTime {
+        fn ty() -> crate::Type {
+            crate::types::TIMESTAMP
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            micros(&Self::ty(), *self).map(Some)
+        }
+    }
+
+    impl Element for time::PrimitiveDateTime {
+        fn array_ty() -> crate::Type {
+            crate::types::TIMESTAMP_ARRAY
+        }
+    }
+
+    impl Encode for time::OffsetDateTime {
+        fn ty() -> crate::Type {
+            crate::types::TIMESTAMPTZ
+        }
+
+        fn encode(&self) -> crate::errors::Result<Option<Vec<u8>>> {
+            let utc = self.to_offset(time::UtcOffset::UTC);
+
+            micros(
+                &Self::ty(),
+                time::PrimitiveDateTime::new(utc.date(), utc.time()),
+            )
+            .map(Some)
+        }
+    }
+
+    impl Element for time::OffsetDateTime {
+        fn array_ty() -> crate::Type {
+            crate::types::TIMESTAMPTZ_ARRAY
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Value;
+
+    fn decode(query: &str) -> Value {
+        let conn = crate::test::new_conn();
+        let result = conn.exec_params(query, &[], &[], &[], crate::Format::Binary);
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+
+        let ty = crate::Type::try_from(result.field_type(0)).unwrap();
+        match result.value(0, 0) {
+            Some(raw) => super::decode(&ty, raw).unwrap(),
+            None => Value::Null,
+        }
+    }
+
+    #[test]
+    fn scalars() {
+        assert_eq!(decode("SELECT true"), Value::Bool(true));
+        assert_eq!(decode("SELECT 2::int2"), Value::Int2(2));
+        assert_eq!(decode("SELECT -4::int4"), Value::Int4(-4));
+        assert_eq!(decode("SELECT 8::int8"), Value::Int8(8));
+        assert_eq!(decode("SELECT 1.5::float8"), Value::Float8(1.5));
+        assert_eq!(
+            decode("SELECT 'foo'::varchar"),
+            Value::Text("foo".to_string())
+        );
+        assert_eq!(
+            decode("SELECT '{\"a\": 1}'::jsonb"),
+            Value::Text("{\"a\": 1}".to_string())
+        );
+        assert_eq!(
+            decode("SELECT '\\x00ff'::bytea"),
+            Value::Bytea(vec![0, 255])
+        );
+        assert_eq!(
+            decode("SELECT 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid").to_string(),
+            "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"
+        );
+    }
+
+    #[test]
+    fn datetime() {
+        assert_eq!(
+            decode("SELECT '2000-01-02'::date"),
+            Value::Date(super::Date(1))
+        );
+        assert_eq!(
+            decode("SELECT '2024-02-29'::date").to_string(),
+            "2024-02-29"
+        );
+        assert_eq!(
+            decode("SELECT '0044-03-15 BC'::date").to_string(),
+            "0044-03-15 BC"
+        );
+        assert_eq!(decode("SELECT 'infinity'::date").to_string(), "infinity");
+        assert_eq!(
+            decode("SELECT '13:14:15.5'::time").to_string(),
+            "13:14:15.5"
+        );
+        assert_eq!(
+            decode("SELECT '13:14:15-05:30'::timetz").to_string(),
+            "13:14:15-05:30"
+        );
+        assert_eq!(
+            decode("SELECT '1999-12-31 23:59:59.000001'::timestamp").to_string(),
+            "1999-12-31 23:59:59.000001"
+        );
+        assert_eq!(
+            decode("SELECT '2024-01-01 12:00:00+02'::timestamptz").to_string(),
+            "2024-01-01 10:00:00+00"
+        );
+
+        let Value::TimestampTz(timestamp) = decode("SELECT 'epoch'::timestamptz") else {
+            panic!();
+        };
+        assert_eq!(timestamp.to_system_time(), Some(std::time::UNIX_EPOCH));
+    }
+
+    #[test]
+    fn numeric() {
+        for value in [
+            "0",
+            "1",
+            "-12345.678",
+            "0.0001",
+            "100000000",
+            "3.14159265358979",
+            "NaN",
+        ] {
+            assert_eq!(
+                decode(&format!("SELECT '{value}'::numeric")).to_string(),
+                value
+            );
+        }
+
+        assert_eq!(decode("SELECT 1.50::numeric(10, 3)").to_string(), "1.500");
+    }
+
+    #[test]
+    fn array() {
+        assert_eq!(
+            decode("SELECT '{{1,NULL},{3,4}}'::int4[]"),
+            Value::Array {
+                dimensions: vec![
+                    super::Dimension {
+                        len: 2,
+                        lower_bound: 1
+                    },
+                    super::Dimension {
+                        len: 2,
+                        lower_bound: 1
+                    },
+                ],
+                elements: vec![Value::Int4(1), Value::Null, Value::Int4(3), Value::Int4(4)],
+            }
+        );
+        assert_eq!(
+            decode("SELECT '{}'::text[]"),
+            Value::Array {
+                dimensions: Vec::new(),
+                elements: Vec::new(),
+            }
+        );
+    }
+
+    fn encode<T: super::Encode + ?Sized>(value: &T) -> String {
+        let conn = crate::test::new_conn();
+        conn.exec("SET TimeZone = 'UTC'");
+        let (oid, value) = super::encode(value).unwrap();
+
+        let result = conn.exec_params(
+            "SELECT $1::text",
+            &[oid],
+            &[value.as_deref()],
+            &[crate::Format::Binary],
+            crate::Format::Text,
+        );
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+
+        result
+            .get::<Option<String>>(0, 0)
+            .unwrap()
+            .unwrap_or_else(|| "NULL".to_string())
+    }
+
+    #[test]
+    fn encode_scalars() {
+        assert_eq!(encode(&true), "true");
+        assert_eq!(encode(&-2_i16), "-2");
+        assert_eq!(encode(&8_i64), "8");
+        assert_eq!(encode(&1.5_f64), "1.5");
+        assert_eq!(encode("foo"), "foo");
+        assert_eq!(encode(&b"\0\xff"[..]), "\\x00ff");
+        assert_eq!(encode(&None::<i32>), "NULL");
+        assert_eq!(encode(&super::Date(-1)), "1999-12-31");
+        assert_eq!(encode(&super::Time(1_500_000)), "00:00:01.5");
+        assert_eq!(encode(&std::time::UNIX_EPOCH), "1970-01-01 00:00:00+00");
+    }
+
+    #[test]
+    fn encode_array() {
+        assert_eq!(encode(&[1_i64, 2, 3][..]), "{1,2,3}");
+        assert_eq!(
+            encode(&vec![Some("a b".to_string()), None]),
+            "{\"a b\",NULL}"
+        );
+        assert_eq!(encode(&Vec::<f64>::new()), "{}");
+    }
+
+    #[test]
+    #[cfg(feature = "uuid")]
+    fn encode_uuid() {
+        let uuid = uuid::Uuid::parse_str("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11").unwrap();
+
+        assert_eq!(encode(&uuid), "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11");
+    }
+
+    #[test]
+    #[cfg(feature = "chrono")]
+    fn encode_chrono() {
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        let datetime = date.and_hms_micro_opt(13, 14, 15, 500).unwrap();
+
+        assert_eq!(encode(&date), "2024-02-29");
+        assert_eq!(encode(&datetime.time()), "13:14:15.0005");
+        assert_eq!(encode(&datetime), "2024-02-29 13:14:15.0005");
+        assert_eq!(encode(&datetime.and_utc()), "2024-02-29 13:14:15.0005+00");
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn encode_time() {
+        let date = time::Date::from_calendar_date(1999, time::Month::December, 31).unwrap();
+        let datetime = date.with_hms_micro(23, 59, 59, 1).unwrap();
+
+        assert_eq!(encode(&date), "1999-12-31");
+        assert_eq!(encode(&datetime.time()), "23:59:59.000001");
+        assert_eq!(encode(&datetime), "1999-12-31 23:59:59.000001");
+        assert_eq!(
+            encode(&datetime.assume_offset(time::UtcOffset::from_hms(1, 0, 0).unwrap())),
+            "1999-12-31 22:59:59.000001+00"
+        );
+    }
+
+    #[test]
+    fn composite() {
+        let conn = crate::test::new_conn();
+        conn.exec_checked("CREATE TYPE pg_temp.mood AS ENUM ('sad', 'ok')")
+            .unwrap();
+        conn.exec_checked("CREATE TYPE pg_temp.pair AS (a int4, b text, c pg_temp.mood)")
+            .unwrap();
+
+        let result = conn
+            .exec_params_checked(
+                r#"SELECT (1, 'a "b"', 'ok')::pg_temp.pair, (NULL, '', 'sad')::pg_temp.pair"#,
+                &[],
+                &[],
+                &[],
+                crate::Format::Binary,
+            )
+            .unwrap();
+        let ty = crate::Type::from_database(&conn, result.field_type(0)).unwrap();
+
+        let value = super::decode(&ty, result.value(0, 0).unwrap()).unwrap();
+        assert_eq!(
+            value,
+            Value::Composite(vec![
+                ("a".to_string(), Value::Int4(1)),
+                ("b".to_string(), Value::Text(r#"a "b""#.to_string())),
+                ("c".to_string(), Value::Text("ok".to_string())),
+            ])
+        );
+        assert_eq!(value.to_string(), r#"(1,"a ""b""",ok)"#);
+
+        let value = super::decode(&ty, result.value(0, 1).unwrap()).unwrap();
+        assert_eq!(value.to_string(), r#"(,"",sad)"#);
+    }
+
+    #[test]
+    fn malformed() {
+        let raw = |values: &[i32]| {
+            values
+                .iter()
+                .flat_map(|x| x.to_be_bytes())
+                .collect::<Vec<_>>()
+        };
+        let array = crate::types::INT4_ARRAY;
+
+        let huge = raw(&[1, 0, crate::types::INT4.oid as i32, i32::MAX, 1]);
+        assert!(super::decode(&array, &huge).is_err());
+
+        let overflow = raw(&[
+            2,
+            0,
+            crate::types::INT4.oid as i32,
+            i32::MAX,
+            1,
+            i32::MAX,
+            1,
+        ]);
+        assert!(super::decode(&array, &overflow).is_err());
+
+        let truncated = raw(&[1, 0, crate::types::INT4.oid as i32, 2, 1, 4, 1]);
+        assert!(super::decode(&array, &truncated).is_err());
+
+        let numeric = |values: &[i16]| {
+            let raw = values
+                .iter()
+                .flat_map(|x| x.to_be_bytes())
+                .collect::<Vec<_>>();
+            super::decode(&crate::types::NUMERIC, &raw)
+        };
+        assert!(numeric(&[0, 0, 0, -1]).is_err());
+        assert!(numeric(&[0, 0, 0, 0x4000]).is_err());
+        assert!(numeric(&[1, 0, 0, 0, 10_000]).is_err());
+        assert!(numeric(&[2, 0, 0, 0, 1]).is_err());
+        assert!(numeric(&[0, 0, 0x1234, 0]).is_err());
+        assert_eq!(
+            numeric(&[1, 0, 0, 6, 1]).unwrap(),
+            Value::Numeric("1.000000".to_string())
+        );
+
+        assert!(super::decode(&crate::types::INT4, &[0, 0, 1]).is_err());
+        assert!(super::decode(&crate::types::TEXT, &[0xff]).is_err());
+        assert!(super::decode(&crate::types::TIMETZ, &[0; 8]).is_err());
+        assert!(super::decode(&crate::types::JSONB, &[2, b'1']).is_err());
+    }
+
+    #[test]
+    fn unsupported() {
+        let conn = crate::test::new_conn();
+        let result = conn.exec_params(
+            "SELECT '1 day'::interval",
+            &[],
+            &[],
+            &[],
+            crate::Format::Binary,
+        );
+        let ty = crate::Type::try_from(result.field_type(0)).unwrap();
+
+        assert!(matches!(
+            super::decode(&ty, result.value(0, 0).unwrap()),
+            Err(crate::errors::Error::WrongType { .. })
+        ));
+    }
+}
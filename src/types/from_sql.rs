@@ -0,0 +1,247 @@
+/**
+ * A type that can be decoded from a result value.
+ *
+ * See `libpq::PQResult::get`.
+ */
+pub trait FromSql: Sized {
+    /**
+     * Decodes a non-null value of the `ty` SQL type, received in `format`.
+     */
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self>;
+
+    /**
+     * Decodes a null value, returns an `Error::UnexpectedNull` error by default.
+     */
+    fn from_null(_ty: crate::Oid) -> crate::errors::Result<Self> {
+        Err(crate::errors::Error::UnexpectedNull)
+    }
+}
+
+/**
+ * Decodes a non-null value like `FromSql::from_sql`, falling back to the text decoding when `T`
+ * doesn't decode `ty` in binary format: the value is converted to its text representation by
+ * `libpq::types::codec::decode`.
+ */
+pub(crate) fn decode<T: FromSql>(
+    ty: crate::Oid,
+    format: crate::Format,
+    raw: &[u8],
+) -> crate::errors::Result<T> {
+    let err = match T::from_sql(ty, format, raw) {
+        Err(err @ crate::errors::Error::WrongType { .. }) if format == crate::Format::Binary => err,
+        result => return result,
+    };
+
+    let Some(text) = crate::Type::try_from(ty)
+        .ok()
+        .and_then(|ty| crate::types::codec::decode(&ty, raw).ok())
+        .map(|value| value.to_string())
+    else {
+        return Err(err);
+    };
+
+    log::trace!("Decoding binary value of type {ty} from its text representation '{text}'");
+    T::from_sql(ty, crate::Format::Text, text.as_bytes())
+}
+
+fn wrong_type<T>(ty: crate::Oid) -> crate::errors::Error {
+    crate::errors::Error::WrongType {
+        oid: ty,
+        rust_type: std::any::type_name::<T>(),
+    }
+}
+
+fn text(raw: &[u8]) -> crate::errors::Result<&str> {
+    Ok(std::str::from_utf8(raw)?)
+}
+
+fn binary<const N: usize>(raw: &[u8]) -> crate::errors::Result<[u8; N]> {
+    raw.try_into()
+        .map_err(|_| crate::errors::Error::Backend(format!("Invalid value length: {}", raw.len())))
+}
+
+impl<T: FromSql> FromSql for Option<T> {
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self> {
+        T::from_sql(ty, format, raw).map(Some)
+    }
+
+    fn from_null(_: crate::Oid) -> crate::errors::Result<Self> {
+        Ok(None)
+    }
+}
+
+impl FromSql for bool {
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self> {
+        if ty != crate::types::BOOL.oid {
+            return Err(wrong_type::<Self>(ty));
+        }
+
+        match (format, raw) {
+            (crate::Format::Text, b"t") | (crate::Format::Binary, [1]) => Ok(true),
+            (crate::Format::Text, b"f") | (crate::Format::Binary, [0]) => Ok(false),
+            _ => Err(crate::errors::Error::Backend(format!(
+                "Invalid boolean value: {raw:?}"
+            ))),
+        }
+    }
+}
+
+macro_rules! from_sql_number {
+    ($rust:ty, $($sql:ident => $binary:ty),+) => {
+        impl FromSql for $rust {
+            fn from_sql(
+                ty: crate::Oid,
+                format: crate::Format,
+                raw: &[u8],
+            ) -> crate::errors::Result<Self> {
+                $(
+                    if ty == crate::types::$sql.oid {
+                        return match format {
+                            crate::Format::Text => Ok(text(raw)?.parse()?),
+                            crate::Format::Binary => {
+                                Ok(<$binary>::from_be_bytes(binary(raw)?) as $rust)
+                            }
+                        };
+                    }
+                )+
+
+                Err(wrong_type::<Self>(ty))
+            }
+        }
+    };
+}
+
+from_sql_number!(i16, INT2 => i16);
+from_sql_number!(i32, INT4 => i32, INT2 => i16);
+from_sql_number!(i64, INT8 => i64, INT4 => i32, INT2 => i16);
+from_sql_number!(u32, OID => u32);
+from_sql_number!(f32, FLOAT4 => f32);
+from_sql_number!(f64, FLOAT8 => f64, FLOAT4 => f32);
+
+impl FromSql for String {
+    /**
+     * Any value is accepted in text format, only textual types in binary format.
+     */
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self> {
+        let textual = [
+            crate::types::TEXT.oid,
+            crate::types::VARCHAR.oid,
+            crate::types::BPCHAR.oid,
+            crate::types::NAME.oid,
+            crate::types::UNKNOWN.oid,
+            crate::types::JSON.oid,
+            crate::types::XML.oid,
+        ];
+
+        if format == crate::Format::Binary && !textual.contains(&ty) {
+            return Err(wrong_type::<Self>(ty));
+        }
+
+        text(raw).map(ToString::to_string)
+    }
+}
+
+impl FromSql for Vec<u8> {
+    fn from_sql(ty: crate::Oid, format: crate::Format, raw: &[u8]) -> crate::errors::Result<Self> {
+        if ty != crate::types::BYTEA.oid {
+            return Err(wrong_type::<Self>(ty));
+        }
+
+        match format {
+            crate::Format::Text => crate::escape::decode_bytea(raw),
+            crate::Format::Binary => Ok(raw.to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn text() {
+        let conn = crate::test::new_conn();
+        let result = conn.exec(
+            "SELECT true, 2::int2, 4::int4, 8::int8, 1.5::float4, 2.5::float8, 'foo', '\\x00ff'::bytea, NULL::int4, 26::oid",
+        );
+
+        assert_eq!(result.get::<bool>(0, 0), Ok(true));
+        assert_eq!(result.get::<i16>(0, 1), Ok(2));
+        assert_eq!(result.get::<i32>(0, 2), Ok(4));
+        assert_eq!(result.get::<i64>(0, 2), Ok(4));
+        assert_eq!(result.get::<i64>(0, 3), Ok(8));
+        assert_eq!(result.get::<f32>(0, 4), Ok(1.5));
+        assert_eq!(result.get::<f64>(0, 5), Ok(2.5));
+        assert_eq!(result.get::<String>(0, 6), Ok("foo".to_string()));
+        assert_eq!(result.get::<String>(0, 3), Ok("8".to_string()));
+        assert_eq!(result.get::<Vec<u8>>(0, 7), Ok(vec![0, 255]));
+        assert_eq!(result.get::<Option<i32>>(0, 8), Ok(None));
+        assert_eq!(result.get::<Option<i32>>(0, 2), Ok(Some(4)));
+        assert_eq!(result.get::<u32>(0, 9), Ok(26));
+
+        assert_eq!(
+            result.get::<i32>(0, 8),
+            Err(crate::errors::Error::UnexpectedNull)
+        );
+        assert_eq!(
+            result.get::<i32>(0, 3),
+            Err(crate::errors::Error::WrongType {
+                oid: crate::types::INT8.oid,
+                rust_type: "i32",
+            })
+        );
+    }
+
+    #[test]
+    fn binary() {
+        let conn = crate::test::new_conn();
+        let result = conn.exec_params(
+            "SELECT true, 2::int2, 4::int4, 8::int8, 1.5::float4, 2.5::float8, 'foo', '\\x00ff'::bytea",
+            &[],
+            &[],
+            &[],
+            crate::Format::Binary,
+        );
+
+        assert_eq!(result.get::<bool>(0, 0), Ok(true));
+        assert_eq!(result.get::<i16>(0, 1), Ok(2));
+        assert_eq!(result.get::<i64>(0, 1), Ok(2));
+        assert_eq!(result.get::<i32>(0, 2), Ok(4));
+        assert_eq!(result.get::<i64>(0, 3), Ok(8));
+        assert_eq!(result.get::<f32>(0, 4), Ok(1.5));
+        assert_eq!(result.get::<f64>(0, 4), Ok(1.5));
+        assert_eq!(result.get::<f64>(0, 5), Ok(2.5));
+        assert_eq!(result.get::<String>(0, 6), Ok("foo".to_string()));
+        assert_eq!(result.get::<Vec<u8>>(0, 7), Ok(vec![0, 255]));
+
+        assert!(result.get::<Vec<u8>>(0, 3).is_err());
+    }
+
+    #[test]
+    fn binary_fallback() {
+        let conn = crate::test::new_conn();
+        let result = conn.exec_params(
+            "SELECT 1.50::numeric, '2024-02-29'::date, 8::int8, '{1.5,NULL}'::numeric[], '1 day'::interval",
+            &[],
+            &[],
+            &[],
+            crate::Format::Binary,
+        );
+
+        assert_eq!(result.get::<String>(0, 0), Ok("1.50".to_string()));
+        assert_eq!(result.get::<String>(0, 1), Ok("2024-02-29".to_string()));
+        assert_eq!(result.get::<String>(0, 2), Ok("8".to_string()));
+        assert_eq!(
+            result
+                .get::<crate::types::Array<String>>(0, 3)
+                .map(crate::types::Array::into_elements),
+            Ok(vec![Some("1.5".to_string()), None])
+        );
+
+        // No binary decoder for intervals.
+        assert!(matches!(
+            result.get::<String>(0, 4),
+            Err(crate::errors::Error::WrongType { .. })
+        ));
+        // The text representation doesn't help either.
+        assert!(result.get::<i64>(0, 0).is_err());
+    }
+}
@@ -1,11 +1,24 @@
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub mod codec;
+
+mod array;
+mod from_sql;
+mod registry;
+mod to_param;
+
+pub use array::*;
+pub use from_sql::*;
+pub use to_param::*;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Kind {
     Array(crate::Oid),
     BitString,
     Boolean,
-    Composite,
+    /** The attributes, empty for the builtin types. */
+    Composite(Vec<Field>),
     DateTime,
-    Enum,
+    /** The labels, in sort order, empty for the builtin types. */
+    Enum(Vec<String>),
     Geometric,
     Internal,
     Network,
@@ -18,6 +31,15 @@ pub enum Kind {
     UserDefined,
 }
 
+/**
+ * An attribute of a composite type.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    pub name: String,
+    pub ty: Type,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Type {
     pub oid: crate::Oid,
@@ -0,0 +1,192 @@
+impl crate::Type {
+    /**
+     * Returns the type `oid`, looking up in the catalog of the database the types unknown to
+     * this crate, like the user-defined ones.
+     *
+     * The attributes of a composite type and the labels of an enum are filled in, so values
+     * of these types can be decoded by `libpq::types::codec::decode`. Types are cached per
+     * connection: a type altered after the first lookup isn't seen.
+     *
+     * ```no_run
+     * # let conn = libpq::Connection::new("")?;
+     * let result = conn.exec_params_checked("SELECT 'ok'::mood", &[], &[], &[], libpq::Format::Binary)?;
+     * let ty = libpq::Type::from_database(&conn, result.field_type(0))?;
+     *
+     * if let libpq::types::Kind::Enum(labels) = &ty.kind {
+     *     println!("{}: {}", ty.name, labels.join(", "));
+     * }
+     * # Ok::<(), libpq::errors::Error>(())
+     * ```
+     */
+    pub fn from_database(conn: &crate::Connection, oid: crate::Oid) -> crate::errors::Result<Self> {
+        if let Ok(ty) = Self::try_from(oid) {
+            return Ok(ty);
+        }
+
+        if let Some(ty) = conn.types().borrow().get(&oid) {
+            return Ok(ty.clone());
+        }
+
+        let ty = lookup(conn, oid)?;
+        conn.types().borrow_mut().insert(oid, ty.clone());
+
+        Ok(ty)
+    }
+}
+
+fn lookup(conn: &crate::Connection, oid: crate::Oid) -> crate::errors::Result<crate::Type> {
+    log::trace!("Looking up type {oid}");
+
+    let result = conn.exec_checked(&format!(
+        "SELECT t.typname, t.typtype::text, t.typcategory::text, t.typelem, t.typrelid,
+                coalesce(r.rngsubtype, 0::oid),
+                coalesce(obj_description(t.oid, 'pg_type'), format_type(t.oid, NULL))
+            FROM pg_catalog.pg_type t
+            LEFT JOIN pg_catalog.pg_range r ON r.rngtypid = t.oid
+            WHERE t.oid = {oid}"
+    ))?;
+
+    if result.ntuples() == 0 {
+        return Err(crate::errors::Error::Backend(format!("Unknow type {oid}")));
+    }
+
+    let name = result.get::<String>(0, 0)?;
+    let category = result.get::<String>(0, 2)?;
+
+    let kind = match result.get::<String>(0, 1)?.as_str() {
+        "c" => crate::types::Kind::Composite(fields(conn, result.get(0, 4)?)?),
+        "e" => crate::types::Kind::Enum(labels(conn, oid)?),
+        _ => match category.as_str() {
+            "A" => crate::types::Kind::Array(result.get(0, 3)?),
+            "B" => crate::types::Kind::Boolean,
+            "D" => crate::types::Kind::DateTime,
+            "G" => crate::types::Kind::Geometric,
+            "I" => crate::types::Kind::Network,
+            "N" => crate::types::Kind::Numeric,
+            "P" => crate::types::Kind::Pseudo,
+            "S" => crate::types::Kind::String,
+            "R" => crate::types::Kind::Range(result.get(0, 5)?),
+            "T" => crate::types::Kind::Timestamp,
+            "V" => crate::types::Kind::BitString,
+            "Z" => crate::types::Kind::Internal,
+            "U" => crate::types::Kind::UserDefined,
+            _ => crate::types::Kind::Unknow,
+        },
+    };
+
+    Ok(crate::Type {
+        oid,
+        descr: intern(result.get(0, 6)?),
+        name: intern(name),
+        kind,
+    })
+}
+
+/**
+ * The attributes of the composite type of the relation `relid`, in order.
+ */
+fn fields(
+    conn: &crate::Connection,
+    relid: crate::Oid,
+) -> crate::errors::Result<Vec<crate::types::Field>> {
+    let result = conn.exec_checked(&format!(
+        "SELECT attname, atttypid
+            FROM pg_catalog.pg_attribute
+            WHERE attrelid = {relid} AND attnum > 0 AND NOT attisdropped
+            ORDER BY attnum"
+    ))?;
+
+    (0..result.ntuples())
+        .map(|row| {
+            Ok(crate::types::Field {
+                name: result.get(row, 0)?,
+                ty: crate::Type::from_database(conn, result.get(row, 1)?)?,
+            })
+        })
+        .collect()
+}
+
+fn labels(conn: &crate::Connection, oid: crate::Oid) -> crate::errors::Result<Vec<String>> {
+    let result = conn.exec_checked(&format!(
+        "SELECT enumlabel FROM pg_catalog.pg_enum WHERE enumtypid = {oid} ORDER BY enumsortorder"
+    ))?;
+
+    (0..result.ntuples())
+        .map(|row| result.get(row, 0))
+        .collect()
+}
+
+/**
+ * `libpq::Type` names are static: the names read from the database are leaked, once per
+ * distinct name.
+ */
+fn intern(s: String) -> &'static str {
+    static NAMES: std::sync::Mutex<std::collections::BTreeSet<&'static str>> =
+        std::sync::Mutex::new(std::collections::BTreeSet::new());
+
+    let mut names = NAMES.lock().unwrap_or_else(|x| x.into_inner());
+
+    match names.get(s.as_str()) {
+        Some(name) => name,
+        None => {
+            let name = Box::leak(s.into_boxed_str());
+            names.insert(name);
+            name
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    fn oid(conn: &crate::Connection, ty: &str) -> crate::Oid {
+        conn.exec_checked(&format!("SELECT '{ty}'::regtype::oid"))
+            .unwrap()
+            .get(0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn from_database() {
+        let conn = crate::test::new_conn();
+        conn.exec_checked("CREATE TYPE pg_temp.mood AS ENUM ('sad', 'ok')")
+            .unwrap();
+        conn.exec_checked("CREATE TYPE pg_temp.pair AS (a int4, b text, c pg_temp.mood)")
+            .unwrap();
+        conn.exec_checked("ALTER TYPE pg_temp.pair DROP ATTRIBUTE b")
+            .unwrap();
+
+        assert_eq!(
+            crate::Type::from_database(&conn, crate::types::INT4.oid),
+            Ok(crate::types::INT4)
+        );
+
+        let mood = crate::Type::from_database(&conn, oid(&conn, "pg_temp.mood")).unwrap();
+        assert_eq!(mood.name, "mood");
+        assert_eq!(
+            mood.kind,
+            crate::types::Kind::Enum(vec!["sad".to_string(), "ok".to_string()])
+        );
+
+        let pair = crate::Type::from_database(&conn, oid(&conn, "pg_temp.pair")).unwrap();
+        assert_eq!(pair.name, "pair");
+        assert_eq!(
+            pair.kind,
+            crate::types::Kind::Composite(vec![
+                crate::types::Field {
+                    name: "a".to_string(),
+                    ty: crate::types::INT4,
+                },
+                crate::types::Field {
+                    name: "c".to_string(),
+                    ty: mood.clone(),
+                },
+            ])
+        );
+        assert_eq!(conn.types().borrow().len(), 2);
+
+        let array = crate::Type::from_database(&conn, oid(&conn, "pg_temp.mood[]")).unwrap();
+        assert_eq!(array.kind, crate::types::Kind::Array(mood.oid));
+
+        assert!(crate::Type::from_database(&conn, 0).is_err());
+    }
+}
@@ -0,0 +1,469 @@
+/**
+ * A Rust value usable as query parameter.
+ *
+ * See `libpq::Connection::exec_typed`.
+ */
+pub trait ToParam {
+    /**
+     * The parameter SQL type, `0` lets the server infer it.
+     */
+    fn oid(&self) -> crate::Oid;
+
+    /**
+     * Format of the encoded value, `libpq::Format::for_oid` of the type by default: binary
+     * when the crate has a codec for it, text otherwise.
+     */
+    fn format(&self) -> crate::Format {
+        crate::Format::for_oid(self.oid())
+    }
+
+    /**
+     * Encodes the value, `None` for a null value.
+     *
+     * Text values don't have to be NUL terminated, but can't contain a NUL byte.
+     */
+    fn to_param(&self) -> Option<Vec<u8>>;
+
+    /**
+     * Rust type of the value, used to find the connection default type of untyped parameters:
+     * `Some(TypeId::of::<Self>())` to be matched by `set_param_type::<Self>`. `None`, the
+     * default, if no default type applies.
+     *
+     * See `libpq::Connection::set_param_type`.
+     */
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        None
+    }
+
+    /**
+     * The SQL type of `None::<Self>`, `0` by default to let the server infer it.
+     *
+     * Like `libpq::types::ToParam::null_param_type_id`, a null reference has no type: use an
+     * owned type like `None::<i64>`.
+     */
+    fn type_oid() -> crate::Oid
+    where
+        Self: Sized,
+    {
+        0
+    }
+
+    /**
+     * `libpq::types::ToParam::param_type_id` of `None::<Self>`.
+     *
+     * A null reference, like `None::<&str>`, has no Rust type to use: use an owned type like
+     * `None::<String>`.
+     */
+    fn null_param_type_id() -> Option<std::any::TypeId>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+impl<T: ToParam + ?Sized> ToParam for &T {
+    fn oid(&self) -> crate::Oid {
+        (**self).oid()
+    }
+
+    fn format(&self) -> crate::Format {
+        (**self).format()
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        (**self).to_param()
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        (**self).param_type_id()
+    }
+}
+
+impl<T: ToParam> ToParam for Option<T> {
+    /**
+     * The type of a null value is `libpq::types::ToParam::type_oid` of `T`.
+     */
+    fn oid(&self) -> crate::Oid {
+        match self {
+            Some(value) => value.oid(),
+            None => T::type_oid(),
+        }
+    }
+
+    fn format(&self) -> crate::Format {
+        self.as_ref()
+            .map(ToParam::format)
+            .unwrap_or(crate::Format::Text)
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        self.as_ref().and_then(ToParam::to_param)
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        match self {
+            Some(value) => value.param_type_id(),
+            None => T::null_param_type_id(),
+        }
+    }
+
+    fn type_oid() -> crate::Oid {
+        T::type_oid()
+    }
+
+    fn null_param_type_id() -> Option<std::any::TypeId> {
+        T::null_param_type_id()
+    }
+}
+
+impl ToParam for bool {
+    fn oid(&self) -> crate::Oid {
+        crate::types::BOOL.oid
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        Some(vec![*self as u8])
+    }
+
+    fn type_oid() -> crate::Oid {
+        crate::types::BOOL.oid
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        Self::null_param_type_id()
+    }
+
+    fn null_param_type_id() -> Option<std::any::TypeId> {
+        Some(std::any::TypeId::of::<Self>())
+    }
+}
+
+macro_rules! to_param_number {
+    ($rust:ty, $sql:ident) => {
+        impl ToParam for $rust {
+            fn oid(&self) -> crate::Oid {
+                crate::types::$sql.oid
+            }
+
+            fn to_param(&self) -> Option<Vec<u8>> {
+                Some(self.to_be_bytes().to_vec())
+            }
+
+            fn type_oid() -> crate::Oid {
+                crate::types::$sql.oid
+            }
+
+            fn param_type_id(&self) -> Option<std::any::TypeId> {
+                Self::null_param_type_id()
+            }
+
+            fn null_param_type_id() -> Option<std::any::TypeId> {
+                Some(std::any::TypeId::of::<Self>())
+            }
+        }
+    };
+}
+
+to_param_number!(i16, INT2);
+to_param_number!(i32, INT4);
+to_param_number!(i64, INT8);
+to_param_number!(u32, OID);
+to_param_number!(f32, FLOAT4);
+to_param_number!(f64, FLOAT8);
+
+impl ToParam for str {
+    /**
+     * Strings are sent as untyped literals, so they can be used for any parameter type.
+     */
+    fn oid(&self) -> crate::Oid {
+        0
+    }
+
+    fn format(&self) -> crate::Format {
+        crate::Format::Text
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        Some(self.as_bytes().to_vec())
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        Some(std::any::TypeId::of::<Self>())
+    }
+}
+
+impl ToParam for String {
+    fn oid(&self) -> crate::Oid {
+        self.as_str().oid()
+    }
+
+    fn format(&self) -> crate::Format {
+        self.as_str().format()
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        self.as_str().to_param()
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        Self::null_param_type_id()
+    }
+
+    fn null_param_type_id() -> Option<std::any::TypeId> {
+        Some(std::any::TypeId::of::<Self>())
+    }
+}
+
+impl ToParam for [u8] {
+    fn oid(&self) -> crate::Oid {
+        crate::types::BYTEA.oid
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        Some(self.to_vec())
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        Some(std::any::TypeId::of::<Self>())
+    }
+}
+
+impl ToParam for Vec<u8> {
+    fn oid(&self) -> crate::Oid {
+        self.as_slice().oid()
+    }
+
+    fn format(&self) -> crate::Format {
+        self.as_slice().format()
+    }
+
+    fn to_param(&self) -> Option<Vec<u8>> {
+        self.as_slice().to_param()
+    }
+
+    fn type_oid() -> crate::Oid {
+        crate::types::BYTEA.oid
+    }
+
+    fn param_type_id(&self) -> Option<std::any::TypeId> {
+        Self::null_param_type_id()
+    }
+
+    fn null_param_type_id() -> Option<std::any::TypeId> {
+        Some(std::any::TypeId::of::<Self>())
+    }
+}
+
+/**
+ * Parameters encoded for `libpq::Connection::exec_params`.
+ */
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Params {
+    pub types: Vec<crate::Oid>,
+    pub type_ids: Vec<Option<std::any::TypeId>>,
+    pub values: Vec<Option<Vec<u8>>>,
+    pub formats: Vec<crate::Format>,
+    /** First text value containing a NUL byte, libpq would truncate it. */
+    pub error: Option<std::ffi::NulError>,
+}
+
+impl Params {
+    pub fn new(params: &[&dyn ToParam]) -> Self {
+        let mut this = Self::with_capacity(params.len());
+
+        for param in params {
+            this.push(*param);
+        }
+
+        this
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            types: Vec::with_capacity(capacity),
+            type_ids: Vec::with_capacity(capacity),
+            values: Vec::with_capacity(capacity),
+            formats: Vec::with_capacity(capacity),
+            error: None,
+        }
+    }
+
+    pub fn push(&mut self, param: &dyn ToParam) {
+        let format = param.format();
+        let mut value = param.to_param();
+
+        if let (crate::Format::Text, Some(value)) = (format, &mut value) {
+            match std::ffi::CString::new(std::mem::take(value)) {
+                Ok(s) => *value = s.into_bytes_with_nul(),
+                Err(err) => {
+                    self.error.get_or_insert_with(|| err.clone());
+                    *value = err.into_vec();
+                }
+            }
+        }
+
+        self.types.push(param.oid());
+        self.type_ids.push(param.param_type_id());
+        self.values.push(value);
+        self.formats.push(format);
+    }
+
+    pub fn len(&self) -> usize {
+        self.types.len()
+    }
+
+    /**
+     * Returns an error if a text value contains a NUL byte.
+     */
+    pub fn check(&self) -> crate::errors::Result {
+        match &self.error {
+            Some(err) => Err(err.clone().into()),
+            None => Ok(()),
+        }
+    }
+
+    pub fn values(&self) -> Vec<Option<&[u8]>> {
+        self.values.iter().map(Option::as_deref).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn exec_typed() {
+        let conn = crate::test::new_conn();
+
+        let result = conn
+            .exec_typed(
+                "SELECT $1, $2, $3, $4, $5, $6::int4, $7, $8",
+                &[
+                    &true,
+                    &2_i16,
+                    &4_i32,
+                    &8_i64,
+                    &1.5_f64,
+                    &"42",
+                    &b"\0\xff".to_vec(),
+                    &None::<i32>,
+                ],
+            )
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+
+        assert_eq!(result.get::<bool>(0, 0), Ok(true));
+        assert_eq!(result.get::<i16>(0, 1), Ok(2));
+        assert_eq!(result.get::<i32>(0, 2), Ok(4));
+        assert_eq!(result.get::<i64>(0, 3), Ok(8));
+        assert_eq!(result.get::<f64>(0, 4), Ok(1.5));
+        assert_eq!(result.get::<i32>(0, 5), Ok(42));
+        assert_eq!(result.get::<Vec<u8>>(0, 6), Ok(vec![0, 255]));
+        assert_eq!(result.get::<Option<String>>(0, 7), Ok(None));
+    }
+
+    #[test]
+    fn send_query_typed() {
+        let conn = crate::test::new_conn();
+
+        conn.send_query_typed("SELECT $1 || $2", &[&"foo", &String::from("bar")])
+            .unwrap();
+
+        let result = conn.result().unwrap();
+        assert_eq!(result.get::<String>(0, 0), Ok("foobar".to_string()));
+        assert!(conn.result().is_none());
+    }
+
+    #[test]
+    fn nul() {
+        let conn = crate::test::new_conn();
+
+        assert!(matches!(
+            conn.exec_typed("SELECT $1::text", &[&"a\0b"]),
+            Err(crate::errors::Error::NulError(_))
+        ));
+        assert!(matches!(
+            conn.send_query_typed("SELECT $1::text, $2", &[&1_i32, &String::from("\0")]),
+            Err(crate::errors::Error::NulError(_))
+        ));
+
+        let result = conn
+            .exec_typed("SELECT $1::bytea", &[&b"a\0b".to_vec()])
+            .unwrap();
+        assert_eq!(result.get::<Vec<u8>>(0, 0), Ok(b"a\0b".to_vec()));
+    }
+
+    #[test]
+    fn param_types() {
+        let conn = crate::test::new_conn();
+
+        let result = conn
+            .exec_typed("SELECT $1 IS NULL", &[&None::<String>])
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::FatalError);
+
+        conn.set_param_type::<str>(crate::types::TEXT.oid);
+        conn.set_param_type::<String>(crate::types::VARCHAR.oid);
+
+        let result = conn
+            .exec_typed(
+                "SELECT pg_typeof($1)::text, pg_typeof($2)::text, $3 IS NULL",
+                &[&"a", &String::from("b"), &None::<String>],
+            )
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+        assert_eq!(result.get::<String>(0, 0), Ok("text".to_string()));
+        assert_eq!(
+            result.get::<String>(0, 1),
+            Ok("character varying".to_string())
+        );
+        assert_eq!(result.get::<bool>(0, 2), Ok(true));
+
+        let string = String::from("b");
+        let params: [&dyn crate::types::ToParam; 6] = [
+            &&"a",
+            &Some("a"),
+            &Some(&string),
+            &Some(String::from("b")),
+            &None::<String>,
+            &Some(None::<i64>),
+        ];
+        assert_eq!(
+            conn.param_types(&super::Params::new(&params)),
+            [
+                crate::types::TEXT.oid,
+                crate::types::TEXT.oid,
+                crate::types::VARCHAR.oid,
+                crate::types::VARCHAR.oid,
+                crate::types::VARCHAR.oid,
+                crate::types::INT8.oid,
+            ]
+        );
+        assert_eq!(
+            conn.param_types(&super::Params::new(&[&None::<&str>, &None::<i32>])),
+            [0, crate::types::INT4.oid]
+        );
+
+        conn.unset_param_type::<String>();
+        let result = conn
+            .exec_typed("SELECT $1 IS NULL", &[&None::<String>])
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::FatalError);
+    }
+
+    #[test]
+    fn null() {
+        let conn = crate::test::new_conn();
+
+        let result = conn
+            .exec_typed(
+                "SELECT $1 IS NULL, pg_typeof($1)::text, pg_typeof($2)::text, pg_typeof($3)::text",
+                &[&None::<i64>, &Some(None::<bool>), &None::<Vec<u8>>],
+            )
+            .unwrap();
+        assert_eq!(result.status(), crate::Status::TuplesOk);
+        assert_eq!(result.get::<bool>(0, 0), Ok(true));
+        assert_eq!(result.get::<String>(0, 1), Ok("bigint".to_string()));
+        assert_eq!(result.get::<String>(0, 2), Ok("boolean".to_string()));
+        assert_eq!(result.get::<String>(0, 3), Ok("bytea".to_string()));
+    }
+}